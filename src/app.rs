@@ -2,15 +2,31 @@ use gtk4::prelude::*;
 use gtk4::subclass::prelude::*;
 use gtk4::{gio, glib};
 use libadwaita as adw;
+use std::ops::ControlFlow;
 
-use crate::ui::AsusctlGuiWindow;
+use crate::backend::{self, KeyboardBrightness, PowerProfile};
+use crate::change_notify::ChangeNotifier;
+use crate::profile_sync::ProfileSync;
+use crate::tray::StatusIcon;
+use crate::ui::{AsusctlGuiWindow, PROFILE_ACCELS, Page, color_scheme_from_setting};
 
 mod imp {
     use super::*;
     use adw::subclass::prelude::*;
+    use std::cell::{Cell, RefCell};
 
     #[derive(Debug, Default)]
-    pub struct AsusctlGuiApp;
+    pub struct AsusctlGuiApp {
+        // Startup settings are applied once per process, not on every activation
+        pub startup_applied: Cell<bool>,
+        pub settings: RefCell<Option<gio::Settings>>,
+        pub profile_sync: RefCell<Option<ProfileSync>>,
+        pub change_notifier: RefCell<Option<ChangeNotifier>>,
+        /// Status icon, and the hold that keeps the app alive without a window
+        pub background: RefCell<Option<(StatusIcon, gio::ApplicationHoldGuard)>>,
+        /// Page given with --page, shown instead of the startup page
+        pub requested_page: Cell<Option<Page>>,
+    }
 
     #[glib::object_subclass]
     impl ObjectSubclass for AsusctlGuiApp {
@@ -19,18 +35,79 @@ mod imp {
         type ParentType = adw::Application;
     }
 
-    impl ObjectImpl for AsusctlGuiApp {}
+    impl ObjectImpl for AsusctlGuiApp {
+        fn constructed(&self) {
+            self.parent_constructed();
+            self.obj().setup_actions();
+        }
+    }
 
     impl ApplicationImpl for AsusctlGuiApp {
+        fn handle_local_options(&self, options: &glib::VariantDict) -> ControlFlow<glib::ExitCode> {
+            // Headless mode: print the current state and exit without opening a window
+            if options.contains("status") {
+                return match backend::get_status_json() {
+                    Ok(json) => {
+                        println!("{json}");
+                        ControlFlow::Break(glib::ExitCode::SUCCESS)
+                    }
+                    Err(e) => {
+                        log::error!("Failed to gather status: {e}");
+                        ControlFlow::Break(glib::ExitCode::FAILURE)
+                    }
+                };
+            }
+
+            if let Ok(Some(name)) = options.lookup::<String>("page") {
+                let Ok(page) = Page::try_from(name.as_str()) else {
+                    let names: Vec<&str> = Page::ALL.iter().map(|page| page.as_str()).collect();
+                    eprintln!("Unknown page '{name}'. Valid pages: {}", names.join(", "));
+                    return ControlFlow::Break(glib::ExitCode::FAILURE);
+                };
+
+                // An instance that is already running switches its own window
+                let obj = self.obj();
+                if obj.register(gio::Cancellable::NONE).is_ok() && obj.is_remote() {
+                    obj.activate_action("show-page", Some(&page.as_str().to_variant()));
+                    return ControlFlow::Break(glib::ExitCode::SUCCESS);
+                }
+                self.requested_page.set(Some(page));
+            }
+
+            self.parent_handle_local_options(options)
+        }
+
         fn activate(&self) {
             let obj = self.obj();
             let app: &adw::Application = obj.upcast_ref();
 
             // Set up keyboard shortcuts
             app.set_accels_for_action("win.quit", &["<Control>q"]);
+            app.set_accels_for_action("win.preferences", &["<Control>comma"]);
             app.set_accels_for_action("win.show-shortcuts", &["<Control>question"]);
+            app.set_accels_for_action("win.refresh", &["F5", "<Control>r"]);
+            for (profile, accel) in PowerProfile::ALL.iter().zip(PROFILE_ACCELS) {
+                let name = profile.to_string().to_lowercase();
+                app.set_accels_for_action(&format!("win.set-profile::{name}"), &[accel]);
+            }
+
+            // Before the window is built, so it never shows in the wrong style
+            adw::StyleManager::default().set_color_scheme(color_scheme_from_setting(
+                &obj.settings().string("color-scheme"),
+            ));
+
+            // Apply startup settings before the pages read their initial state
+            if !self.startup_applied.replace(true) {
+                obj.apply_startup_settings();
+                obj.setup_profile_sync();
+                obj.setup_change_notifier();
+                obj.setup_background();
+            }
 
             let window = AsusctlGuiWindow::new(app);
+            if let Some(page) = self.requested_page.take() {
+                window.show_page(page);
+            }
             window.present();
         }
     }
@@ -47,8 +124,202 @@ glib::wrapper! {
 
 impl AsusctlGuiApp {
     pub fn new() -> Self {
-        glib::Object::builder()
+        let app: Self = glib::Object::builder()
             .property("application-id", "com.github.bl4ckspell7.asusctl-gui")
-            .build()
+            .build();
+
+        app.add_main_option(
+            "status",
+            glib::Char::from(b's'),
+            glib::OptionFlags::NONE,
+            glib::OptionArg::None,
+            "Print the current state as JSON and exit",
+            None,
+        );
+        app.add_main_option(
+            "page",
+            glib::Char::from(b'p'),
+            glib::OptionFlags::NONE,
+            glib::OptionArg::String,
+            "Open on the given page: about, aura, power, fan-curves, slash or anime",
+            Some("NAME"),
+        );
+
+        app
+    }
+
+    fn setup_actions(&self) {
+        // Used by --page when another instance is already running
+        let show_page = gio::SimpleAction::new("show-page", Some(glib::VariantTy::STRING));
+        let app_weak = self.downgrade();
+        show_page.connect_activate(move |_, parameter| {
+            let Some(app) = app_weak.upgrade() else {
+                return;
+            };
+            let Some(page) = parameter
+                .and_then(|p| p.str())
+                .and_then(|name| Page::try_from(name).ok())
+            else {
+                return;
+            };
+
+            match app.active_window().and_downcast::<AsusctlGuiWindow>() {
+                Some(window) => {
+                    window.show_page(page);
+                    window.present();
+                }
+                None => {
+                    app.imp().requested_page.set(Some(page));
+                    app.activate();
+                }
+            }
+        });
+        self.add_action(&show_page);
+    }
+
+    fn settings(&self) -> gio::Settings {
+        self.imp()
+            .settings
+            .borrow_mut()
+            .get_or_insert_with(|| gio::Settings::new("com.github.bl4ckspell7.asusctl-gui"))
+            .clone()
+    }
+
+    /// Apply the configured profile and keyboard brightness if enabled in preferences
+    ///
+    /// The writes run on the backend queue, so the window opens right away
+    /// and its first reads still see the applied values.
+    fn apply_startup_settings(&self) {
+        let settings = self.settings();
+        if !settings.boolean("apply-on-startup") {
+            return;
+        }
+
+        let profile = settings
+            .string("startup-profile")
+            .parse::<PowerProfile>()
+            .inspect_err(|e| log::warn!("Invalid startup profile: {e}"))
+            .ok();
+        let level = settings
+            .string("startup-keyboard-brightness")
+            .parse::<KeyboardBrightness>()
+            .inspect_err(|e| log::warn!("Invalid startup keyboard brightness: {e}"))
+            .ok();
+
+        // Nobody waits for the result; failures are only logged
+        drop(backend::queue::submit(move || {
+            if let Some(profile) = profile
+                && let Err(e) = backend::set_profile(profile)
+            {
+                log::error!("Failed to apply startup profile: {e}");
+            }
+            if let Some(level) = level
+                && let Err(e) = backend::set_keyboard_brightness(level)
+            {
+                log::error!("Failed to apply startup keyboard brightness: {e}");
+            }
+        }));
+    }
+
+    /// Start GNOME/asusd profile mirroring if enabled and follow the setting afterwards
+    fn setup_profile_sync(&self) {
+        let settings = self.settings();
+        self.set_profile_sync_enabled(settings.boolean("sync-power-profiles"));
+
+        let app_weak = self.downgrade();
+        settings.connect_changed(Some("sync-power-profiles"), move |settings, key| {
+            if let Some(app) = app_weak.upgrade() {
+                app.set_profile_sync_enabled(settings.boolean(key));
+            }
+        });
+    }
+
+    fn set_profile_sync_enabled(&self, enabled: bool) {
+        let imp = self.imp();
+
+        if !enabled {
+            // Dropping the watcher unsubscribes from both services
+            imp.profile_sync.replace(None);
+            return;
+        }
+
+        if imp.profile_sync.borrow().is_some() {
+            return;
+        }
+
+        match ProfileSync::start() {
+            Ok(sync) => {
+                imp.profile_sync.replace(Some(sync));
+            }
+            Err(e) => log::error!("Failed to start power profile sync: {e}"),
+        }
+    }
+
+    /// Watch for external changes if notifications are enabled, following the setting
+    fn setup_change_notifier(&self) {
+        let settings = self.settings();
+        self.set_change_notifier_enabled(settings.boolean("notify-on-change"));
+
+        let app_weak = self.downgrade();
+        settings.connect_changed(Some("notify-on-change"), move |settings, key| {
+            if let Some(app) = app_weak.upgrade() {
+                app.set_change_notifier_enabled(settings.boolean(key));
+            }
+        });
+    }
+
+    fn set_change_notifier_enabled(&self, enabled: bool) {
+        let imp = self.imp();
+
+        if !enabled {
+            imp.change_notifier.replace(None);
+            return;
+        }
+
+        if imp.change_notifier.borrow().is_some() {
+            return;
+        }
+
+        match ChangeNotifier::start() {
+            Ok(notifier) => {
+                imp.change_notifier.replace(Some(notifier));
+            }
+            Err(e) => log::error!("Failed to watch for external changes: {e}"),
+        }
+    }
+
+    /// Show the status icon if enabled and follow the setting afterwards
+    fn setup_background(&self) {
+        let settings = self.settings();
+        self.set_background_enabled(settings.boolean("run-in-background"));
+
+        let app_weak = self.downgrade();
+        settings.connect_changed(Some("run-in-background"), move |settings, key| {
+            if let Some(app) = app_weak.upgrade() {
+                app.set_background_enabled(settings.boolean(key));
+            }
+        });
+    }
+
+    fn set_background_enabled(&self, enabled: bool) {
+        let imp = self.imp();
+
+        if !enabled {
+            // Without a window left, this also ends the app
+            imp.background.replace(None);
+            return;
+        }
+
+        if imp.background.borrow().is_some() {
+            return;
+        }
+
+        // Only keep running when the icon is there to bring the window back
+        match StatusIcon::start() {
+            Ok(icon) => {
+                imp.background.replace(Some((icon, self.hold())));
+            }
+            Err(e) => log::warn!("Failed to show the status icon: {e}"),
+        }
     }
 }
@@ -1,3 +1,23 @@
+mod anime;
 mod asusctl;
+mod backup;
+mod battery;
+mod fan_curves;
+mod gpu;
+mod lighting_link;
+mod mock;
+pub mod queue;
+mod sensors;
+mod service;
+mod supergfx;
 
+pub use anime::*;
 pub use asusctl::*;
+pub use backup::*;
+pub use battery::*;
+pub use fan_curves::*;
+pub use gpu::*;
+pub use lighting_link::*;
+pub use sensors::*;
+pub use service::*;
+pub use supergfx::*;
@@ -0,0 +1,182 @@
+//! AniMe Matrix LED panel through asusd's Anime interface.
+//!
+//! The panel is its own asusd object, found at runtime like the Aura and
+//! Slash devices. Besides turning the display on and off and setting its
+//! brightness, asusd can play built-in animations on boot, while awake,
+//! on sleep and on shutdown; each event has a choice of two animations.
+
+use std::sync::OnceLock;
+
+use zbus::zvariant::Value;
+
+use super::asusctl::{
+    FromDbus, bus_names, discover_device, read_dbus_property_at, write_dbus_property_at,
+};
+use super::{AsusctlError, Result};
+
+static ANIME_PATH: OnceLock<Option<String>> = OnceLock::new();
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum AnimeBrightness {
+    Off,
+    Low,
+    #[default]
+    Med,
+    High,
+}
+
+impl AnimeBrightness {
+    pub const ALL: [AnimeBrightness; 4] = [Self::Off, Self::Low, Self::Med, Self::High];
+
+    fn from_u32(value: u32) -> Result<Self> {
+        match value {
+            0 => Ok(Self::Off),
+            1 => Ok(Self::Low),
+            2 => Ok(Self::Med),
+            3 => Ok(Self::High),
+            _ => Err(AsusctlError::ParseError(format!(
+                "Unknown AniMe brightness: {value}"
+            ))),
+        }
+    }
+
+    fn as_u32(&self) -> u32 {
+        match self {
+            Self::Off => 0,
+            Self::Low => 1,
+            Self::Med => 2,
+            Self::High => 3,
+        }
+    }
+}
+
+impl std::fmt::Display for AnimeBrightness {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Off => write!(f, "Off"),
+            Self::Low => write!(f, "Low"),
+            Self::Med => write!(f, "Medium"),
+            Self::High => write!(f, "High"),
+        }
+    }
+}
+
+/// Events asusd plays a built-in animation for
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AnimeEvent {
+    Boot,
+    Awake,
+    Sleep,
+    Shutdown,
+}
+
+impl AnimeEvent {
+    pub const ALL: [AnimeEvent; 4] = [Self::Boot, Self::Awake, Self::Sleep, Self::Shutdown];
+
+    /// Names of the two animations available for this event, by index
+    pub fn animations(&self) -> [&'static str; 2] {
+        match self {
+            Self::Boot => ["Glitch Construction", "Static Emergence"],
+            Self::Awake => ["Binary Banner Scroll", "ROG Logo Glitch"],
+            Self::Sleep => ["Banner Swipe", "Starfield"],
+            Self::Shutdown => ["Glitch Out", "See Ya"],
+        }
+    }
+}
+
+impl std::fmt::Display for AnimeEvent {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Boot => write!(f, "Boot"),
+            Self::Awake => write!(f, "Awake"),
+            Self::Sleep => write!(f, "Sleep"),
+            Self::Shutdown => write!(f, "Shutdown"),
+        }
+    }
+}
+
+/// Selected animation index for each event, in `AnimeEvent::ALL` order
+pub type AnimeBuiltins = [u8; 4];
+
+/// AniMe object path, or `Unsupported` when the laptop has no panel
+fn anime_path() -> Result<&'static String> {
+    discover_device(
+        &ANIME_PATH,
+        bus_names().platform_path,
+        bus_names().anime_interface,
+    )?
+    .ok_or_else(|| AsusctlError::Unsupported("no AniMe D-Bus path".to_string()))
+}
+
+fn read_anime_property<T: FromDbus>(property: &str) -> Result<T> {
+    read_dbus_property_at(anime_path()?, bus_names().anime_interface, property)
+}
+
+fn write_anime_property<'a>(property: &str, value: impl Into<Value<'a>>) -> Result<()> {
+    write_dbus_property_at(anime_path()?, bus_names().anime_interface, property, value)
+}
+
+/// Whether the panel is switched on
+pub fn get_anime_enabled() -> Result<bool> {
+    read_anime_property("EnableDisplay")
+}
+
+pub fn set_anime_enabled(enabled: bool) -> Result<()> {
+    write_anime_property("EnableDisplay", enabled)
+}
+
+pub fn get_anime_brightness() -> Result<AnimeBrightness> {
+    AnimeBrightness::from_u32(read_anime_property("Brightness")?)
+}
+
+pub fn set_anime_brightness(brightness: AnimeBrightness) -> Result<()> {
+    write_anime_property("Brightness", brightness.as_u32())
+}
+
+/// Whether asusd plays the built-in animations at all
+pub fn get_anime_builtins_enabled() -> Result<bool> {
+    read_anime_property("BuiltinsEnabled")
+}
+
+pub fn set_anime_builtins_enabled(enabled: bool) -> Result<()> {
+    write_anime_property("BuiltinsEnabled", enabled)
+}
+
+pub fn get_anime_builtins() -> Result<AnimeBuiltins> {
+    let (boot, awake, sleep, shutdown): (u8, u8, u8, u8) =
+        read_anime_property("BuiltinAnimations")?;
+    Ok([boot, awake, sleep, shutdown])
+}
+
+pub fn set_anime_builtins(builtins: AnimeBuiltins) -> Result<()> {
+    if let Some(index) = builtins.iter().find(|&&index| index > 1) {
+        return Err(AsusctlError::ParseError(format!(
+            "Unknown built-in animation: {index}"
+        )));
+    }
+
+    let [boot, awake, sleep, shutdown] = builtins;
+    write_anime_property("BuiltinAnimations", (boot, awake, sleep, shutdown))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_anime_brightness_round_trip() {
+        for brightness in AnimeBrightness::ALL {
+            assert_eq!(
+                AnimeBrightness::from_u32(brightness.as_u32()).unwrap(),
+                brightness
+            );
+        }
+        assert!(AnimeBrightness::from_u32(4).is_err());
+    }
+
+    #[test]
+    fn test_set_anime_builtins_rejects_unknown_index() {
+        let result = set_anime_builtins([0, 1, 2, 0]);
+        assert!(matches!(result, Err(AsusctlError::ParseError(_))));
+    }
+}
@@ -6,29 +6,89 @@
 //!
 //! State reading strategy:
 //! - Platform (profiles, charge limit): D-Bus via xyz.ljones.Platform
-//! - Slash: Config file at /etc/asusd/slash.ron (D-Bus fallback)
+//! - Slash: D-Bus via xyz.ljones.Slash (config file /etc/asusd/slash.ron as fallback)
 //! - Aura/Keyboard brightness: D-Bus via xyz.ljones.Aura
+//!
+//! D-Bus calls go through a shared zbus connection to the system bus and
+//! decode replies into Rust types.
 
+use serde::{Deserialize, Serialize};
 use std::fs;
-use std::process::Command;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Output, Stdio};
 use std::str::FromStr;
-use std::sync::OnceLock;
-
-// D-Bus constants
-const DBUS_DEST: &str = "xyz.ljones.Asusd";
-const PLATFORM_PATH: &str = "/xyz/ljones";
-const PLATFORM_INTERFACE: &str = "xyz.ljones.Platform";
-const AURA_BASE_PATH: &str = "/xyz/ljones/aura";
-const AURA_INTERFACE: &str = "xyz.ljones.Aura";
-const SLASH_INTERFACE: &str = "xyz.ljones.Slash";
+use std::sync::{Mutex, OnceLock, PoisonError, RwLock};
+use std::thread;
+use std::time::{Duration, Instant};
+use zbus::blocking::{Connection, Proxy, connection, fdo, proxy};
+use zbus::names::BusName;
+use zbus::proxy::CacheProperties;
+use zbus::zvariant::{self, OwnedStructure, OwnedValue, Structure, Value};
+
+use super::SettingsBackup;
+use super::mock;
+
+/// D-Bus names used by one generation of asusd
+#[derive(Debug)]
+pub struct BusNames {
+    pub dest: &'static str,
+    pub platform_path: &'static str,
+    pub platform_interface: &'static str,
+    /// Aura devices are children of this path
+    pub aura_base_path: &'static str,
+    pub aura_interface: &'static str,
+    pub slash_interface: &'static str,
+    pub anime_interface: &'static str,
+    /// Served at `platform_path`
+    pub fan_curves_interface: &'static str,
+}
+
+/// Known asusd bus names, newest first
+const KNOWN_BUS_NAMES: &[BusNames] = &[
+    BusNames {
+        dest: "xyz.ljones.Asusd",
+        platform_path: "/xyz/ljones",
+        platform_interface: "xyz.ljones.Platform",
+        aura_base_path: "/xyz/ljones/aura",
+        aura_interface: "xyz.ljones.Aura",
+        slash_interface: "xyz.ljones.Slash",
+        anime_interface: "xyz.ljones.Anime",
+        fan_curves_interface: "xyz.ljones.FanCurves",
+    },
+    // asusd 5.x and older
+    BusNames {
+        dest: "org.asuslinux.Daemon",
+        platform_path: "/org/asuslinux",
+        platform_interface: "org.asuslinux.Platform",
+        aura_base_path: "/org/asuslinux",
+        aura_interface: "org.asuslinux.Aura",
+        slash_interface: "org.asuslinux.Slash",
+        anime_interface: "org.asuslinux.Anime",
+        fan_curves_interface: "org.asuslinux.FanCurves",
+    },
+];
+
+/// Timeout for a single D-Bus call; asusd answers well within this
+const DBUS_TIMEOUT: Duration = Duration::from_secs(5);
 
 // Config file paths (fallback)
 const SLASH_CONFIG_PATH: &str = "/etc/asusd/slash.ron";
+/// Environment variable pointing at slash.ron for non-standard installs
+const SLASH_CONFIG_ENV_VAR: &str = "ASUSD_SLASH_CONFIG";
 
-// Cached D-Bus paths (discovered at runtime)
+// Cached D-Bus names and paths (discovered at runtime)
+/// `Some(None)` once a lookup found no daemon; see `invalidate_session_cache`
+static BUS_NAMES: Mutex<Option<Option<&'static BusNames>>> = Mutex::new(None);
 static AURA_PATH: OnceLock<Option<String>> = OnceLock::new();
 static SLASH_PATH: OnceLock<Option<String>> = OnceLock::new();
 
+// asusctl output that doesn't change while asusd keeps running
+static SYSTEM_INFO: Mutex<Option<SystemInfo>> = Mutex::new(None);
+static SUPPORTED_FEATURES: Mutex<Option<SupportedFeatures>> = Mutex::new(None);
+/// Latest value this app wrote to each asusd property it reports changes of
+static OWN_WRITES: Mutex<Vec<OwnWrite>> = Mutex::new(Vec::new());
+
 // ============================================================================
 // Error Types
 // ============================================================================
@@ -43,6 +103,10 @@ pub enum AsusctlError {
     CommandFailed(String),
     /// Failed to parse command output
     ParseError(String),
+    /// The hardware or asusd does not provide this feature
+    Unsupported(String),
+    /// polkit or D-Bus policy refused the change
+    PermissionDenied(String),
 }
 
 impl std::fmt::Display for AsusctlError {
@@ -52,6 +116,8 @@ impl std::fmt::Display for AsusctlError {
             Self::ServiceNotRunning => write!(f, "asusd service is not running"),
             Self::CommandFailed(msg) => write!(f, "Command failed: {msg}"),
             Self::ParseError(msg) => write!(f, "Parse error: {msg}"),
+            Self::Unsupported(msg) => write!(f, "Not supported: {msg}"),
+            Self::PermissionDenied(msg) => write!(f, "Not authorized to change this: {msg}"),
         }
     }
 }
@@ -64,7 +130,7 @@ pub type Result<T> = std::result::Result<T, AsusctlError>;
 // Keyboard Brightness
 // ============================================================================
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
 pub enum KeyboardBrightness {
     Off,
     Low,
@@ -73,6 +139,48 @@ pub enum KeyboardBrightness {
     High,
 }
 
+impl KeyboardBrightness {
+    pub const ALL: [KeyboardBrightness; 4] = [Self::Off, Self::Low, Self::Med, Self::High];
+
+    /// Capitalised name for labels, e.g. "Med"
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Off => "Off",
+            Self::Low => "Low",
+            Self::Med => "Med",
+            Self::High => "High",
+        }
+    }
+
+    /// Level as 0 (off) to 3 (high), matching asusd's `Brightness` property
+    pub fn as_u8(&self) -> u8 {
+        match self {
+            Self::Off => 0,
+            Self::Low => 1,
+            Self::Med => 2,
+            Self::High => 3,
+        }
+    }
+
+    pub fn from_u8(value: u8) -> Result<Self> {
+        Self::ALL
+            .get(value as usize)
+            .copied()
+            .ok_or_else(|| AsusctlError::ParseError(format!("Unknown brightness value: {value}")))
+    }
+
+    /// Level as a percentage: 0, 33, 67 or 100
+    pub fn to_percent(self) -> u8 {
+        ((self.as_u8() as u32 * 100 + 1) / 3) as u8
+    }
+
+    /// Nearest level to a percentage; values above 100 count as high
+    pub fn from_percent(percent: u8) -> Self {
+        let level = (percent.min(100) as u32 * 3 + 50) / 100;
+        Self::ALL[level as usize]
+    }
+}
+
 impl std::fmt::Display for KeyboardBrightness {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
@@ -104,7 +212,7 @@ impl FromStr for KeyboardBrightness {
 // Power Profile
 // ============================================================================
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
 pub enum PowerProfile {
     Quiet,
     #[default]
@@ -112,6 +220,47 @@ pub enum PowerProfile {
     Performance,
 }
 
+impl PowerProfile {
+    pub const ALL: [PowerProfile; 3] = [Self::Quiet, Self::Balanced, Self::Performance];
+
+    /// Profile name used by power-profiles-daemon (`net.hadess.PowerProfiles`)
+    pub fn ppd_name(&self) -> &'static str {
+        match self {
+            Self::Quiet => "power-saver",
+            Self::Balanced => "balanced",
+            Self::Performance => "performance",
+        }
+    }
+
+    pub fn from_ppd_name(name: &str) -> Option<Self> {
+        match name {
+            "power-saver" => Some(Self::Quiet),
+            "balanced" => Some(Self::Balanced),
+            "performance" => Some(Self::Performance),
+            _ => None,
+        }
+    }
+
+    /// Numeric value of asusd's `PlatformProfile` property
+    pub fn platform_profile_value(&self) -> u32 {
+        match self {
+            Self::Balanced => 0,
+            Self::Performance => 1,
+            Self::Quiet => 2,
+        }
+    }
+
+    /// Map asusd's `PlatformProfile` value; LowPower (3) is treated as Quiet
+    pub fn from_platform_profile_value(value: u32) -> Option<Self> {
+        match value {
+            0 => Some(Self::Balanced),
+            1 => Some(Self::Performance),
+            2 | 3 => Some(Self::Quiet),
+            _ => None,
+        }
+    }
+}
+
 impl std::fmt::Display for PowerProfile {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
@@ -137,23 +286,25 @@ impl FromStr for PowerProfile {
     }
 }
 
+/// Profiles reported by asusctl; `None` where the line was missing or unknown
 #[derive(Debug, Clone, Default)]
 pub struct ProfileState {
-    pub active: PowerProfile,
-    pub on_ac: PowerProfile,
-    pub on_battery: PowerProfile,
+    pub active: Option<PowerProfile>,
+    pub on_ac: Option<PowerProfile>,
+    pub on_battery: Option<PowerProfile>,
 }
 
 // ============================================================================
 // Aura Modes
 // ============================================================================
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
 pub enum AuraMode {
     #[default]
     Static,
     Breathe,
     Pulse,
+    RainbowWave,
 }
 
 impl std::fmt::Display for AuraMode {
@@ -162,6 +313,7 @@ impl std::fmt::Display for AuraMode {
             Self::Static => write!(f, "Static"),
             Self::Breathe => write!(f, "Breathe"),
             Self::Pulse => write!(f, "Pulse"),
+            Self::RainbowWave => write!(f, "Rainbow Wave"),
         }
     }
 }
@@ -174,16 +326,191 @@ impl FromStr for AuraMode {
             "static" => Ok(Self::Static),
             "breathe" => Ok(Self::Breathe),
             "pulse" => Ok(Self::Pulse),
+            "rainbowwave" | "rainbow wave" | "rainbow-wave" => Ok(Self::RainbowWave),
             _ => Err(AsusctlError::ParseError(format!("Unknown aura mode: {s}"))),
         }
     }
 }
 
+impl AuraMode {
+    /// Mode from asusd's `LedMode` value; None for modes this app doesn't offer
+    pub fn from_led_mode(value: u32) -> Option<Self> {
+        match value {
+            0 => Some(Self::Static),
+            1 => Some(Self::Breathe),
+            3 => Some(Self::RainbowWave),
+            10 => Some(Self::Pulse),
+            _ => None,
+        }
+    }
+
+    /// Subcommand of `asusctl aura` that sets this mode
+    fn cli_name(&self) -> &'static str {
+        match self {
+            Self::Static => "static",
+            Self::Breathe => "breathe",
+            Self::Pulse => "pulse",
+            Self::RainbowWave => "rainbow-wave",
+        }
+    }
+
+    /// Whether the mode is drawn in the chosen colour
+    pub fn has_colour(&self) -> bool {
+        !matches!(self, Self::RainbowWave)
+    }
+
+    /// Whether the mode animates at a chosen speed
+    ///
+    /// `asusctl aura pulse` only takes a colour, so Pulse has no speed.
+    pub fn has_speed(&self) -> bool {
+        matches!(self, Self::Breathe | Self::RainbowWave)
+    }
+
+    /// Whether the mode moves in a chosen direction
+    pub fn has_direction(&self) -> bool {
+        matches!(self, Self::RainbowWave)
+    }
+}
+
+/// Animation speed of an Aura effect
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize)]
+pub enum AuraSpeed {
+    Low,
+    #[default]
+    Med,
+    High,
+}
+
+impl std::fmt::Display for AuraSpeed {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Low => write!(f, "low"),
+            Self::Med => write!(f, "med"),
+            Self::High => write!(f, "high"),
+        }
+    }
+}
+
+/// Direction an Aura wave travels across the keyboard
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize)]
+pub enum AuraDirection {
+    #[default]
+    Right,
+    Left,
+    Up,
+    Down,
+}
+
+impl std::fmt::Display for AuraDirection {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Right => write!(f, "right"),
+            Self::Left => write!(f, "left"),
+            Self::Up => write!(f, "up"),
+            Self::Down => write!(f, "down"),
+        }
+    }
+}
+
+/// Speed and direction for the modes that animate
+///
+/// Values a mode doesn't use are left out of the asusctl call, and unset
+/// ones fall back to the defaults: medium speed, moving right.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct AuraEffect {
+    pub speed: Option<AuraSpeed>,
+    pub direction: Option<AuraDirection>,
+}
+
+/// A separately coloured keyboard area, as named by asusd
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum AuraZone {
+    Key1,
+    Key2,
+    Key3,
+    Key4,
+    Logo,
+    BarLeft,
+    BarRight,
+}
+
+impl AuraZone {
+    pub const ALL: [AuraZone; 7] = [
+        Self::Key1,
+        Self::Key2,
+        Self::Key3,
+        Self::Key4,
+        Self::Logo,
+        Self::BarLeft,
+        Self::BarRight,
+    ];
+
+    /// Zone argument accepted by `asusctl aura <mode> --zone`
+    fn cli_name(&self) -> &'static str {
+        match self {
+            Self::Key1 => "one",
+            Self::Key2 => "two",
+            Self::Key3 => "three",
+            Self::Key4 => "four",
+            Self::Logo => "logo",
+            Self::BarLeft => "lightbar-left",
+            Self::BarRight => "lightbar-right",
+        }
+    }
+
+    /// Name as printed by `asusctl --show-supported`
+    fn debug_name(&self) -> &'static str {
+        match self {
+            Self::Key1 => "Key1",
+            Self::Key2 => "Key2",
+            Self::Key3 => "Key3",
+            Self::Key4 => "Key4",
+            Self::Logo => "Logo",
+            Self::BarLeft => "BarLeft",
+            Self::BarRight => "BarRight",
+        }
+    }
+}
+
+impl std::fmt::Display for AuraZone {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Key1 => write!(f, "Keyboard Zone 1"),
+            Self::Key2 => write!(f, "Keyboard Zone 2"),
+            Self::Key3 => write!(f, "Keyboard Zone 3"),
+            Self::Key4 => write!(f, "Keyboard Zone 4"),
+            Self::Logo => write!(f, "Logo"),
+            Self::BarLeft => write!(f, "Light Bar Left"),
+            Self::BarRight => write!(f, "Light Bar Right"),
+        }
+    }
+}
+
+/// A complete keyboard lighting setup
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AuraConfig {
+    pub mode: AuraMode,
+    /// RGB colour
+    pub colour: [u8; 3],
+    pub brightness: KeyboardBrightness,
+}
+
+impl Default for AuraConfig {
+    /// Known-good lighting: static white at medium brightness
+    fn default() -> Self {
+        Self {
+            mode: AuraMode::Static,
+            colour: [0xff, 0xff, 0xff],
+            brightness: KeyboardBrightness::Med,
+        }
+    }
+}
+
 // ============================================================================
 // Slash Mode
 // ============================================================================
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
 pub enum SlashMode {
     Bounce,
     Slash,
@@ -203,6 +530,84 @@ pub enum SlashMode {
     Buzzer,
 }
 
+impl SlashMode {
+    /// All modes, in the order the UI lists them
+    pub const ALL: [SlashMode; 15] = [
+        Self::Bounce,
+        Self::Slash,
+        Self::Loading,
+        Self::BitStream,
+        Self::Transmission,
+        Self::Flow,
+        Self::Flux,
+        Self::Phantom,
+        Self::Spectrum,
+        Self::Hazard,
+        Self::Interfacing,
+        Self::Ramp,
+        Self::GameOver,
+        Self::Start,
+        Self::Buzzer,
+    ];
+
+    /// Position in `ALL`, as used by the mode combo
+    pub fn index(&self) -> u32 {
+        Self::ALL
+            .iter()
+            .position(|mode| mode == self)
+            .expect("every mode is listed in ALL") as u32
+    }
+
+    pub fn from_index(index: u32) -> Option<Self> {
+        Self::ALL.get(index as usize).copied()
+    }
+
+    /// Short description of the animation
+    pub fn description(&self) -> &'static str {
+        match self {
+            Self::Bounce => "Bouncing light effect",
+            Self::Slash => "Slashing light animation",
+            Self::Loading => "Progress bar style animation",
+            Self::BitStream => "Digital data stream effect",
+            Self::Transmission => "Data transmission visualization",
+            Self::Flow => "Flowing light effect",
+            Self::Flux => "Pulsing light pattern",
+            Self::Phantom => "Ghostly fading effect",
+            Self::Spectrum => "Color spectrum animation",
+            Self::Hazard => "Warning/hazard style flashing",
+            Self::Interfacing => "Interface connection visualization",
+            Self::Ramp => "Ramping up/down brightness",
+            Self::GameOver => "Game over animation",
+            Self::Start => "Startup animation",
+            Self::Buzzer => "Alert/notification style animation",
+        }
+    }
+
+    /// Mode from the raw value of the Slash `Mode` D-Bus property
+    pub fn from_u8(value: u8) -> Result<Self> {
+        match value {
+            0x10 => Ok(Self::Bounce),
+            0x12 => Ok(Self::Slash),
+            0x13 => Ok(Self::Loading),
+            0x1d => Ok(Self::BitStream),
+            0x1a => Ok(Self::Transmission),
+            0x19 => Ok(Self::Flow),
+            0x25 => Ok(Self::Flux),
+            0x24 => Ok(Self::Phantom),
+            0x26 => Ok(Self::Spectrum),
+            0x32 => Ok(Self::Hazard),
+            0x33 => Ok(Self::Interfacing),
+            0x34 => Ok(Self::Ramp),
+            0x42 => Ok(Self::GameOver),
+            0x43 => Ok(Self::Start),
+            0x44 => Ok(Self::Buzzer),
+            _ => Err(AsusctlError::ParseError(format!(
+                "Unknown slash mode value: {value:#04x}"
+            ))),
+        }
+    }
+}
+
 impl std::fmt::Display for SlashMode {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
@@ -250,22 +655,65 @@ impl FromStr for SlashMode {
     }
 }
 
+// ============================================================================
+// GPU MUX Mode
+// ============================================================================
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GpuMuxMode {
+    /// The internal display is driven directly by the dGPU
+    Discrete,
+    /// The iGPU drives the display, the dGPU renders on demand
+    Hybrid,
+}
+
+impl std::fmt::Display for GpuMuxMode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Discrete => write!(f, "Discrete"),
+            Self::Hybrid => write!(f, "Hybrid (Optimus)"),
+        }
+    }
+}
+
 // ============================================================================
 // Supported Features (from --show-supported)
 // ============================================================================
 
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, Serialize)]
 pub struct SupportedFeatures {
     pub has_aura: bool,
     pub has_platform: bool,
     pub has_fan_curves: bool,
     pub has_slash: bool,
-    pub keyboard_brightness_levels: Vec<KeyboardBrightness>,
+    pub has_anime: bool,
+    /// `None` when asusctl didn't list the levels at all
+    pub keyboard_brightness_levels: Option<Vec<KeyboardBrightness>>,
     pub aura_modes: Vec<AuraMode>,
+    /// Separately coloured areas; empty for single-colour keyboards
+    pub aura_zones: Vec<AuraZone>,
     pub has_charge_control: bool,
     pub has_throttle_policy: bool,
 }
 
+impl SupportedFeatures {
+    /// Whether asusd reported anything at all this app can control
+    pub fn has_any(&self) -> bool {
+        self.has_aura
+            || self.has_platform
+            || self.has_fan_curves
+            || self.has_slash
+            || self.has_anime
+            || self.has_charge_control
+            || self.has_throttle_policy
+            || self
+                .keyboard_brightness_levels
+                .as_ref()
+                .is_some_and(|levels| !levels.is_empty())
+            || !self.aura_modes.is_empty()
+    }
+}
+
 // ============================================================================
 // System Info (from --version)
 // ============================================================================
@@ -277,324 +725,940 @@ pub struct SystemInfo {
     pub board_name: String,
 }
 
+// ============================================================================
+// Status Snapshot
+// ============================================================================
+
+/// Snapshot of the current hardware state: the settings a backup would
+/// hold, plus what the laptop supports.
+///
+/// Fields that could not be read are `None` so a partially working system
+/// still produces a useful report.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct Status {
+    #[serde(flatten)]
+    pub settings: SettingsBackup,
+    pub supported_features: Option<SupportedFeatures>,
+}
+
 // ============================================================================
 // Command Execution Helper
 // ============================================================================
 
 fn run_asusctl(args: &[&str]) -> Result<String> {
-    let output = Command::new("asusctl").args(args).output().map_err(|e| {
-        if e.kind() == std::io::ErrorKind::NotFound {
-            AsusctlError::NotInstalled
-        } else {
-            AsusctlError::CommandFailed(e.to_string())
-        }
-    })?;
+    spawn_asusctl(args, false)
+}
+
+/// Run an asusctl query that may exit non-zero while still printing its answer
+///
+/// Older asusctl releases fail the exit status on `--version`,
+/// `--show-supported` and `profile --profile-get` even when the output is
+/// complete, so for these any stdout is accepted.
+fn run_asusctl_lenient(args: &[&str]) -> Result<String> {
+    spawn_asusctl(args, true)
+}
 
-    let stdout = String::from_utf8_lossy(&output.stdout).to_string();
-    let stderr = String::from_utf8_lossy(&output.stderr).to_string();
+/// How long one asusctl run may take before it is killed
+const ASUSCTL_TIMEOUT: Duration = Duration::from_secs(3);
+/// asusd sometimes stalls for a moment, so a lenient read that times out is
+/// tried again. Other runs may be setters: a killed one can still have been
+/// applied, so they are never repeated.
+const ASUSCTL_READ_ATTEMPTS: u32 = 2;
 
-    // Check for common error patterns
-    if stderr.contains("Connection refused") || stderr.contains("asusd") {
-        return Err(AsusctlError::ServiceNotRunning);
-    }
+/// Runs asusctl with the given arguments; `None` when it had to be killed
+type AsusctlRunner = fn(&[&str]) -> std::io::Result<Option<Output>>;
 
-    // Note: asusctl often returns non-zero but still provides useful output
-    let _ = output.status.success();
+/// What starts asusctl, replaced by a fake in tests
+static ASUSCTL_RUNNER: RwLock<AsusctlRunner> = RwLock::new(run_asusctl_process);
 
-    Ok(stdout)
+fn run_asusctl_process(args: &[&str]) -> std::io::Result<Option<Output>> {
+    run_with_timeout(Command::new("asusctl").args(args), ASUSCTL_TIMEOUT)
 }
 
-// ============================================================================
-// D-Bus Helper Functions
-// ============================================================================
+fn spawn_asusctl(args: &[&str], lenient: bool) -> Result<String> {
+    if mock::enabled() {
+        return mock::run_asusctl(args);
+    }
 
-fn read_dbus_property_at(path: &str, interface: &str, property: &str) -> Result<String> {
-    let output = Command::new("busctl")
-        .args(["get-property", DBUS_DEST, path, interface, property])
-        .output()
-        .map_err(|e| AsusctlError::CommandFailed(format!("busctl failed: {e}")))?;
+    log::debug!("Running asusctl {}", args.join(" "));
 
-    if !output.status.success() {
-        let stderr = String::from_utf8_lossy(&output.stderr);
-        if stderr.contains("No such") || stderr.contains("not found") {
-            return Err(AsusctlError::ServiceNotRunning);
-        }
-        return Err(AsusctlError::CommandFailed(stderr.to_string()));
+    let runner = *ASUSCTL_RUNNER
+        .read()
+        .unwrap_or_else(PoisonError::into_inner);
+    let attempts = if lenient { ASUSCTL_READ_ATTEMPTS } else { 1 };
+    for attempt in 1..=attempts {
+        let Some(output) = runner(args).map_err(|e| {
+            if e.kind() == std::io::ErrorKind::NotFound {
+                AsusctlError::NotInstalled
+            } else {
+                AsusctlError::CommandFailed(e.to_string())
+            }
+        })?
+        else {
+            log::warn!(
+                "asusctl {} timed out (attempt {attempt} of {attempts})",
+                args.join(" ")
+            );
+            continue;
+        };
+
+        let stdout = String::from_utf8_lossy(&output.stdout).to_string();
+        let stderr = String::from_utf8_lossy(&output.stderr).to_string();
+
+        return check_asusctl_output(output.status.success(), stdout, &stderr, lenient)
+            .inspect_err(|e| log::warn!("asusctl {} failed: {e}", args.join(" ")));
     }
 
-    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+    Err(AsusctlError::CommandFailed(format!(
+        "timeout after {}s",
+        ASUSCTL_TIMEOUT.as_secs()
+    )))
 }
 
-fn parse_dbus_bool(output: &str) -> Result<bool> {
-    let value = output
-        .strip_prefix("b ")
-        .ok_or_else(|| AsusctlError::ParseError(format!("Expected boolean, got: {output}")))?;
+/// Run `command`, killing it once `timeout` has passed
+///
+/// Returns `None` when the command had to be killed.
+fn run_with_timeout(command: &mut Command, timeout: Duration) -> std::io::Result<Option<Output>> {
+    let mut child = command
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()?;
+
+    // Drain both pipes meanwhile, so a full pipe can't block the child
+    let stdout = drain_pipe(child.stdout.take());
+    let stderr = drain_pipe(child.stderr.take());
+
+    let deadline = Instant::now() + timeout;
+    let status = loop {
+        if let Some(status) = child.try_wait()? {
+            break status;
+        }
+        if Instant::now() >= deadline {
+            let _ = child.kill();
+            let _ = child.wait();
+            return Ok(None);
+        }
+        thread::sleep(Duration::from_millis(10));
+    };
 
-    match value {
-        "true" => Ok(true),
-        "false" => Ok(false),
-        _ => Err(AsusctlError::ParseError(format!(
-            "Invalid boolean value: {value}"
-        ))),
-    }
+    Ok(Some(Output {
+        status,
+        stdout: stdout.join().unwrap_or_default(),
+        stderr: stderr.join().unwrap_or_default(),
+    }))
 }
 
-fn parse_dbus_byte(output: &str) -> Result<u8> {
-    let value = output
-        .strip_prefix("y ")
-        .ok_or_else(|| AsusctlError::ParseError(format!("Expected byte, got: {output}")))?;
+fn drain_pipe(pipe: Option<impl Read + Send + 'static>) -> thread::JoinHandle<Vec<u8>> {
+    thread::spawn(move || {
+        let mut buffer = Vec::new();
+        if let Some(mut pipe) = pipe {
+            let _ = pipe.read_to_end(&mut buffer);
+        }
+        buffer
+    })
+}
+
+/// Turn the result of an asusctl run into its stdout or an error
+fn check_asusctl_output(
+    success: bool,
+    stdout: String,
+    stderr: &str,
+    lenient: bool,
+) -> Result<String> {
+    if asusd_unreachable(stderr) {
+        return Err(AsusctlError::ServiceNotRunning);
+    }
 
-    value
-        .parse()
-        .map_err(|_| AsusctlError::ParseError(format!("Invalid byte value: {value}")))
-}
+    if success || (lenient && !stdout.trim().is_empty()) {
+        return Ok(stdout);
+    }
 
-fn parse_dbus_uint(output: &str) -> Result<u32> {
-    let value = output
-        .strip_prefix("u ")
-        .ok_or_else(|| AsusctlError::ParseError(format!("Expected uint, got: {output}")))?;
+    let message = stderr.trim();
+    if permission_denied(message) {
+        return Err(AsusctlError::PermissionDenied(message.to_string()));
+    }
+    Err(AsusctlError::CommandFailed(if message.is_empty() {
+        "asusctl exited with an error".to_string()
+    } else {
+        message.to_string()
+    }))
+}
 
-    value
-        .parse()
-        .map_err(|_| AsusctlError::ParseError(format!("Invalid uint value: {value}")))
+/// Whether asusctl's stderr says it couldn't reach asusd at all
+///
+/// Other messages that merely mention asusd are real errors and must not be
+/// reported as a stopped service.
+fn asusd_unreachable(stderr: &str) -> bool {
+    const PATTERNS: &[&str] = &[
+        "Connection refused",
+        "Failed to connect",
+        "NameHasNoOwner",
+        "ServiceUnknown",
+        "was not provided by any .service files",
+    ];
+
+    PATTERNS.iter().any(|pattern| stderr.contains(pattern))
+}
+
+/// Whether an error message says polkit or the bus policy refused the call
+fn permission_denied(message: &str) -> bool {
+    const PATTERNS: &[&str] = &[
+        "permission denied",
+        "not authorized",
+        "interactive authentication required",
+        // org.freedesktop.DBus.Error.AccessDenied
+        "error.accessdenied",
+    ];
+
+    let lower = message.to_lowercase();
+    PATTERNS.iter().any(|pattern| lower.contains(pattern))
 }
 
 // ============================================================================
-// D-Bus Path Discovery
+// D-Bus Helper Functions
 // ============================================================================
 
-/// Discover child paths under /xyz/ljones/aura using busctl
-fn discover_aura_children() -> Result<Vec<String>> {
-    let output = Command::new("busctl")
-        .args(["tree", "--list", DBUS_DEST])
-        .output()
-        .map_err(|e| AsusctlError::CommandFailed(format!("busctl tree failed: {e}")))?;
-
-    if !output.status.success() {
-        return Err(AsusctlError::ServiceNotRunning);
+/// Find the bus name the running asusd owns
+///
+/// The result is cached, also when no daemon answers, until
+/// `invalidate_session_cache` or the next `check_availability`. Without a
+/// daemon the newest names are returned so errors mention the current one.
+pub fn bus_names() -> &'static BusNames {
+    let mut cached = BUS_NAMES.lock().unwrap_or_else(PoisonError::into_inner);
+    cached
+        .get_or_insert_with(find_bus_names)
+        .unwrap_or(&KNOWN_BUS_NAMES[0])
+}
+
+/// Ask the bus which of the known names a daemon owns
+fn find_bus_names() -> Option<&'static BusNames> {
+    let found = KNOWN_BUS_NAMES
+        .iter()
+        .find(|names| bus_name_has_owner(names.dest));
+    match found {
+        Some(names) => log::info!("Using asusd D-Bus name {}", names.dest),
+        None => log::debug!("No asusd on the system bus"),
     }
+    found
+}
 
-    let stdout = String::from_utf8_lossy(&output.stdout);
-    let paths: Vec<String> = stdout
-        .lines()
-        .filter(|line| line.starts_with(AURA_BASE_PATH) && line.len() > AURA_BASE_PATH.len())
-        .map(|s| s.to_string())
-        .collect();
+pub(super) fn bus_name_has_owner(dest: &str) -> bool {
+    if mock::enabled() {
+        return mock::name_has_owner(dest);
+    }
 
-    Ok(paths)
+    system_bus()
+        .and_then(|bus| fdo::DBusProxy::new(bus).map_err(|e| classify_dbus_error(&e.to_string())))
+        .is_ok_and(|proxy| {
+            BusName::try_from(dest).is_ok_and(|name| proxy.name_has_owner(name).unwrap_or(false))
+        })
 }
 
-/// Check if a D-Bus path implements a specific interface by trying to read a known property
-fn path_has_interface(path: &str, interface: &str, test_property: &str) -> bool {
-    let output = Command::new("busctl")
-        .args(["get-property", DBUS_DEST, path, interface, test_property])
-        .output()
-        .ok();
+/// Shared system bus connection, opened on first use
+fn system_bus() -> Result<&'static Connection> {
+    static SYSTEM_BUS: OnceLock<Connection> = OnceLock::new();
 
-    match output {
-        Some(out) => out.status.success(),
-        None => false,
+    if let Some(bus) = SYSTEM_BUS.get() {
+        return Ok(bus);
     }
-}
 
-/// Get the Aura D-Bus path (cached after first discovery)
-fn get_aura_path() -> Option<&'static String> {
-    AURA_PATH
-        .get_or_init(|| {
-            let paths = discover_aura_children().ok()?;
-            // Aura interface has "Brightness" property (keyboard brightness)
-            for path in &paths {
-                if path_has_interface(path, AURA_INTERFACE, "Brightness") {
-                    eprintln!("[asusctl-gui] Discovered Aura D-Bus path: {path}");
-                    return Some(path.clone());
-                }
-            }
-            eprintln!("[asusctl-gui] Warning: No Aura D-Bus path found");
-            None
-        })
-        .as_ref()
+    let bus = connection::Builder::system()
+        .and_then(|builder| builder.method_timeout(DBUS_TIMEOUT).build())
+        .map_err(|e| {
+            AsusctlError::CommandFailed(format!("Failed to connect to the system bus: {e}"))
+        })?;
+    Ok(SYSTEM_BUS.get_or_init(|| bus))
 }
 
-/// Get the Slash D-Bus path (cached after first discovery)
-fn get_slash_path() -> Option<&'static String> {
-    SLASH_PATH
-        .get_or_init(|| {
-            let paths = discover_aura_children().ok()?;
-            // Slash interface has "Enabled" property
-            for path in &paths {
-                if path_has_interface(path, SLASH_INTERFACE, "Enabled") {
-                    eprintln!("[asusctl-gui] Discovered Slash D-Bus path: {path}");
-                    return Some(path.clone());
-                }
-            }
-            eprintln!("[asusctl-gui] Warning: No Slash D-Bus path found");
-            None
-        })
-        .as_ref()
+/// Proxy for one interface of an object on the system bus
+///
+/// Properties aren't cached, so every read asks the service and a page
+/// always shows the current hardware state.
+fn proxy<'a>(dest: &'a str, path: &'a str, interface: &'a str) -> Result<Proxy<'a>> {
+    proxy::Builder::<Proxy>::new(system_bus()?)
+        .destination(dest)
+        .and_then(|builder| builder.path(path))
+        .and_then(|builder| builder.interface(interface))
+        .and_then(|builder| builder.cache_properties(CacheProperties::No).build())
+        .map_err(|e| AsusctlError::ParseError(format!("Invalid D-Bus address {dest} {path}: {e}")))
 }
 
-// ============================================================================
-// Parsing Functions
-// ============================================================================
-
-fn parse_system_info(output: &str) -> Result<SystemInfo> {
-    let mut info = SystemInfo::default();
-
-    for line in output.lines() {
-        let line = line.trim();
+/// A value decoded from a D-Bus reply or property
+pub(super) trait FromDbus: TryFrom<OwnedValue, Error = zvariant::Error> {}
 
-        if let Some(version) = line.strip_prefix("asusctl version:") {
-            info.asusctl_version = version.trim().to_string();
-        } else if let Some(family) = line.strip_prefix("Product family:") {
-            info.product_family = family.trim().to_string();
-        } else if let Some(board) = line.strip_prefix("Board name:") {
-            info.board_name = board.trim().to_string();
-        }
-    }
+impl<T: TryFrom<OwnedValue, Error = zvariant::Error>> FromDbus for T {}
 
-    Ok(info)
+/// Decode `value`, naming `what` and the actual type when it doesn't fit
+fn decode<T: FromDbus>(value: OwnedValue, what: &str) -> Result<T> {
+    let signature = value.value_signature().to_string();
+    T::try_from(value).map_err(|e| {
+        AsusctlError::ParseError(format!("Unexpected type {signature} for {what}: {e}"))
+    })
 }
 
-fn parse_supported_features(output: &str) -> Result<SupportedFeatures> {
-    let mut features = SupportedFeatures::default();
-
-    // Parse core functions
-    features.has_aura = output.contains("xyz.ljones.Aura");
-    features.has_platform = output.contains("xyz.ljones.Platform");
-    features.has_fan_curves = output.contains("xyz.ljones.FanCurves");
-    features.has_slash = output.contains("xyz.ljones.Slash");
-
-    // Parse platform properties
-    features.has_charge_control = output.contains("ChargeControlEndThreshold");
-    features.has_throttle_policy = output.contains("ThrottlePolicy");
-
-    // Parse keyboard brightness levels
-    let brightness_section = extract_section(output, "Supported Keyboard Brightness:");
-    for level in ["Off", "Low", "Med", "High"] {
-        if brightness_section.contains(level) {
-            if let Ok(brightness) = KeyboardBrightness::from_str(level) {
-                features.keyboard_brightness_levels.push(brightness);
-            }
-        }
+/// Call a method on any service on the system bus
+///
+/// Returns the value the method replies with, or `None` when it replies with
+/// nothing. Methods replying with several values aren't used.
+fn call_dbus_raw(
+    dest: &str,
+    path: &str,
+    interface: &str,
+    method: &str,
+    args: Option<&Structure<'_>>,
+) -> Result<Option<OwnedValue>> {
+    if mock::enabled() {
+        return mock::call_dbus(dest, path, interface, method, args);
     }
 
-    // Parse aura modes
-    let aura_section = extract_section(output, "Supported Aura Modes:");
-    for mode in ["Static", "Breathe", "Pulse"] {
-        if aura_section.contains(mode) {
-            if let Ok(aura_mode) = AuraMode::from_str(mode) {
-                features.aura_modes.push(aura_mode);
-            }
-        }
+    let proxy = proxy(dest, path, interface)?;
+    let reply = match args {
+        Some(args) => proxy.call_method(method, args),
+        None => proxy.call_method(method, &()),
     }
+    .map_err(|e| {
+        log::debug!("D-Bus {interface}.{method} on {dest} {path} failed: {e}");
+        classify_dbus_error(&e.to_string())
+    })?;
 
-    Ok(features)
+    let body = reply.body();
+    if body.is_empty() {
+        return Ok(None);
+    }
+    let OwnedStructure(values) = body
+        .deserialize()
+        .map_err(|e| AsusctlError::ParseError(format!("Malformed {method} reply: {e}")))?;
+    values
+        .into_fields()
+        .into_iter()
+        .next()
+        .map(OwnedValue::try_from)
+        .transpose()
+        .map_err(|e| AsusctlError::ParseError(format!("Malformed {method} reply: {e}")))
+}
+
+/// Call a method on any service on the system bus, decoding its reply
+pub(super) fn call_dbus<T: FromDbus>(
+    dest: &str,
+    path: &str,
+    interface: &str,
+    method: &str,
+    args: Option<&Structure<'_>>,
+) -> Result<T> {
+    let reply = call_dbus_raw(dest, path, interface, method, args)?
+        .ok_or_else(|| AsusctlError::ParseError(format!("Empty {method} reply")))?;
+    decode(reply, method)
+}
+
+/// Call a method on asusd whose reply carries nothing
+pub(super) fn call_asusd(
+    path: &str,
+    interface: &str,
+    method: &str,
+    args: &Structure<'_>,
+) -> Result<()> {
+    call_dbus_raw(bus_names().dest, path, interface, method, Some(args))?;
+    Ok(())
 }
 
-fn parse_profile_state(output: &str) -> Result<ProfileState> {
-    let mut state = ProfileState::default();
-
-    for line in output.lines() {
-        let line = line.trim();
-
-        if let Some(profile) = line.strip_prefix("Active profile is") {
-            state.active = PowerProfile::from_str(profile.trim())?;
-        } else if let Some(profile) = line.strip_prefix("Profile on AC is") {
-            state.on_ac = PowerProfile::from_str(profile.trim())?;
-        } else if let Some(profile) = line.strip_prefix("Profile on Battery is") {
-            state.on_battery = PowerProfile::from_str(profile.trim())?;
-        }
+/// Call a method on asusd, decoding its reply
+pub(super) fn query_asusd<T: FromDbus>(
+    path: &str,
+    interface: &str,
+    method: &str,
+    args: &Structure<'_>,
+) -> Result<T> {
+    call_dbus(bus_names().dest, path, interface, method, Some(args))
+}
+
+pub(super) fn read_dbus_property_at<T: FromDbus>(
+    path: &str,
+    interface: &str,
+    property: &str,
+) -> Result<T> {
+    read_dbus_property(bus_names().dest, path, interface, property)
+}
+
+/// Read a property from any service on the system bus
+pub(super) fn read_dbus_property<T: FromDbus>(
+    dest: &str,
+    path: &str,
+    interface: &str,
+    property: &str,
+) -> Result<T> {
+    decode(read_dbus_value(dest, path, interface, property)?, property)
+}
+
+/// Write a property on asusd
+pub(super) fn write_dbus_property_at<'a>(
+    path: &str,
+    interface: &str,
+    property: &str,
+    value: impl Into<Value<'a>>,
+) -> Result<()> {
+    let dest = bus_names().dest;
+    let value = value.into();
+    if mock::enabled() {
+        let value = value
+            .try_into_owned()
+            .map_err(|e| AsusctlError::ParseError(format!("Can't write {property}: {e}")))?;
+        mock::set_property(interface, property, value);
+        return Ok(());
     }
 
-    Ok(state)
+    proxy(dest, path, interface)?
+        .set_property(property, value)
+        .map_err(|e| {
+            log::debug!("Setting {interface}.{property} on {dest} {path} failed: {e}");
+            classify_dbus_error(&e.to_string())
+        })
 }
 
-/// Helper to extract a section from the output (between a header and the next header or end)
-fn extract_section(output: &str, header: &str) -> String {
-    let mut in_section = false;
-    let mut section = String::new();
-    let mut bracket_depth = 0;
-
-    for line in output.lines() {
-        if line.contains(header) {
-            in_section = true;
-            continue;
-        }
+/// Read a property without decoding it, for structs read field by field
+fn read_dbus_value(dest: &str, path: &str, interface: &str, property: &str) -> Result<OwnedValue> {
+    if mock::enabled() {
+        return mock::get_property(interface, property);
+    }
 
-        if in_section {
-            // Track bracket depth to know when section ends
-            bracket_depth += line.matches('[').count() as i32;
-            bracket_depth -= line.matches(']').count() as i32;
+    proxy(dest, path, interface)?
+        .get_property(property)
+        .map_err(|e| {
+            log::debug!("Reading {interface}.{property} on {dest} {path} failed: {e}");
+            classify_dbus_error(&e.to_string())
+        })
+}
 
-            section.push_str(line);
-            section.push('\n');
+/// Map a failed D-Bus read to an error
+///
+/// A missing property, interface or object means the feature is absent on
+/// this machine, which is reported as `Unsupported` rather than a failure.
+/// Matches both the D-Bus error names and their human-readable messages.
+fn classify_dbus_error(message: &str) -> AsusctlError {
+    const UNSUPPORTED: &[&str] = &[
+        "unknown property",
+        "unknown interface",
+        "unknown object",
+        "unknown method",
+        "unknownproperty",
+        "unknowninterface",
+        "unknownobject",
+        "unknownmethod",
+        "no such property",
+        "no such interface",
+        "no such object",
+    ];
+
+    if permission_denied(message) {
+        return AsusctlError::PermissionDenied(message.trim().to_string());
+    }
 
-            // Section ends when we close all brackets and hit a new section
-            if bracket_depth <= 0 && line.contains(']') {
-                break;
-            }
-        }
+    let lower = message.to_lowercase();
+    if UNSUPPORTED.iter().any(|pattern| lower.contains(pattern)) {
+        return AsusctlError::Unsupported(message.trim().to_string());
     }
+    if lower.contains("no such") || lower.contains("not found") || lower.contains("not provided") {
+        return AsusctlError::ServiceNotRunning;
+    }
+    AsusctlError::CommandFailed(message.trim().to_string())
+}
 
-    section
+/// Extract colour1 from a `LedModeData` value, a struct like
+/// (mode, zone, (r, g, b), (r, g, b), speed, direction)
+fn led_mode_colour(data: &Value<'_>) -> Result<[u8; 3]> {
+    let colour = match data {
+        Value::Structure(fields) => fields.fields().get(2),
+        _ => None,
+    };
+    colour
+        .and_then(|colour| <(u8, u8, u8)>::try_from(colour).ok())
+        .map(|(r, g, b)| [r, g, b])
+        .ok_or_else(|| {
+            AsusctlError::ParseError(format!(
+                "Unexpected LedModeData type {}",
+                data.value_signature()
+            ))
+        })
 }
 
-/// Parse slash config from /etc/asusd/slash.ron
-fn parse_slash_config() -> Result<SlashState> {
-    let content = fs::read_to_string(SLASH_CONFIG_PATH)
-        .map_err(|e| AsusctlError::ParseError(format!("Failed to read slash config: {e}")))?;
+/// One zone of asusd's `LedPower`: zone, then lit at boot, awake, asleep and shutdown
+type AuraPowerState = (u32, bool, bool, bool, bool);
 
-    let mut state = SlashState::default();
+/// `LedPower` zones covering the keyboard: Keyboard and KeyboardAndLightbar
+const KEYBOARD_POWER_ZONES: [u32; 2] = [1, 5];
 
-    for line in content.lines() {
+/// Read the zones of a `LedPower` value, a struct holding an array of zones
+///
+/// Other layouts are reported as `Unsupported`, so the setting is hidden
+/// rather than shown with a made-up state.
+fn led_power_states(data: OwnedValue) -> Result<Vec<AuraPowerState>> {
+    let signature = data.value_signature().to_string();
+    <(Vec<AuraPowerState>,)>::try_from(data)
+        .map(|(states,)| states)
+        .map_err(|_| AsusctlError::Unsupported(format!("Unexpected LedPower type {signature}")))
+}
+
+// ============================================================================
+// D-Bus Path Discovery
+// ============================================================================
+
+/// Discover all object paths below `base` by walking asusd's introspection data
+fn discover_children(base: &str) -> Result<Vec<String>> {
+    let mut found = Vec::new();
+    let mut pending = vec![base.to_string()];
+
+    while let Some(path) = pending.pop() {
+        for name in introspect(&path)?.children {
+            let child = join_object_path(&path, &name);
+            found.push(child.clone());
+            pending.push(child);
+        }
+    }
+
+    found.sort();
+    Ok(found)
+}
+
+/// Child nodes and interfaces of one object, from its introspection data
+#[derive(Debug, Default, PartialEq, Eq)]
+struct Introspection {
+    /// Names relative to the object
+    children: Vec<String>,
+    interfaces: Vec<String>,
+}
+
+impl Introspection {
+    /// Only relative child names are kept, which skips the root `<node>`
+    /// when it carries its own absolute path.
+    fn parse(xml: &str) -> Result<Self> {
+        let node = zbus_xml::Node::from_reader(xml.as_bytes())
+            .map_err(|e| AsusctlError::ParseError(format!("Malformed introspection data: {e}")))?;
+
+        Ok(Self {
+            children: node
+                .nodes()
+                .iter()
+                .filter_map(|child| child.name())
+                .filter(|name| !name.starts_with('/'))
+                .map(str::to_string)
+                .collect(),
+            interfaces: node
+                .interfaces()
+                .iter()
+                .map(|interface| interface.name().to_string())
+                .collect(),
+        })
+    }
+
+    fn implements(&self, interface: &str) -> bool {
+        self.interfaces.iter().any(|name| name == interface)
+    }
+}
+
+/// Introspect one asusd object
+fn introspect(path: &str) -> Result<Introspection> {
+    let dest = bus_names().dest;
+    let xml = if mock::enabled() {
+        mock::introspect(path)
+    } else {
+        fdo::IntrospectableProxy::builder(system_bus()?)
+            .destination(dest)
+            .and_then(|builder| builder.path(path))
+            .and_then(|builder| builder.cache_properties(CacheProperties::No).build())
+            .and_then(|proxy| proxy.introspect().map_err(zbus::Error::from))
+            .map_err(|e| {
+                log::debug!("Introspecting {path} on {dest} failed: {e}");
+                classify_dbus_error(&e.to_string())
+            })?
+    };
+    Introspection::parse(&xml)
+}
+
+fn join_object_path(parent: &str, name: &str) -> String {
+    format!("{}/{name}", parent.trim_end_matches('/'))
+}
+
+/// An object exported by asusd and the interfaces it implements
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AsusdObject {
+    pub path: String,
+    pub interfaces: Vec<String>,
+}
+
+/// List every object asusd exports, for debugging unknown-object errors
+pub fn list_asusd_objects() -> Result<Vec<AsusdObject>> {
+    let mut objects = Vec::new();
+
+    for path in discover_children("/")? {
+        let Ok(Introspection { interfaces, .. }) = introspect(&path) else {
+            continue;
+        };
+
+        // Intermediate path nodes only carry the standard interfaces
+        if interfaces
+            .iter()
+            .all(|i| i.starts_with("org.freedesktop.DBus."))
+        {
+            continue;
+        }
+
+        objects.push(AsusdObject { path, interfaces });
+    }
+
+    Ok(objects)
+}
+
+/// Find the object below `base` that implements `interface`
+///
+/// The first lookup that reaches asusd is cached for the session, including
+/// finding nothing. A failed walk (asusd not running yet, say) is returned
+/// as is and retried on the next call, so the device still turns up once the
+/// service is back.
+pub(super) fn discover_device(
+    cache: &'static OnceLock<Option<String>>,
+    base: &str,
+    interface: &str,
+) -> Result<Option<&'static String>> {
+    if let Some(cached) = cache.get() {
+        return Ok(cached.as_ref());
+    }
+
+    let found = discover_children(base)?
+        .into_iter()
+        .find(|path| introspect(path).is_ok_and(|object| object.implements(interface)));
+    match &found {
+        Some(path) => log::info!("Discovered {interface} at {path}"),
+        None => log::warn!("No {interface} object below {base}"),
+    }
+
+    Ok(cache.get_or_init(|| found).as_ref())
+}
+
+/// Get the Aura D-Bus path (cached after first discovery)
+fn get_aura_path() -> Result<Option<&'static String>> {
+    discover_device(
+        &AURA_PATH,
+        bus_names().aura_base_path,
+        bus_names().aura_interface,
+    )
+}
+
+/// Get the Slash D-Bus path (cached after first discovery)
+///
+/// Some models expose the slash bar as its own node rather than an aura
+/// child, so everything under the platform path is searched.
+fn get_slash_path() -> Result<Option<&'static String>> {
+    discover_device(
+        &SLASH_PATH,
+        bus_names().platform_path,
+        bus_names().slash_interface,
+    )
+}
+
+/// Aura path, or `Unsupported` when the laptop has no Aura device
+fn aura_path() -> Result<&'static String> {
+    get_aura_path()?.ok_or_else(|| AsusctlError::Unsupported("no Aura D-Bus path".to_string()))
+}
+
+/// Slash path, or `Unsupported` when the laptop has no Slash LED bar
+fn slash_path() -> Result<&'static String> {
+    get_slash_path()?.ok_or_else(|| AsusctlError::Unsupported("no Slash D-Bus path".to_string()))
+}
+
+// ============================================================================
+// Parsing Functions
+// ============================================================================
+
+fn parse_system_info(output: &str) -> Result<SystemInfo> {
+    let mut info = SystemInfo::default();
+
+    for line in output.lines() {
         let line = line.trim();
 
-        if line.starts_with("enabled:") {
-            state.enabled = line.contains("true");
-        } else if line.starts_with("brightness:") {
-            if let Some(val) = extract_number(line) {
-                state.brightness = val as u8;
-            }
-        } else if line.starts_with("display_interval:") {
-            if let Some(val) = extract_number(line) {
-                state.interval = val as u8;
+        if let Some(version) = line.strip_prefix("asusctl version:") {
+            info.asusctl_version = version.trim().to_string();
+        } else if let Some(family) = line.strip_prefix("Product family:") {
+            info.product_family = family.trim().to_string();
+        } else if let Some(board) = line.strip_prefix("Board name:") {
+            info.board_name = board.trim().to_string();
+        }
+    }
+
+    Ok(info)
+}
+
+/// Parse `asusctl --show-supported`
+///
+/// Only the listed sections count: a feature whose section is missing or
+/// doesn't name it stays off.
+fn parse_supported_features(output: &str) -> Result<SupportedFeatures> {
+    let sections = parse_sections(output);
+    let find_section = |name: &str| -> Option<&[String]> {
+        sections
+            .iter()
+            .find(|(header, _)| header.eq_ignore_ascii_case(name))
+            .map(|(_, items)| items.as_slice())
+    };
+    let section = |name: &str| find_section(name).unwrap_or_default();
+
+    // Interfaces are listed as e.g. "xyz.ljones.Aura", or "org.asuslinux.Aura" before 6.0
+    let core = section("Supported Core Functions");
+    let has_interface = |name: &str| {
+        core.iter()
+            .any(|item| item.rsplit('.').next() == Some(name))
+    };
+
+    // Properties may be prefixed with their interface, e.g. "xyz.ljones.Platform: ChargeControlEndThreshold"
+    let properties: Vec<&str> = section("Supported Platform Properties")
+        .iter()
+        .map(|item| item.rsplit([':', '.']).next().unwrap_or(item).trim())
+        .collect();
+
+    let zones = section("Supported Aura Zones");
+
+    Ok(SupportedFeatures {
+        has_aura: has_interface("Aura"),
+        has_platform: has_interface("Platform"),
+        has_fan_curves: has_interface("FanCurves"),
+        has_slash: has_interface("Slash"),
+        has_anime: has_interface("Anime"),
+        has_charge_control: properties.contains(&"ChargeControlEndThreshold"),
+        has_throttle_policy: properties.contains(&"ThrottlePolicy"),
+        keyboard_brightness_levels: find_section("Supported Keyboard Brightness")
+            .map(|items| items.iter().filter_map(|item| item.parse().ok()).collect()),
+        aura_modes: section("Supported Aura Modes")
+            .iter()
+            .filter_map(|item| item.parse().ok())
+            .collect(),
+        aura_zones: AuraZone::ALL
+            .into_iter()
+            .filter(|zone| zones.iter().any(|item| item == zone.debug_name()))
+            .collect(),
+    })
+}
+
+/// Split output into "Header:" sections and the items of their bracketed lists
+///
+/// Lists may span lines or sit on the header line, items may be quoted, and
+/// brackets nested inside an item are kept as part of it.
+fn parse_sections(output: &str) -> Vec<(String, Vec<String>)> {
+    let mut sections: Vec<(String, Vec<String>)> = Vec::new();
+    let mut depth = 0usize;
+    let mut item = String::new();
+
+    fn push_item(sections: &mut [(String, Vec<String>)], item: &mut String) {
+        let value = item.trim().trim_matches('"').trim();
+        if !value.is_empty()
+            && let Some((_, items)) = sections.last_mut()
+        {
+            items.push(value.to_string());
+        }
+        item.clear();
+    }
+
+    for line in output.lines() {
+        let mut rest = line.trim();
+
+        if depth == 0 {
+            match rest.split_once(':') {
+                Some((header, tail)) if !header.contains('[') => {
+                    sections.push((header.trim().to_string(), Vec::new()));
+                    rest = tail;
+                }
+                _ => {}
             }
-        } else if line.starts_with("display_mode:") {
-            if let Some(mode_str) = extract_string_value(line) {
-                state.mode = SlashMode::from_str(&mode_str).unwrap_or_default();
+        }
+
+        for c in rest.chars() {
+            match c {
+                '[' => {
+                    depth += 1;
+                    if depth > 1 {
+                        item.push(c);
+                    }
+                }
+                ']' if depth > 0 => {
+                    depth -= 1;
+                    if depth == 0 {
+                        push_item(&mut sections, &mut item);
+                    } else {
+                        item.push(c);
+                    }
+                }
+                ',' if depth == 1 => push_item(&mut sections, &mut item),
+                _ if depth > 0 => item.push(c),
+                // Text outside any list, e.g. a version banner
+                _ => {}
             }
         }
+
+        if depth == 1 {
+            push_item(&mut sections, &mut item);
+        }
+    }
+
+    sections
+}
+
+/// Parse `asusctl profile --profile-get`
+///
+/// Lines are matched regardless of case, colour codes, leading markers and
+/// trailing punctuation, with "is" or ":" before the profile. A profile name
+/// this version doesn't know is logged and leaves that field `None`.
+fn parse_profile_state(output: &str) -> Result<ProfileState> {
+    let mut state = ProfileState::default();
+
+    for line in output.lines() {
+        let line = strip_ansi(line).to_lowercase();
+        let line = line.trim_start_matches(|c: char| !c.is_alphanumeric());
+
+        let (field, rest) = if let Some(rest) = line.strip_prefix("active profile") {
+            (&mut state.active, rest)
+        } else if let Some(rest) = line.strip_prefix("profile on ac") {
+            (&mut state.on_ac, rest)
+        } else if let Some(rest) = line.strip_prefix("profile on battery") {
+            (&mut state.on_battery, rest)
+        } else {
+            continue;
+        };
+
+        let rest = rest.trim_start();
+        let name = rest
+            .strip_prefix("is ")
+            .or_else(|| rest.strip_prefix(':'))
+            .unwrap_or(rest)
+            .trim()
+            .trim_end_matches(|c: char| !c.is_alphanumeric())
+            .trim_matches(['"', '\'']);
+
+        match PowerProfile::from_str(name) {
+            Ok(profile) => *field = Some(profile),
+            Err(e) => log::warn!("Ignoring profile line {line:?}: {e}"),
+        }
     }
 
     Ok(state)
 }
 
+/// Remove ANSI escape sequences such as colour codes
+fn strip_ansi(line: &str) -> String {
+    let mut plain = String::with_capacity(line.len());
+    let mut chars = line.chars();
+
+    while let Some(c) = chars.next() {
+        if c != '\x1b' {
+            plain.push(c);
+            continue;
+        }
+        // CSI sequences end at the first letter, e.g. "\x1b[1;32m"
+        if chars.next() == Some('[') {
+            for c in chars.by_ref() {
+                if c.is_ascii_alphabetic() {
+                    break;
+                }
+            }
+        }
+    }
+
+    plain
+}
+
+/// Location of slash.ron, `ASUSD_SLASH_CONFIG` if set
+fn slash_config_path() -> PathBuf {
+    std::env::var_os(SLASH_CONFIG_ENV_VAR)
+        .filter(|path| !path.is_empty())
+        .map_or_else(|| PathBuf::from(SLASH_CONFIG_PATH), PathBuf::from)
+}
+
+/// Parse slash config from slash.ron, see `slash_config_path`
+fn parse_slash_config() -> Result<SlashState> {
+    parse_slash_config_at(&slash_config_path())
+}
+
+fn parse_slash_config_at(path: &Path) -> Result<SlashState> {
+    let content = fs::read_to_string(path).map_err(|e| {
+        AsusctlError::ParseError(format!(
+            "Failed to read slash config {}: {e}",
+            path.display()
+        ))
+    })?;
+
+    Ok(parse_slash_ron(&content))
+}
+
+/// Read the top-level Slash fields from slash.ron content
+///
+/// Only `key: value` lines are looked at; a field that is missing or can't
+/// be read keeps its default.
+fn parse_slash_ron(content: &str) -> SlashState {
+    let mut state = SlashState::default();
+
+    for line in content.lines() {
+        let Some((key, _)) = line.split_once(':') else {
+            continue;
+        };
+
+        match key.trim() {
+            "enabled" => {
+                if let Some(val) = extract_string_value(line) {
+                    state.enabled = val == "true";
+                }
+            }
+            "brightness" => {
+                if let Some(val) = extract_number(line).and_then(|v| u8::try_from(v).ok()) {
+                    state.brightness = val;
+                }
+            }
+            "display_interval" => {
+                if let Some(val) = extract_number(line).and_then(|v| u8::try_from(v).ok()) {
+                    state.interval = val;
+                }
+            }
+            "display_mode" => {
+                if let Some(mode) =
+                    extract_string_value(line).and_then(|v| SlashMode::from_str(&v).ok())
+                {
+                    state.mode = mode;
+                }
+            }
+            _ => {}
+        }
+    }
+
+    state
+}
+
 /// Extract a number from a line like "brightness: 255,"
 fn extract_number(line: &str) -> Option<u32> {
-    line.split(':')
-        .nth(1)?
-        .trim()
-        .trim_end_matches(',')
-        .parse()
-        .ok()
+    extract_string_value(line)?.parse().ok()
 }
 
-/// Extract a string value from a line like "display_mode: BitStream,"
+/// Extract a plain value from a line like `display_mode: BitStream,`
+///
+/// Everything after the first ':' is the value, so a quoted value may itself
+/// contain colons. Quotes, a trailing comma and a `//` comment are dropped.
+/// Nested values (tuples, lists, structs) are not plain values and give `None`.
 fn extract_string_value(line: &str) -> Option<String> {
-    Some(
-        line.split(':')
-            .nth(1)?
-            .trim()
-            .trim_end_matches(',')
-            .to_string(),
-    )
+    let value = line.split_once(':')?.1.trim();
+
+    if let Some(quoted) = value.strip_prefix('"') {
+        let (inner, _) = quoted.split_once('"')?;
+        return Some(inner.to_string());
+    }
+
+    let value = value.split("//").next()?;
+    let value = value.split(',').next()?.trim();
+    if value.is_empty() || value.starts_with(['(', '[', '{']) {
+        return None;
+    }
+    Some(value.to_string())
 }
 
 // ============================================================================
 // Slash State Struct
 // ============================================================================
 
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
 pub struct SlashState {
     pub enabled: bool,
     pub brightness: u8,
@@ -606,16 +1670,70 @@ pub struct SlashState {
 // Public API - System Info
 // ============================================================================
 
+/// Return the cached value, or `load` it and cache it if that works
+///
+/// The lock is held while loading so concurrent callers spawn asusctl once.
+fn cached<T: Clone>(cache: &Mutex<Option<T>>, load: impl FnOnce() -> Result<T>) -> Result<T> {
+    let mut cached = cache.lock().unwrap_or_else(PoisonError::into_inner);
+    if let Some(value) = cached.as_ref() {
+        return Ok(value.clone());
+    }
+
+    let value = load()?;
+    *cached = Some(value.clone());
+    Ok(value)
+}
+
+/// Forget the bus name, system info and supported features read this session
+///
+/// Call this when asusd may have changed, e.g. after it was restarted.
+pub fn invalidate_session_cache() {
+    BUS_NAMES
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner)
+        .take();
+    SYSTEM_INFO
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner)
+        .take();
+    SUPPORTED_FEATURES
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner)
+        .take();
+}
+
 /// Get system information (version, product family, board name)
+///
+/// Read once per session; see `invalidate_session_cache`.
 pub fn get_system_info() -> Result<SystemInfo> {
-    let output = run_asusctl(&["--version"])?;
-    parse_system_info(&output)
+    cached(&SYSTEM_INFO, || {
+        let output = run_asusctl_lenient(&["--version"])?;
+        parse_system_info(&output)
+    })
 }
 
 /// Get supported features for this laptop
+///
+/// Read once per session; see `invalidate_session_cache`.
 pub fn get_supported_features() -> Result<SupportedFeatures> {
-    let output = run_asusctl(&["--show-supported"])?;
-    parse_supported_features(&output)
+    cached(&SUPPORTED_FEATURES, || {
+        let output = run_asusctl_lenient(&["--show-supported"])?;
+        parse_supported_features(&output)
+    })
+}
+
+/// Check that asusctl is installed and the asusd service owns its D-Bus name
+pub fn check_availability() -> Result<()> {
+    run_asusctl_lenient(&["--version"])?;
+
+    // Look again rather than trusting the cache, which may predate asusd starting
+    let found = find_bus_names();
+    *BUS_NAMES.lock().unwrap_or_else(PoisonError::into_inner) = Some(found);
+    if found.is_none() {
+        return Err(AsusctlError::ServiceNotRunning);
+    }
+
+    Ok(())
 }
 
 // ============================================================================
@@ -624,20 +1742,49 @@ pub fn get_supported_features() -> Result<SupportedFeatures> {
 
 /// Get current keyboard brightness via D-Bus
 pub fn get_keyboard_brightness_dbus() -> Result<KeyboardBrightness> {
-    let path = get_aura_path()
-        .ok_or_else(|| AsusctlError::CommandFailed("Aura D-Bus path not found".to_string()))?;
-    let output = read_dbus_property_at(path, AURA_INTERFACE, "Brightness")?;
-    let value = parse_dbus_uint(&output)?;
+    let path = aura_path()?;
+    let value: u32 = read_dbus_property_at(path, bus_names().aura_interface, "Brightness")?;
 
-    match value {
-        0 => Ok(KeyboardBrightness::Off),
-        1 => Ok(KeyboardBrightness::Low),
-        2 => Ok(KeyboardBrightness::Med),
-        3 => Ok(KeyboardBrightness::High),
-        _ => Err(AsusctlError::ParseError(format!(
-            "Unknown brightness value: {value}"
-        ))),
+    u8::try_from(value)
+        .map_err(|_| AsusctlError::ParseError(format!("Unknown brightness value: {value}")))
+        .and_then(KeyboardBrightness::from_u8)
+}
+
+/// Get current keyboard brightness from `asusctl --kbd-bright` without a level
+fn get_keyboard_brightness_cli() -> Result<KeyboardBrightness> {
+    parse_keyboard_brightness(&run_asusctl(&["--kbd-bright"])?)
+}
+
+/// Get current keyboard brightness, through asusctl when D-Bus fails
+///
+/// Some asusd versions export the Aura object under a path we can't find
+/// ("Unknown object"), while asusctl still reaches it. When both fail the
+/// D-Bus error is returned, so a laptop without Aura stays `Unsupported`.
+pub fn get_keyboard_brightness() -> Result<KeyboardBrightness> {
+    get_keyboard_brightness_dbus().or_else(|dbus_error| {
+        log::debug!("Reading keyboard brightness over D-Bus failed ({dbus_error}), trying asusctl");
+        get_keyboard_brightness_cli().map_err(|cli_error| {
+            log::debug!("Reading keyboard brightness through asusctl failed: {cli_error}");
+            dbus_error
+        })
+    })
+}
+
+/// Read the level from the "Current keyboard led brightness: High" line
+fn parse_keyboard_brightness(output: &str) -> Result<KeyboardBrightness> {
+    for line in output.lines() {
+        if line.contains("Current keyboard led brightness:") {
+            let level = line
+                .split(':')
+                .nth(1)
+                .ok_or_else(|| AsusctlError::ParseError("Missing brightness value".to_string()))?
+                .trim();
+            return KeyboardBrightness::from_str(level);
+        }
     }
+    Err(AsusctlError::ParseError(
+        "Could not find brightness level in output".to_string(),
+    ))
 }
 
 /// Set keyboard brightness level
@@ -646,74 +1793,430 @@ pub fn set_keyboard_brightness(level: KeyboardBrightness) -> Result<()> {
     Ok(())
 }
 
+/// Get the active keyboard lighting mode via D-Bus
+///
+/// Returns `None` when the keyboard runs a mode this app doesn't offer.
+pub fn get_aura_mode_dbus() -> Result<Option<AuraMode>> {
+    let path = aura_path()?;
+    let value = read_dbus_property_at(path, bus_names().aura_interface, "LedMode")?;
+    Ok(AuraMode::from_led_mode(value))
+}
+
+/// Get the primary colour of the active lighting effect via D-Bus
+pub fn get_aura_colour_dbus() -> Result<[u8; 3]> {
+    let path = aura_path()?;
+    let data = read_dbus_value(
+        bus_names().dest,
+        path,
+        bus_names().aura_interface,
+        "LedModeData",
+    )?;
+    led_mode_colour(&data)
+}
+
+fn read_led_power(path: &str) -> Result<Vec<AuraPowerState>> {
+    let data = read_dbus_value(
+        bus_names().dest,
+        path,
+        bus_names().aura_interface,
+        "LedPower",
+    )?;
+    led_power_states(data)
+}
+
+/// Whether the keyboard lighting stays on while the laptop sleeps
+///
+/// Returns `Unsupported` when asusd has no power settings for the keyboard.
+pub fn get_keyboard_lit_on_sleep() -> Result<bool> {
+    read_led_power(aura_path()?)?
+        .into_iter()
+        .find(|(zone, ..)| KEYBOARD_POWER_ZONES.contains(zone))
+        .map(|(_, _, _, sleep, _)| sleep)
+        .ok_or_else(|| AsusctlError::Unsupported("no keyboard power zone".to_string()))
+}
+
+/// Keep the keyboard lit during sleep or turn it off, leaving the other states alone
+pub fn set_keyboard_lit_on_sleep(lit: bool) -> Result<()> {
+    let path = aura_path()?;
+    let mut states = read_led_power(path)?;
+    let mut found = false;
+    for (zone, _, _, sleep, _) in &mut states {
+        if KEYBOARD_POWER_ZONES.contains(zone) {
+            *sleep = lit;
+            found = true;
+        }
+    }
+    if !found {
+        return Err(AsusctlError::Unsupported(
+            "no keyboard power zone".to_string(),
+        ));
+    }
+
+    write_dbus_property_at(path, bus_names().aura_interface, "LedPower", (states,))
+}
+
+/// Set the keyboard colour, keeping the active lighting mode and `effect`
+///
+/// Modes the app doesn't offer, or that have no colour, are replaced by
+/// Static, since asusctl needs a mode to apply a colour to.
+pub fn set_aura_colour(colour: [u8; 3], effect: AuraEffect) -> Result<()> {
+    let mode = get_aura_mode_dbus()
+        .ok()
+        .flatten()
+        .filter(AuraMode::has_colour)
+        .unwrap_or_default();
+    set_aura_mode(mode, colour, effect)
+}
+
+/// Arguments of `asusctl aura` for a mode, its colour and effect
+fn aura_mode_args(mode: AuraMode, colour: [u8; 3], effect: AuraEffect) -> Vec<String> {
+    let mut args = vec!["aura".to_string(), mode.cli_name().to_string()];
+
+    if mode.has_colour() {
+        let [r, g, b] = colour;
+        args.extend(["-c".to_string(), format!("{r:02x}{g:02x}{b:02x}")]);
+    }
+    if mode.has_speed() {
+        args.extend([
+            "-s".to_string(),
+            effect.speed.unwrap_or_default().to_string(),
+        ]);
+    }
+    if mode.has_direction() {
+        let direction = effect.direction.unwrap_or_default();
+        args.extend(["-d".to_string(), direction.to_string()]);
+    }
+
+    args
+}
+
+/// Set the keyboard lighting mode with its colour, speed and direction
+pub fn set_aura_mode(mode: AuraMode, colour: [u8; 3], effect: AuraEffect) -> Result<()> {
+    let args = aura_mode_args(mode, colour, effect);
+    run_asusctl(&args.iter().map(String::as_str).collect::<Vec<_>>())?;
+    Ok(())
+}
+
+/// Set the colour of one keyboard zone, keeping the active lighting mode
+///
+/// Uses `asusctl aura <mode> -c <colour> --zone <zone>`; other zones keep
+/// their colour.
+pub fn set_aura_zone_colour(zone: AuraZone, colour: [u8; 3]) -> Result<()> {
+    let mode = get_aura_mode_dbus()
+        .ok()
+        .flatten()
+        .filter(AuraMode::has_colour)
+        .unwrap_or_default();
+    let [r, g, b] = colour;
+    let colour = format!("{r:02x}{g:02x}{b:02x}");
+    run_asusctl(&[
+        "aura",
+        mode.cli_name(),
+        "-c",
+        &colour,
+        "--zone",
+        zone.cli_name(),
+    ])?;
+    Ok(())
+}
+
+/// Apply mode, colour and brightness, one asusctl run after the other
+///
+/// A convenience wrapper: no asusctl release is known to apply
+/// `--kbd-bright` given next to the `aura` command, so there is no
+/// combined run to use.
+pub fn apply_aura(config: &AuraConfig) -> Result<()> {
+    set_aura_mode(config.mode, config.colour, AuraEffect::default())?;
+    set_keyboard_brightness(config.brightness)
+}
+
 // ============================================================================
 // Public API - Power Profiles
 // ============================================================================
 
 /// Get current profile state (active, on AC, on battery) via CLI
 pub fn get_profile_state() -> Result<ProfileState> {
-    let output = run_asusctl(&["profile", "--profile-get"])?;
+    let output = run_asusctl_lenient(&["profile", "--profile-get"])?;
     parse_profile_state(&output)
 }
 
+/// How long after a successful write its change signal is still expected
+///
+/// asusd sends no signal when the value was already set, so the write
+/// must not stay expected forever.
+const OWN_CHANGE_GRACE: Duration = Duration::from_secs(2);
+
+/// A write by this app whose change signal may still arrive
+#[derive(Debug)]
+struct OwnWrite {
+    property: &'static str,
+    value: u32,
+    /// `None` while the write is still running
+    expires: Option<Instant>,
+}
+
+/// Run `write`, so the change signal it causes isn't reported as external
+///
+/// The write is expected from before it starts, as the signal can arrive
+/// before the setter returns. A failed write is forgotten right away.
+fn expecting_change(
+    property: &'static str,
+    value: u32,
+    write: impl FnOnce() -> Result<()>,
+) -> Result<()> {
+    {
+        let mut writes = OWN_WRITES.lock().unwrap_or_else(PoisonError::into_inner);
+        writes.retain(|write| write.property != property);
+        writes.push(OwnWrite {
+            property,
+            value,
+            expires: None,
+        });
+    }
+
+    let result = write();
+
+    let mut writes = OWN_WRITES.lock().unwrap_or_else(PoisonError::into_inner);
+    if result.is_ok() {
+        let expires = Instant::now() + OWN_CHANGE_GRACE;
+        for write in writes.iter_mut() {
+            if write.property == property && write.value == value {
+                write.expires = Some(expires);
+            }
+        }
+    } else {
+        writes.retain(|write| write.property != property || write.value != value);
+    }
+    result
+}
+
+/// Whether `property` changing to `value` is the echo of this app's own write
+///
+/// The write is forgotten once seen, so a later external change back to
+/// the same value is still reported.
+pub fn is_own_change(property: &str, value: u32) -> bool {
+    is_own_change_at(property, value, Instant::now())
+}
+
+fn is_own_change_at(property: &str, value: u32, now: Instant) -> bool {
+    let mut writes = OWN_WRITES.lock().unwrap_or_else(PoisonError::into_inner);
+    writes.retain(|write| write.expires.is_none_or(|expires| expires > now));
+    let own = writes
+        .iter()
+        .any(|write| write.property == property && write.value == value);
+    if own {
+        writes.retain(|write| write.property != property);
+    }
+    own
+}
+
 /// Set the active power profile using powerprofilesctl (preferred) or asusctl (fallback)
 ///
 /// Uses power-profiles-daemon when available to maintain GNOME integration.
 /// Falls back to asusctl if powerprofilesctl is not installed.
 pub fn set_profile(profile: PowerProfile) -> Result<()> {
-    // Try powerprofilesctl first for GNOME integration
-    if set_profile_ppdctl(profile).is_ok() {
-        eprintln!("[asusctl-gui] Set power profile to {profile}, using powerprofilesctl");
-        return Ok(());
+    expecting_change("PlatformProfile", profile.platform_profile_value(), || {
+        // Try powerprofilesctl first for GNOME integration
+        if set_profile_ppdctl(profile).is_ok() {
+            log::debug!("Set power profile to {profile}, using powerprofilesctl");
+            return Ok(());
+        }
+
+        // Fall back to asusctl
+        run_asusctl(&["profile", "--profile-set", &profile.to_string()])?;
+        log::debug!("Set power profile to {profile}, using asusctl");
+        Ok(())
+    })
+}
+
+/// Set profile using powerprofilesctl
+fn set_profile_ppdctl(profile: PowerProfile) -> Result<()> {
+    // Leave the real power-profiles-daemon alone
+    if mock::enabled() {
+        return Err(AsusctlError::NotInstalled);
+    }
+
+    let output = Command::new("powerprofilesctl")
+        .args(["set", profile.ppd_name()])
+        .output()
+        .map_err(|e| {
+            if e.kind() == std::io::ErrorKind::NotFound {
+                AsusctlError::NotInstalled
+            } else {
+                AsusctlError::CommandFailed(e.to_string())
+            }
+        })?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(AsusctlError::CommandFailed(stderr.to_string()));
     }
 
-    // Fall back to asusctl
-    run_asusctl(&["profile", "--profile-set", &profile.to_string()])?;
-    eprintln!("[asusctl-gui] Set power profile to {profile}, using asusctl");
     Ok(())
 }
 
-/// Set profile using powerprofilesctl
-fn set_profile_ppdctl(profile: PowerProfile) -> Result<()> {
-    let profile_name = match profile {
-        PowerProfile::Quiet => "power-saver",
-        PowerProfile::Balanced => "balanced",
-        PowerProfile::Performance => "performance",
-    };
+// Platform properties holding a profile, in platform_profile numbering
+
+fn read_profile_property(property: &str) -> Result<PowerProfile> {
+    let value: u32 = read_dbus_property_at(
+        bus_names().platform_path,
+        bus_names().platform_interface,
+        property,
+    )?;
+
+    PowerProfile::from_platform_profile_value(value)
+        .ok_or_else(|| AsusctlError::ParseError(format!("Unknown {property} value: {value}")))
+}
+
+fn write_profile_property(property: &str, profile: PowerProfile) -> Result<()> {
+    write_dbus_property_at(
+        bus_names().platform_path,
+        bus_names().platform_interface,
+        property,
+        profile.platform_profile_value(),
+    )
+}
+
+/// Get the active profile straight from asusd's D-Bus property
+pub fn get_profile_dbus() -> Result<PowerProfile> {
+    read_profile_property("PlatformProfile")
+}
+
+/// Get the profile asusd switches to when AC power is connected
+pub fn get_profile_on_ac_dbus() -> Result<PowerProfile> {
+    read_profile_property("PlatformProfileOnAc")
+}
+
+/// Set the profile asusd switches to when AC power is connected
+pub fn set_profile_on_ac(profile: PowerProfile) -> Result<()> {
+    write_profile_property("PlatformProfileOnAc", profile)
+}
+
+/// Get the profile asusd switches to when running on battery
+pub fn get_profile_on_battery_dbus() -> Result<PowerProfile> {
+    read_profile_property("PlatformProfileOnBattery")
+}
+
+/// Set the profile asusd switches to when running on battery
+pub fn set_profile_on_battery(profile: PowerProfile) -> Result<()> {
+    write_profile_property("PlatformProfileOnBattery", profile)
+}
+
+// Throttle policy, asusd's own performance mode. It is kept separately from
+// the platform profile.
+
+/// Get the active throttle policy via D-Bus
+pub fn get_throttle_policy() -> Result<PowerProfile> {
+    read_profile_property("ThrottlePolicy")
+}
+
+/// Set the active throttle policy via D-Bus
+pub fn set_throttle_policy(policy: PowerProfile) -> Result<()> {
+    write_profile_property("ThrottlePolicy", policy)
+}
+
+/// Get the throttle policy applied when AC power is connected
+pub fn get_throttle_policy_on_ac() -> Result<PowerProfile> {
+    read_profile_property("ThrottlePolicyOnAc")
+}
+
+/// Set the throttle policy applied when AC power is connected
+pub fn set_throttle_policy_on_ac(policy: PowerProfile) -> Result<()> {
+    write_profile_property("ThrottlePolicyOnAc", policy)
+}
+
+/// Get the throttle policy applied when running on battery
+pub fn get_throttle_policy_on_battery() -> Result<PowerProfile> {
+    read_profile_property("ThrottlePolicyOnBattery")
+}
+
+/// Set the throttle policy applied when running on battery
+pub fn set_throttle_policy_on_battery(policy: PowerProfile) -> Result<()> {
+    write_profile_property("ThrottlePolicyOnBattery", policy)
+}
+
+/// Get charge control threshold via D-Bus
+pub fn get_charge_limit_dbus() -> Result<u8> {
+    read_dbus_property_at(
+        bus_names().platform_path,
+        bus_names().platform_interface,
+        "ChargeControlEndThreshold",
+    )
+}
+
+/// Whether the display's panel overdrive is on
+///
+/// Returns `Unsupported` on models without the setting.
+pub fn get_panel_overdrive() -> Result<bool> {
+    read_dbus_property_at(
+        bus_names().platform_path,
+        bus_names().platform_interface,
+        "PanelOverdrive",
+    )
+}
+
+pub fn set_panel_overdrive(enabled: bool) -> Result<()> {
+    write_dbus_property_at(
+        bus_names().platform_path,
+        bus_names().platform_interface,
+        "PanelOverdrive",
+        enabled,
+    )
+}
 
-    let output = Command::new("powerprofilesctl")
-        .args(["set", profile_name])
-        .output()
-        .map_err(|e| {
-            if e.kind() == std::io::ErrorKind::NotFound {
-                AsusctlError::NotInstalled
-            } else {
-                AsusctlError::CommandFailed(e.to_string())
-            }
-        })?;
+/// Get the GPU MUX mode via D-Bus
+pub fn get_gpu_mux_mode_dbus() -> Result<GpuMuxMode> {
+    let value: u8 = read_dbus_property_at(
+        bus_names().platform_path,
+        bus_names().platform_interface,
+        "GpuMuxMode",
+    )?;
 
-    if !output.status.success() {
-        let stderr = String::from_utf8_lossy(&output.stderr);
-        return Err(AsusctlError::CommandFailed(stderr.to_string()));
+    match value {
+        0 => Ok(GpuMuxMode::Discrete),
+        1 => Ok(GpuMuxMode::Hybrid),
+        value => Err(AsusctlError::ParseError(format!(
+            "Unknown GPU MUX mode: {value}"
+        ))),
     }
+}
 
+/// Lowest and highest charge limit asusd accepts, in percent
+pub const CHARGE_LIMIT_RANGE: std::ops::RangeInclusive<u8> = 20..=100;
+
+pub(super) fn check_charge_limit(limit: u8) -> Result<()> {
+    if !CHARGE_LIMIT_RANGE.contains(&limit) {
+        return Err(AsusctlError::ParseError(format!(
+            "Charge limit {limit}% is outside {}-{}%",
+            CHARGE_LIMIT_RANGE.start(),
+            CHARGE_LIMIT_RANGE.end()
+        )));
+    }
     Ok(())
 }
 
-/// Get charge control threshold via D-Bus
-pub fn get_charge_limit_dbus() -> Result<u8> {
-    let output = read_dbus_property_at(
-        PLATFORM_PATH,
-        PLATFORM_INTERFACE,
+/// Set charge control threshold via D-Bus
+pub fn set_charge_limit_dbus(limit: u8) -> Result<()> {
+    check_charge_limit(limit)?;
+    write_dbus_property_at(
+        bus_names().platform_path,
+        bus_names().platform_interface,
         "ChargeControlEndThreshold",
-    )?;
-    parse_dbus_byte(&output)
+        limit,
+    )
 }
 
-/// Set charge limit (20-100)
+/// Set charge limit (20-100), over D-Bus with the CLI as fallback
 pub fn set_charge_limit(limit: u8) -> Result<()> {
-    run_asusctl(&["--chg-limit", &limit.to_string()])?;
-    Ok(())
+    check_charge_limit(limit)?;
+    expecting_change("ChargeControlEndThreshold", limit.into(), || {
+        if set_charge_limit_dbus(limit).is_ok() {
+            return Ok(());
+        }
+
+        run_asusctl(&["--chg-limit", &limit.to_string()])?;
+        Ok(())
+    })
 }
 
 // ============================================================================
@@ -732,6 +2235,13 @@ pub fn disable_slash() -> Result<()> {
     Ok(())
 }
 
+/// Brightness restored when slash is switched on while its brightness is 0
+///
+/// Some firmwares treat brightness 0 as off even when `Enabled` is true, so
+/// the UI shows slash as disabled in that case and uses this value when the
+/// user switches it back on.
+pub const SLASH_DEFAULT_BRIGHTNESS: u8 = 128;
+
 /// Set slash brightness (0-255)
 pub fn set_slash_brightness(brightness: u8) -> Result<()> {
     run_asusctl(&["slash", "--brightness", &brightness.to_string()])?;
@@ -744,90 +2254,154 @@ pub fn set_slash_mode(mode: SlashMode) -> Result<()> {
     Ok(())
 }
 
+/// Slowest animation interval asusd accepts
+pub const SLASH_MAX_INTERVAL: u8 = 5;
+
+/// Speed names, indexed by interval
+///
+/// `asusctl slash --interval`, the D-Bus property and slash.ron all carry
+/// the same 0-5 value, so the interval is the index into this list. The
+/// order follows asusd's name for the value, the interval between frames,
+/// so a longer one plays slower; it hasn't been checked on hardware.
+pub const SLASH_SPEED_LABELS: [&str; SLASH_MAX_INTERVAL as usize + 1] = [
+    "Fastest",
+    "Very Fast",
+    "Fast",
+    "Slow",
+    "Very Slow",
+    "Slowest",
+];
+
+fn check_slash_interval(interval: u8) -> Result<()> {
+    if interval > SLASH_MAX_INTERVAL {
+        return Err(AsusctlError::ParseError(format!(
+            "Slash interval must be 0-{SLASH_MAX_INTERVAL}, got {interval}"
+        )));
+    }
+    Ok(())
+}
+
 /// Set slash interval (0-5)
 pub fn set_slash_interval(interval: u8) -> Result<()> {
+    check_slash_interval(interval)?;
     run_asusctl(&["slash", "--interval", &interval.to_string()])?;
     Ok(())
 }
 
+/// Mode restored by `reset_slash_defaults`
+pub const SLASH_DEFAULT_MODE: SlashMode = SlashMode::Flow;
+
+/// Interval restored by `reset_slash_defaults`
+pub const SLASH_DEFAULT_INTERVAL: u8 = 0;
+
+/// Switch the bar on with the default brightness, mode and interval
+///
+/// The show-on events are left alone.
+pub fn reset_slash_defaults() -> Result<()> {
+    enable_slash()?;
+    set_slash_brightness(SLASH_DEFAULT_BRIGHTNESS)?;
+    set_slash_mode(SLASH_DEFAULT_MODE)?;
+    set_slash_interval(SLASH_DEFAULT_INTERVAL)
+}
+
 // Slash D-Bus getters
 
-fn get_slash_enabled_dbus() -> Result<bool> {
-    let path = get_slash_path()
-        .ok_or_else(|| AsusctlError::CommandFailed("Slash D-Bus path not found".to_string()))?;
-    let output = read_dbus_property_at(path, SLASH_INTERFACE, "Enabled")?;
-    parse_dbus_bool(&output)
+fn get_slash_enabled_dbus(path: &str) -> Result<bool> {
+    read_dbus_property_at(path, bus_names().slash_interface, "Enabled")
+}
+
+fn get_slash_brightness_dbus(path: &str) -> Result<u8> {
+    read_dbus_property_at(path, bus_names().slash_interface, "Brightness")
+}
+
+fn get_slash_interval_dbus(path: &str) -> Result<u8> {
+    read_dbus_property_at(path, bus_names().slash_interface, "Interval")
 }
 
-fn get_slash_brightness_dbus() -> Result<u8> {
-    let path = get_slash_path()
-        .ok_or_else(|| AsusctlError::CommandFailed("Slash D-Bus path not found".to_string()))?;
-    let output = read_dbus_property_at(path, SLASH_INTERFACE, "Brightness")?;
-    parse_dbus_byte(&output)
+fn get_slash_mode_dbus(path: &str) -> Result<SlashMode> {
+    SlashMode::from_u8(read_dbus_property_at(
+        path,
+        bus_names().slash_interface,
+        "Mode",
+    )?)
 }
 
-fn get_slash_interval_dbus() -> Result<u8> {
-    let path = get_slash_path()
-        .ok_or_else(|| AsusctlError::CommandFailed("Slash D-Bus path not found".to_string()))?;
-    let output = read_dbus_property_at(path, SLASH_INTERFACE, "Interval")?;
-    parse_dbus_byte(&output)
+/// Read a Slash value from D-Bus, or from the config file when there is no Slash object
+///
+/// Errors reading an object that does exist are returned as is, so a
+/// transient failure doesn't show stale values from the config file. Without
+/// a config file an `Unsupported` lookup error is kept, so a laptop without a
+/// Slash bar is not reported as a broken config.
+fn read_slash<T>(
+    from_dbus: impl FnOnce(&str) -> Result<T>,
+    from_config: impl FnOnce(SlashState) -> T,
+) -> Result<T> {
+    match slash_path() {
+        Ok(path) => from_dbus(path),
+        Err(lookup_error) => parse_slash_config()
+            .map(from_config)
+            .map_err(|config_error| match lookup_error {
+                AsusctlError::Unsupported(_) => lookup_error,
+                _ => config_error,
+            }),
+    }
 }
 
 /// Get slash enabled state (D-Bus preferred, config fallback)
 pub fn get_slash_enabled() -> Result<bool> {
-    get_slash_enabled_dbus().or_else(|_| Ok(parse_slash_config()?.enabled))
+    read_slash(get_slash_enabled_dbus, |config| config.enabled)
 }
 
 /// Get slash brightness (D-Bus preferred, config fallback)
 pub fn get_slash_brightness() -> Result<u8> {
-    get_slash_brightness_dbus().or_else(|_| Ok(parse_slash_config()?.brightness))
+    read_slash(get_slash_brightness_dbus, |config| config.brightness)
 }
 
 /// Get slash interval (D-Bus preferred, config fallback)
 pub fn get_slash_interval() -> Result<u8> {
-    get_slash_interval_dbus().or_else(|_| Ok(parse_slash_config()?.interval))
+    read_slash(get_slash_interval_dbus, |config| config.interval)
 }
 
-/// Get slash mode (from config file)
+/// Get slash mode (D-Bus preferred, config fallback)
 pub fn get_slash_mode() -> Result<SlashMode> {
-    Ok(parse_slash_config()?.mode)
+    read_slash(get_slash_mode_dbus, |config| config.mode)
+}
+
+/// Get the full slash state (enabled, brightness, interval, mode)
+pub fn get_slash_state() -> Result<SlashState> {
+    Ok(SlashState {
+        enabled: get_slash_enabled()?,
+        brightness: get_slash_brightness()?,
+        interval: get_slash_interval()?,
+        mode: get_slash_mode()?,
+    })
 }
 
 // Slash show-on event getters (D-Bus only)
 
 pub fn get_slash_show_on_boot() -> Result<bool> {
-    let path = get_slash_path()
-        .ok_or_else(|| AsusctlError::CommandFailed("Slash D-Bus path not found".to_string()))?;
-    let output = read_dbus_property_at(path, SLASH_INTERFACE, "ShowOnBoot")?;
-    parse_dbus_bool(&output)
+    let path = slash_path()?;
+    read_dbus_property_at(path, bus_names().slash_interface, "ShowOnBoot")
 }
 
 pub fn get_slash_show_on_shutdown() -> Result<bool> {
-    let path = get_slash_path()
-        .ok_or_else(|| AsusctlError::CommandFailed("Slash D-Bus path not found".to_string()))?;
-    let output = read_dbus_property_at(path, SLASH_INTERFACE, "ShowOnShutdown")?;
-    parse_dbus_bool(&output)
+    let path = slash_path()?;
+    read_dbus_property_at(path, bus_names().slash_interface, "ShowOnShutdown")
 }
 
 pub fn get_slash_show_on_sleep() -> Result<bool> {
-    let path = get_slash_path()
-        .ok_or_else(|| AsusctlError::CommandFailed("Slash D-Bus path not found".to_string()))?;
-    let output = read_dbus_property_at(path, SLASH_INTERFACE, "ShowOnSleep")?;
-    parse_dbus_bool(&output)
+    let path = slash_path()?;
+    read_dbus_property_at(path, bus_names().slash_interface, "ShowOnSleep")
 }
 
 pub fn get_slash_show_on_battery() -> Result<bool> {
-    let path = get_slash_path()
-        .ok_or_else(|| AsusctlError::CommandFailed("Slash D-Bus path not found".to_string()))?;
-    let output = read_dbus_property_at(path, SLASH_INTERFACE, "ShowOnBattery")?;
-    parse_dbus_bool(&output)
+    let path = slash_path()?;
+    read_dbus_property_at(path, bus_names().slash_interface, "ShowOnBattery")
 }
 
 pub fn get_slash_show_battery_warning() -> Result<bool> {
-    let path = get_slash_path()
-        .ok_or_else(|| AsusctlError::CommandFailed("Slash D-Bus path not found".to_string()))?;
-    let output = read_dbus_property_at(path, SLASH_INTERFACE, "ShowBatteryWarning")?;
-    parse_dbus_bool(&output)
+    let path = slash_path()?;
+    read_dbus_property_at(path, bus_names().slash_interface, "ShowBatteryWarning")
 }
 
 // Slash show-on event setters
@@ -877,6 +2451,24 @@ pub fn set_slash_show_battery_warning(value: bool) -> Result<()> {
     Ok(())
 }
 
+// ============================================================================
+// Public API - Status
+// ============================================================================
+
+/// Gather the current state of all supported subsystems
+pub fn get_status() -> Status {
+    Status {
+        settings: SettingsBackup::read(),
+        supported_features: get_supported_features().ok(),
+    }
+}
+
+/// Gather the current state and serialize it as pretty-printed JSON
+pub fn get_status_json() -> Result<String> {
+    serde_json::to_string_pretty(&get_status())
+        .map_err(|e| AsusctlError::ParseError(format!("Failed to serialize status: {e}")))
+}
+
 // ============================================================================
 // Tests
 // ============================================================================
@@ -885,22 +2477,8 @@ pub fn set_slash_show_battery_warning(value: bool) -> Result<()> {
 mod tests {
     use super::*;
 
-    fn parse_keyboard_brightness(output: &str) -> Result<KeyboardBrightness> {
-        for line in output.lines() {
-            if line.contains("Current keyboard led brightness:") {
-                let level = line
-                    .split(':')
-                    .nth(1)
-                    .ok_or_else(|| {
-                        AsusctlError::ParseError("Missing brightness value".to_string())
-                    })?
-                    .trim();
-                return KeyboardBrightness::from_str(level);
-            }
-        }
-        Err(AsusctlError::ParseError(
-            "Could not find brightness level in output".to_string(),
-        ))
+    fn owned_value<'a>(value: impl Into<Value<'a>>) -> OwnedValue {
+        value.into().try_into_owned().unwrap()
     }
 
     #[test]
@@ -917,6 +2495,190 @@ asusctl version: 6.2.0
         assert_eq!(info.board_name, "GA403UV");
     }
 
+    #[test]
+    fn test_parse_aura_zones() {
+        let output = "Supported Aura Modes:\n[Static, Breathe]\nSupported Aura Zones:\n[\n    Key1,\n    Key2,\n    Logo,\n]\n";
+        let features = parse_supported_features(output).unwrap();
+        assert_eq!(
+            features.aura_zones,
+            [AuraZone::Key1, AuraZone::Key2, AuraZone::Logo]
+        );
+
+        let features = parse_supported_features("Supported Aura Modes:\n[Static]\n").unwrap();
+        assert!(features.aura_zones.is_empty());
+    }
+
+    #[test]
+    fn test_supported_features_has_any() {
+        let none = parse_supported_features("Supported Core Functions:\n").unwrap();
+        assert!(!none.has_any());
+
+        let slash_only =
+            parse_supported_features("Supported Core Functions:\n[\n    xyz.ljones.Slash,\n]\n")
+                .unwrap();
+        assert!(slash_only.has_any());
+
+        // An interface name outside its section doesn't count
+        let stray = parse_supported_features("xyz.ljones.Slash\n").unwrap();
+        assert!(!stray.has_any());
+    }
+
+    // Hand-written layouts, not captures from a real asusctl: quoted items,
+    // reordered and unknown sections, and lists on a single line. Verbatim
+    // `--show-supported` output from asusctl 6.0, 6.1 and 6.2 still needs to
+    // be collected on real hardware and added next to them.
+    const SUPPORTED_QUOTED: &str = r#"Supported Core Functions:
+[
+    "xyz.ljones.Aura",
+    "xyz.ljones.FanCurves",
+    "xyz.ljones.Platform",
+]
+Supported Platform Properties:
+[
+    "xyz.ljones.Platform: ChargeControlEndThreshold",
+    "xyz.ljones.Platform: ThrottlePolicy",
+]
+Supported Keyboard Brightness:
+[
+    "Off",
+    "Low",
+    "Med",
+    "High",
+]
+Supported Aura Modes:
+[
+    "Static",
+    "Breathe",
+]
+Supported Aura Zones:
+[]
+"#;
+
+    const SUPPORTED_REORDERED: &str = "Supported Aura Modes:
+[
+    Static,
+    Breathe,
+    Pulse,
+    Rainbow,
+]
+Supported Aura Power States:
+[
+    KbAuraPowerState [Boot, Awake],
+]
+Supported Core Functions:
+[
+    xyz.ljones.Aura,
+    xyz.ljones.Platform,
+    xyz.ljones.Slash,
+]
+Supported Aura Zones:
+[
+    Logo,
+    BarLeft,
+    BarRight,
+]
+Supported Platform Properties:
+[
+    ChargeControlEndThreshold,
+]
+";
+
+    const SUPPORTED_INLINE: &str =
+        "  Supported Core Functions: [xyz.ljones.Platform, xyz.ljones.Anime]\r
+  Supported Platform Properties: [xyz.ljones.Platform: PanelOverdrive]\r
+  Supported Keyboard Brightness: []\r
+";
+
+    #[test]
+    fn test_parse_supported_quoted() {
+        let features = parse_supported_features(SUPPORTED_QUOTED).unwrap();
+        assert!(features.has_aura && features.has_fan_curves && features.has_platform);
+        assert!(!features.has_slash && !features.has_anime);
+        assert!(features.has_charge_control && features.has_throttle_policy);
+        assert_eq!(
+            features.keyboard_brightness_levels.as_deref(),
+            Some(&KeyboardBrightness::ALL[..])
+        );
+        assert_eq!(features.aura_modes, [AuraMode::Static, AuraMode::Breathe]);
+        assert!(features.aura_zones.is_empty());
+    }
+
+    #[test]
+    fn test_parse_supported_reordered() {
+        let features = parse_supported_features(SUPPORTED_REORDERED).unwrap();
+        assert!(features.has_aura && features.has_platform && features.has_slash);
+        assert!(!features.has_fan_curves);
+        assert!(features.has_charge_control && !features.has_throttle_policy);
+        // Missing section
+        assert_eq!(features.keyboard_brightness_levels, None);
+        // Modes this app doesn't offer are skipped
+        assert_eq!(
+            features.aura_modes,
+            [AuraMode::Static, AuraMode::Breathe, AuraMode::Pulse]
+        );
+        assert_eq!(
+            features.aura_zones,
+            [AuraZone::Logo, AuraZone::BarLeft, AuraZone::BarRight]
+        );
+    }
+
+    #[test]
+    fn test_parse_supported_inline() {
+        let features = parse_supported_features(SUPPORTED_INLINE).unwrap();
+        assert!(features.has_platform && features.has_anime);
+        assert!(!features.has_aura);
+        assert!(!features.has_charge_control && !features.has_throttle_policy);
+        // Listed, but empty
+        assert_eq!(features.keyboard_brightness_levels, Some(Vec::new()));
+        assert!(features.aura_modes.is_empty());
+    }
+
+    #[test]
+    fn test_parse_sections_keeps_nested_items() {
+        let sections = parse_sections(SUPPORTED_REORDERED);
+        let (_, power) = sections
+            .iter()
+            .find(|(header, _)| header == "Supported Aura Power States")
+            .unwrap();
+        assert_eq!(power, &["KbAuraPowerState [Boot, Awake]"]);
+    }
+
+    #[test]
+    fn test_keyboard_brightness_numeric_round_trip() {
+        for level in KeyboardBrightness::ALL {
+            assert_eq!(KeyboardBrightness::from_u8(level.as_u8()).unwrap(), level);
+            assert_eq!(KeyboardBrightness::from_percent(level.to_percent()), level);
+            assert_eq!(
+                level.to_string().parse::<KeyboardBrightness>().unwrap(),
+                level
+            );
+        }
+        assert!(KeyboardBrightness::from_u8(4).is_err());
+
+        let percents = KeyboardBrightness::ALL.map(KeyboardBrightness::to_percent);
+        assert_eq!(percents, [0, 33, 67, 100]);
+    }
+
+    #[test]
+    fn test_keyboard_brightness_from_percent_rounds() {
+        assert_eq!(
+            KeyboardBrightness::from_percent(16),
+            KeyboardBrightness::Off
+        );
+        assert_eq!(
+            KeyboardBrightness::from_percent(17),
+            KeyboardBrightness::Low
+        );
+        assert_eq!(
+            KeyboardBrightness::from_percent(50),
+            KeyboardBrightness::Med
+        );
+        assert_eq!(
+            KeyboardBrightness::from_percent(255),
+            KeyboardBrightness::High
+        );
+    }
+
     #[test]
     fn test_parse_keyboard_brightness() {
         let output = "Starting version 6.2.0\nCurrent keyboard led brightness: High";
@@ -932,9 +2694,134 @@ Profile on AC is Quiet
 Profile on Battery is Quiet"#;
 
         let state = parse_profile_state(output).unwrap();
-        assert_eq!(state.active, PowerProfile::Quiet);
-        assert_eq!(state.on_ac, PowerProfile::Quiet);
-        assert_eq!(state.on_battery, PowerProfile::Quiet);
+        assert_eq!(state.active, Some(PowerProfile::Quiet));
+        assert_eq!(state.on_ac, Some(PowerProfile::Quiet));
+        assert_eq!(state.on_battery, Some(PowerProfile::Quiet));
+    }
+
+    // Hand-written, not output of any asusctl release: colour codes,
+    // markers, colons, other casing and trailing punctuation, in a
+    // different order
+    const PROFILE_STATE_REFORMATTED: &str = "  * Profile on Battery: \"quiet\".
+\x1b[32mActive profile is Performance\x1b[0m
+- PROFILE ON AC IS Balanced,
+";
+
+    #[test]
+    fn test_parse_profile_state_reformatted() {
+        let state = parse_profile_state(PROFILE_STATE_REFORMATTED).unwrap();
+        assert_eq!(state.active, Some(PowerProfile::Performance));
+        assert_eq!(state.on_ac, Some(PowerProfile::Balanced));
+        assert_eq!(state.on_battery, Some(PowerProfile::Quiet));
+
+        // An unknown name only loses its own line
+        let state = parse_profile_state(
+            "Active profile is LowPower\nProfile on AC is Performance\nProfile on Battery is Quiet",
+        )
+        .unwrap();
+        assert_eq!(state.active, None);
+        assert_eq!(state.on_ac, Some(PowerProfile::Performance));
+        assert_eq!(state.on_battery, Some(PowerProfile::Quiet));
+    }
+
+    #[test]
+    fn test_strip_ansi() {
+        assert_eq!(strip_ansi("\x1b[1;32mQuiet\x1b[0m"), "Quiet");
+        assert_eq!(strip_ansi("plain"), "plain");
+    }
+
+    #[test]
+    fn test_status_json_uses_null_for_unavailable_fields() {
+        let status = Status {
+            settings: SettingsBackup {
+                profile: Some(PowerProfile::Quiet),
+                charge_limit: Some(80),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        let json: serde_json::Value = serde_json::to_value(&status).unwrap();
+        assert_eq!(json["profile"], "Quiet");
+        assert_eq!(json["charge_limit"], 80);
+        assert!(json["keyboard_brightness"].is_null());
+        assert!(json["slash"].is_null());
+    }
+
+    #[test]
+    fn test_power_profile_external_names_round_trip() {
+        for profile in PowerProfile::ALL {
+            assert_eq!(
+                PowerProfile::from_ppd_name(profile.ppd_name()),
+                Some(profile)
+            );
+            assert_eq!(
+                PowerProfile::from_platform_profile_value(profile.platform_profile_value()),
+                Some(profile)
+            );
+        }
+        assert_eq!(PowerProfile::from_ppd_name("unknown"), None);
+        assert_eq!(PowerProfile::from_platform_profile_value(42), None);
+    }
+
+    #[test]
+    fn test_aura_mode_from_led_mode() {
+        assert_eq!(AuraMode::from_led_mode(0), Some(AuraMode::Static));
+        assert_eq!(AuraMode::from_led_mode(1), Some(AuraMode::Breathe));
+        assert_eq!(AuraMode::from_led_mode(3), Some(AuraMode::RainbowWave));
+        assert_eq!(AuraMode::from_led_mode(10), Some(AuraMode::Pulse));
+        // Rainbow cycle and other modes aren't offered by the app
+        assert_eq!(AuraMode::from_led_mode(2), None);
+    }
+
+    #[test]
+    fn test_aura_mode_args() {
+        let red = [0xff, 0, 0];
+        assert_eq!(
+            aura_mode_args(AuraMode::Static, red, AuraEffect::default()),
+            ["aura", "static", "-c", "ff0000"]
+        );
+        assert_eq!(
+            aura_mode_args(AuraMode::Breathe, red, AuraEffect::default()),
+            ["aura", "breathe", "-c", "ff0000", "-s", "med"]
+        );
+
+        let effect = AuraEffect {
+            speed: Some(AuraSpeed::High),
+            direction: Some(AuraDirection::Up),
+        };
+        assert_eq!(
+            aura_mode_args(AuraMode::Pulse, red, effect),
+            ["aura", "pulse", "-c", "ff0000"]
+        );
+        assert_eq!(
+            aura_mode_args(AuraMode::RainbowWave, red, effect),
+            ["aura", "rainbow-wave", "-s", "high", "-d", "up"]
+        );
+    }
+
+    #[test]
+    fn test_led_power_states() {
+        let states = vec![
+            (0u32, true, true, false, true),
+            (1u32, true, true, true, false),
+        ];
+        let data = owned_value((states.clone(),));
+        assert_eq!(led_power_states(data).unwrap(), states);
+
+        assert!(matches!(
+            led_power_states(owned_value((1u32, true))),
+            Err(AsusctlError::Unsupported(_))
+        ));
+    }
+
+    #[test]
+    fn test_led_mode_colour() {
+        let data = Value::from((0u32, 0u32, (255u8, 128u8, 0u8), (0u8, 0u8, 0u8), 1u32, 0u32));
+        assert_eq!(led_mode_colour(&data).unwrap(), [255, 128, 0]);
+
+        assert!(led_mode_colour(&Value::from((0u32, 0u32))).is_err());
+        assert!(led_mode_colour(&Value::from(2u32)).is_err());
     }
 
     #[test]
@@ -948,4 +2835,432 @@ Profile on Battery is Quiet"#;
             KeyboardBrightness::Off
         );
     }
+
+    #[test]
+    fn test_slash_mode_from_u8() {
+        let cases = [
+            (0x10, SlashMode::Bounce),
+            (0x12, SlashMode::Slash),
+            (0x13, SlashMode::Loading),
+            (0x1d, SlashMode::BitStream),
+            (0x1a, SlashMode::Transmission),
+            (0x19, SlashMode::Flow),
+            (0x25, SlashMode::Flux),
+            (0x24, SlashMode::Phantom),
+            (0x26, SlashMode::Spectrum),
+            (0x32, SlashMode::Hazard),
+            (0x33, SlashMode::Interfacing),
+            (0x34, SlashMode::Ramp),
+            (0x42, SlashMode::GameOver),
+            (0x43, SlashMode::Start),
+            (0x44, SlashMode::Buzzer),
+        ];
+        assert_eq!(cases.len(), SlashMode::ALL.len());
+
+        for (value, mode) in cases {
+            assert_eq!(SlashMode::from_u8(value).unwrap(), mode);
+            // Keep the numeric table in step with the names used by Display/FromStr
+            assert_eq!(SlashMode::from_str(&mode.to_string()).unwrap(), mode);
+        }
+
+        for value in [0x00, 0x11, 0x45, 0xff] {
+            assert!(matches!(
+                SlashMode::from_u8(value),
+                Err(AsusctlError::ParseError(_))
+            ));
+        }
+    }
+
+    #[test]
+    fn test_slash_mode_index() {
+        for (index, mode) in SlashMode::ALL.iter().enumerate() {
+            assert_eq!(mode.index(), index as u32);
+            assert_eq!(SlashMode::from_index(mode.index()), Some(*mode));
+        }
+        assert_eq!(SlashMode::from_index(SlashMode::ALL.len() as u32), None);
+    }
+
+    #[test]
+    fn test_set_charge_limit_rejects_out_of_range() {
+        for limit in [0, 19, 101, u8::MAX] {
+            assert!(matches!(
+                set_charge_limit(limit),
+                Err(AsusctlError::ParseError(_))
+            ));
+            assert!(matches!(
+                set_charge_limit_dbus(limit),
+                Err(AsusctlError::ParseError(_))
+            ));
+        }
+
+        assert!(check_charge_limit(20).is_ok());
+        assert!(check_charge_limit(100).is_ok());
+    }
+
+    /// slash.ron in the layout asusd writes, written by hand
+    const SLASH_RON: &str = r#"(
+    enabled: true,
+    brightness: 185,
+    display_interval: 3,
+    display_mode: BitStream,
+    show_on_boot: true,
+    show_on_shutdown: true,
+    show_on_sleep: false,
+    show_on_battery: true,
+    show_battery_warning: true,
+)
+"#;
+
+    #[test]
+    fn test_parse_slash_ron() {
+        let state = parse_slash_ron(SLASH_RON);
+        assert!(state.enabled);
+        assert_eq!(state.brightness, 185);
+        assert_eq!(state.interval, 3);
+        assert_eq!(state.mode, SlashMode::BitStream);
+    }
+
+    #[test]
+    fn test_parse_slash_config_at() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("slash.ron");
+        fs::write(&path, SLASH_RON).unwrap();
+
+        assert_eq!(
+            parse_slash_config_at(&path).unwrap(),
+            parse_slash_ron(SLASH_RON)
+        );
+
+        let error = parse_slash_config_at(&dir.path().join("missing.ron")).unwrap_err();
+        assert!(error.to_string().contains("missing.ron"));
+    }
+
+    #[test]
+    fn test_parse_slash_ron_keeps_defaults_on_bad_lines() {
+        let defaults = SlashState::default();
+        let state = parse_slash_ron(
+            "(\n    enabled,\n    brightness: 300,\n    display_interval: fast,\n    display_mode: (Flow),\n    :\n)",
+        );
+        assert_eq!(state.enabled, defaults.enabled);
+        assert_eq!(state.brightness, defaults.brightness);
+        assert_eq!(state.interval, defaults.interval);
+        assert_eq!(state.mode, defaults.mode);
+    }
+
+    #[test]
+    fn test_extract_string_value() {
+        assert_eq!(
+            extract_string_value("display_mode: Flow,").as_deref(),
+            Some("Flow")
+        );
+        assert_eq!(
+            extract_string_value("  display_mode :  \"Flow\" ,").as_deref(),
+            Some("Flow")
+        );
+        assert_eq!(
+            extract_string_value(r#"name: "a: b, c","#).as_deref(),
+            Some("a: b, c")
+        );
+        assert_eq!(
+            extract_string_value("brightness: 255, // max").as_deref(),
+            Some("255")
+        );
+        assert_eq!(extract_string_value("colour: (r: 1, g: 2),"), None);
+        assert_eq!(extract_string_value(r#"name: "unterminated,"#), None);
+        assert_eq!(extract_string_value("display_mode:"), None);
+        assert_eq!(extract_string_value("no separator"), None);
+
+        assert_eq!(extract_number("brightness: 255,"), Some(255));
+        assert_eq!(extract_number("brightness: -1,"), None);
+    }
+
+    #[test]
+    fn test_set_slash_interval_rejects_out_of_range() {
+        assert!(check_slash_interval(0).is_ok());
+        assert!(check_slash_interval(SLASH_MAX_INTERVAL).is_ok());
+        for interval in [SLASH_MAX_INTERVAL + 1, 255] {
+            assert!(matches!(
+                set_slash_interval(interval),
+                Err(AsusctlError::ParseError(_))
+            ));
+        }
+    }
+
+    #[test]
+    fn test_parse_introspection() {
+        let xml = r#"<!DOCTYPE node PUBLIC "-//freedesktop//DTD D-BUS Object Introspection 1.0//EN"
+ "http://www.freedesktop.org/standards/dbus/1.0/introspect.dtd">
+<node name="/xyz/ljones">
+  <interface name="org.freedesktop.DBus.Introspectable">
+    <method name="Introspect"><arg type="s" direction="out"/></method>
+  </interface>
+  <interface name="xyz.ljones.Platform">
+    <property name="ChargeControlEndThreshold" type="y" access="readwrite"/>
+  </interface>
+  <node name="aura"/>
+  <node name="slash_193b_5_5"/>
+</node>"#;
+
+        let object = Introspection::parse(xml).unwrap();
+        assert_eq!(
+            object.interfaces,
+            ["org.freedesktop.DBus.Introspectable", "xyz.ljones.Platform"]
+        );
+        assert_eq!(object.children, ["aura", "slash_193b_5_5"]);
+        assert!(Introspection::parse("<node><interface").is_err());
+        assert_eq!(join_object_path("/", "xyz"), "/xyz");
+        assert_eq!(join_object_path("/xyz/ljones", "aura"), "/xyz/ljones/aura");
+    }
+
+    #[test]
+    fn test_cached_keeps_only_successes() {
+        let cache = Mutex::new(None);
+
+        let failed: Result<u32> = cached(&cache, || {
+            Err(AsusctlError::CommandFailed("boom".to_string()))
+        });
+        assert!(failed.is_err());
+        assert_eq!(cached(&cache, || Ok(1)).unwrap(), 1);
+        assert_eq!(cached(&cache, || Ok(2)).unwrap(), 1);
+
+        cache.lock().unwrap().take();
+        assert_eq!(cached(&cache, || Ok(3)).unwrap(), 3);
+    }
+
+    #[test]
+    fn test_run_with_timeout() {
+        let output = run_with_timeout(Command::new("echo").arg("hello"), Duration::from_secs(5))
+            .unwrap()
+            .unwrap();
+        assert!(output.status.success());
+        assert_eq!(output.stdout, b"hello\n");
+
+        let start = Instant::now();
+        let output =
+            run_with_timeout(Command::new("sleep").arg("5"), Duration::from_millis(100)).unwrap();
+        assert!(output.is_none());
+        assert!(start.elapsed() < Duration::from_secs(5));
+    }
+
+    #[test]
+    fn test_queued_setters_run_in_order_through_the_runner() {
+        use std::os::unix::process::ExitStatusExt;
+        use std::process::ExitStatus;
+
+        static LOG: Mutex<Vec<String>> = Mutex::new(Vec::new());
+
+        fn fake_asusctl(args: &[&str]) -> std::io::Result<Option<Output>> {
+            let command = args.join(" ");
+            LOG.lock().unwrap().push(format!("start {command}"));
+            // A slow first run would be overtaken if the queue overlapped runs
+            if args.contains(&"--mode") {
+                thread::sleep(Duration::from_millis(50));
+            }
+            LOG.lock().unwrap().push(format!("end {command}"));
+
+            let (code, stderr) = if args.contains(&"--disable") {
+                (1, "Error: device busy\n")
+            } else {
+                (0, "")
+            };
+            Ok(Some(Output {
+                status: ExitStatus::from_raw(code << 8),
+                stdout: Vec::new(),
+                stderr: stderr.into(),
+            }))
+        }
+
+        *ASUSCTL_RUNNER.write().unwrap() = fake_asusctl;
+        let queue = super::super::queue::CommandQueue::new();
+        let receivers = [
+            queue.submit(|| set_slash_mode(SlashMode::Ramp)),
+            queue.submit(|| set_slash_brightness(40)),
+            queue.submit(disable_slash),
+            queue.submit(|| set_slash_interval(2)),
+        ];
+        let results: Vec<_> = receivers
+            .into_iter()
+            .map(|receiver| {
+                gtk4::glib::MainContext::default()
+                    .block_on(receiver)
+                    .unwrap()
+            })
+            .collect();
+        *ASUSCTL_RUNNER.write().unwrap() = run_asusctl_process;
+
+        assert!(results[0].is_ok() && results[1].is_ok() && results[3].is_ok());
+        assert!(
+            matches!(&results[2], Err(AsusctlError::CommandFailed(message)) if message == "Error: device busy")
+        );
+        let expected: Vec<String> = [
+            "slash --mode Ramp",
+            "slash --brightness 40",
+            "slash --disable",
+            "slash --interval 2",
+        ]
+        .iter()
+        .flat_map(|command| [format!("start {command}"), format!("end {command}")])
+        .collect();
+        assert_eq!(*LOG.lock().unwrap(), expected);
+    }
+
+    #[test]
+    fn test_asusd_unreachable() {
+        let unreachable = [
+            "Error: Failed to connect to the system bus: Connection refused",
+            "org.freedesktop.DBus.Error.NameHasNoOwner: Could not get owner of name 'xyz.ljones.Asusd'",
+            "Error: org.freedesktop.DBus.Error.ServiceUnknown: The name xyz.ljones.Asusd was not provided by any .service files",
+        ];
+        for stderr in unreachable {
+            assert!(asusd_unreachable(stderr), "{stderr}");
+        }
+
+        let other = [
+            "",
+            "asusd: charge limit must be between 20 and 100",
+            "Error: xyz.ljones.Asusd: Unsupported mode for this keyboard",
+        ];
+        for stderr in other {
+            assert!(!asusd_unreachable(stderr), "{stderr}");
+        }
+    }
+
+    #[test]
+    fn test_check_asusctl_output_reports_failures() {
+        let result = check_asusctl_output(
+            false,
+            String::new(),
+            "Error: invalid value for --chg-limit\n",
+            false,
+        );
+        assert!(
+            matches!(result, Err(AsusctlError::CommandFailed(msg)) if msg == "Error: invalid value for --chg-limit")
+        );
+
+        // Strict commands fail even if something was printed
+        let result = check_asusctl_output(false, "partial\n".to_string(), "", false);
+        assert!(matches!(result, Err(AsusctlError::CommandFailed(_))));
+
+        let result = check_asusctl_output(false, String::new(), "Connection refused", false);
+        assert!(matches!(result, Err(AsusctlError::ServiceNotRunning)));
+    }
+
+    #[test]
+    fn test_permission_denied() {
+        let denied = [
+            "Error: Permission denied (os error 13)",
+            "GDBus.Error:org.freedesktop.DBus.Error.AccessDenied: Rejected send message",
+            "GDBus.Error:org.freedesktop.PolicyKit1.Error.NotAuthorized: Not authorized",
+            "org.freedesktop.DBus.Error.InteractiveAuthorizationRequired: Interactive authentication required.",
+        ];
+        for message in denied {
+            assert!(permission_denied(message), "{message}");
+            assert!(
+                matches!(
+                    classify_dbus_error(message),
+                    AsusctlError::PermissionDenied(_)
+                ),
+                "{message}"
+            );
+        }
+        assert!(!permission_denied("Error: invalid value for --chg-limit"));
+
+        let result = check_asusctl_output(false, String::new(), denied[0], false);
+        assert!(matches!(result, Err(AsusctlError::PermissionDenied(_))));
+    }
+
+    #[test]
+    fn test_check_asusctl_output_lenient_keeps_usable_stdout() {
+        let stdout = "asusctl v6.0.12\n".to_string();
+        let result = check_asusctl_output(false, stdout.clone(), "", true);
+        assert_eq!(result.unwrap(), stdout);
+
+        // Nothing usable printed, so it's still a failure
+        let result = check_asusctl_output(false, " \n".to_string(), "oops", true);
+        assert!(matches!(result, Err(AsusctlError::CommandFailed(msg)) if msg == "oops"));
+
+        let result = check_asusctl_output(true, "ok".to_string(), "", false);
+        assert_eq!(result.unwrap(), "ok");
+    }
+
+    #[test]
+    fn test_introspection_implements() {
+        let xml = r#"<node>
+  <interface name="org.freedesktop.DBus.Properties">
+  </interface>
+  <interface name="xyz.ljones.Aura">
+    <property name="Brightness" type="u" access="readwrite"/>
+  </interface>
+</node>"#;
+
+        let object = Introspection::parse(xml).unwrap();
+        assert!(object.implements("xyz.ljones.Aura"));
+        assert!(!object.implements("xyz.ljones.Slash"));
+        // Property names are not interfaces
+        assert!(!object.implements("Brightness"));
+    }
+
+    #[test]
+    fn test_is_own_change() {
+        expecting_change("TestProperty", 1, || Ok(())).unwrap();
+        expecting_change("TestProperty", 2, || Ok(())).unwrap();
+        assert!(!is_own_change("TestProperty", 1));
+        assert!(is_own_change("TestProperty", 2));
+        // Seen once, so the same value set elsewhere later is external
+        assert!(!is_own_change("TestProperty", 2));
+
+        // The signal arrives while the write is still running
+        expecting_change("EarlySignal", 3, || {
+            assert!(is_own_change("EarlySignal", 3));
+            Ok(())
+        })
+        .unwrap();
+    }
+
+    #[test]
+    fn test_external_change_after_failed_write_is_reported() {
+        let failed = expecting_change("FailedWrite", 4, || {
+            Err(AsusctlError::CommandFailed("boom".to_string()))
+        });
+        assert!(failed.is_err());
+        assert!(!is_own_change("FailedWrite", 4));
+    }
+
+    #[test]
+    fn test_own_write_without_signal_expires() {
+        // asusd stays quiet when the value was already set
+        expecting_change("SilentWrite", 5, || Ok(())).unwrap();
+        let later = Instant::now() + OWN_CHANGE_GRACE + Duration::from_millis(1);
+        assert!(!is_own_change_at("SilentWrite", 5, later));
+        assert!(!is_own_change("SilentWrite", 5));
+    }
+
+    #[test]
+    fn test_classify_dbus_error() {
+        let unsupported = [
+            "Failed to get property ShowOnBoot on interface xyz.ljones.Slash: Unknown property or interface.",
+            "Failed to get property Enabled on interface xyz.ljones.Slash: Unknown interface xyz.ljones.Slash.",
+            "org.freedesktop.DBus.Error.UnknownProperty: Property not found",
+            "GDBus.Error:org.freedesktop.DBus.Error.UnknownMethod: Unknown method FanCurveData",
+            "Failed to get property X on interface Y: Unknown object '/xyz/ljones/aura/19b6_3_4'.",
+            "Call failed: Unknown method FanCurveData or interface xyz.ljones.FanCurves.",
+        ];
+        for message in unsupported {
+            assert!(
+                matches!(classify_dbus_error(message), AsusctlError::Unsupported(_)),
+                "{message}"
+            );
+        }
+
+        assert!(matches!(
+            classify_dbus_error(
+                "Failed to get property X: The name xyz.ljones.Asusd was not provided by any .service files"
+            ),
+            AsusctlError::ServiceNotRunning
+        ));
+        assert!(matches!(
+            classify_dbus_error("Access denied"),
+            AsusctlError::CommandFailed(_)
+        ));
+    }
 }
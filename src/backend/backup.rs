@@ -0,0 +1,154 @@
+//! Saving the current settings to a file and applying them again.
+//!
+//! The file is JSON, like the `--status` output. Every field is optional:
+//! settings that couldn't be read on export are left out, and missing ones
+//! are skipped on import, so a file from another laptop applies what it can.
+
+use std::fs;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use super::{
+    AsusctlError, AuraConfig, AuraEffect, AuraMode, KeyboardBrightness, PowerProfile, Result,
+    SlashState,
+};
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SettingsBackup {
+    pub profile: Option<PowerProfile>,
+    pub profile_on_ac: Option<PowerProfile>,
+    pub profile_on_battery: Option<PowerProfile>,
+    pub charge_limit: Option<u8>,
+    pub keyboard_brightness: Option<KeyboardBrightness>,
+    pub aura_mode: Option<AuraMode>,
+    /// RGB colour
+    pub aura_colour: Option<[u8; 3]>,
+    pub slash: Option<SlashState>,
+}
+
+impl SettingsBackup {
+    /// Read every setting the hardware reports
+    pub fn read() -> Self {
+        Self {
+            profile: super::get_profile_dbus().ok(),
+            profile_on_ac: super::get_profile_on_ac_dbus().ok(),
+            profile_on_battery: super::get_profile_on_battery_dbus().ok(),
+            charge_limit: super::get_charge_limit_dbus().ok(),
+            keyboard_brightness: super::get_keyboard_brightness().ok(),
+            aura_mode: super::get_aura_mode_dbus().ok().flatten(),
+            aura_colour: super::get_aura_colour_dbus().ok(),
+            slash: super::get_slash_state().ok(),
+        }
+    }
+
+    /// Apply the settings one by one, returning the ones that failed
+    ///
+    /// A failure doesn't stop the rest from being applied.
+    pub fn apply(&self) -> Vec<(&'static str, AsusctlError)> {
+        let mut failures = Vec::new();
+        let mut apply = |name: &'static str, result: Result<()>| {
+            if let Err(e) = result {
+                failures.push((name, e));
+            }
+        };
+
+        if let Some(profile) = self.profile {
+            apply("power profile", super::set_profile(profile));
+        }
+        if let Some(profile) = self.profile_on_ac {
+            apply("AC profile", super::set_profile_on_ac(profile));
+        }
+        if let Some(profile) = self.profile_on_battery {
+            apply("battery profile", super::set_profile_on_battery(profile));
+        }
+        if let Some(limit) = self.charge_limit {
+            apply("charge limit", super::set_charge_limit(limit));
+        }
+        if let Some(level) = self.keyboard_brightness {
+            apply("keyboard brightness", super::set_keyboard_brightness(level));
+        }
+        if self.aura_mode.is_some() || self.aura_colour.is_some() {
+            let defaults = AuraConfig::default();
+            apply(
+                "lighting mode",
+                super::set_aura_mode(
+                    self.aura_mode.unwrap_or(defaults.mode),
+                    self.aura_colour.unwrap_or(defaults.colour),
+                    AuraEffect::default(),
+                ),
+            );
+        }
+        if let Some(slash) = &self.slash {
+            apply("Slash", apply_slash(slash));
+        }
+
+        failures
+    }
+}
+
+fn apply_slash(slash: &SlashState) -> Result<()> {
+    if !slash.enabled {
+        return super::disable_slash();
+    }
+
+    super::enable_slash()?;
+    super::set_slash_brightness(slash.brightness)?;
+    super::set_slash_mode(slash.mode)?;
+    super::set_slash_interval(slash.interval)
+}
+
+/// Write the current settings to `path`
+pub fn export_settings(path: &Path) -> Result<()> {
+    let json = serde_json::to_string_pretty(&SettingsBackup::read())
+        .map_err(|e| AsusctlError::ParseError(format!("Failed to serialize settings: {e}")))?;
+    fs::write(path, json).map_err(|e| {
+        AsusctlError::CommandFailed(format!("Failed to write {}: {e}", path.display()))
+    })
+}
+
+/// Read settings saved by `export_settings`
+pub fn read_settings_file(path: &Path) -> Result<SettingsBackup> {
+    let json = fs::read_to_string(path).map_err(|e| {
+        AsusctlError::CommandFailed(format!("Failed to read {}: {e}", path.display()))
+    })?;
+    serde_json::from_str(&json)
+        .map_err(|e| AsusctlError::ParseError(format!("Not a settings file: {e}")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backend::SlashMode;
+
+    #[test]
+    fn test_settings_backup_round_trip() {
+        let backup = SettingsBackup {
+            profile: Some(PowerProfile::Performance),
+            charge_limit: Some(80),
+            aura_mode: Some(AuraMode::Breathe),
+            aura_colour: Some([255, 0, 128]),
+            slash: Some(SlashState {
+                enabled: true,
+                brightness: 200,
+                interval: 2,
+                mode: SlashMode::Ramp,
+            }),
+            ..Default::default()
+        };
+
+        let json = serde_json::to_string(&backup).unwrap();
+        assert_eq!(
+            serde_json::from_str::<SettingsBackup>(&json).unwrap(),
+            backup
+        );
+
+        // Files from other versions may lack fields
+        let partial: SettingsBackup = serde_json::from_str(r#"{"charge_limit": 60}"#).unwrap();
+        assert_eq!(partial.charge_limit, Some(60));
+        assert_eq!(partial.profile, None);
+
+        assert!(serde_json::from_str::<SettingsBackup>(r#"{"profile": "Turbo"}"#).is_err());
+    }
+}
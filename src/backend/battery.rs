@@ -0,0 +1,370 @@
+//! Battery time estimates from UPower and per-battery details from sysfs.
+//!
+//! UPower's display device aggregates all batteries into one, which is what
+//! desktop shells show in their battery indicator. On machines without a
+//! battery it still exists but reports `IsPresent` as false.
+//!
+//! Charge level, cycle count and wear are read per battery from
+//! `/sys/class/power_supply`, where each battery is its own directory.
+//! asusd manages the charge limit of a single battery; on machines with
+//! more, the others' limits are written to sysfs directly.
+
+use std::fs;
+use std::path::Path;
+use std::time::Duration;
+
+use super::asusctl::{FromDbus, check_charge_limit, read_dbus_property, set_charge_limit};
+use super::service::run_privileged;
+use super::{AsusctlError, Result, mock};
+
+const UPOWER_DEST: &str = "org.freedesktop.UPower";
+const DISPLAY_DEVICE_PATH: &str = "/org/freedesktop/UPower/devices/DisplayDevice";
+const DEVICE_INTERFACE: &str = "org.freedesktop.UPower.Device";
+
+// UPower device states
+const STATE_CHARGING: u32 = 1;
+const STATE_DISCHARGING: u32 = 2;
+const STATE_FULLY_CHARGED: u32 = 4;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BatteryEstimate {
+    /// Time until the battery is full
+    UntilFull(Duration),
+    /// Time until the battery is empty
+    UntilEmpty(Duration),
+    FullyCharged,
+    /// UPower has no estimate yet, or the battery is idle
+    Unknown,
+}
+
+impl std::fmt::Display for BatteryEstimate {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::UntilFull(time) => write!(f, "{} until full", format_duration(*time)),
+            Self::UntilEmpty(time) => write!(f, "{} remaining", format_duration(*time)),
+            Self::FullyCharged => write!(f, "Fully charged"),
+            Self::Unknown => write!(f, "Calculating…"),
+        }
+    }
+}
+
+fn read_device_property<T: FromDbus>(property: &str) -> Result<T> {
+    read_dbus_property(UPOWER_DEST, DISPLAY_DEVICE_PATH, DEVICE_INTERFACE, property)
+}
+
+/// Get the estimated time to full or empty
+///
+/// Returns `Unsupported` when the machine has no battery.
+pub fn get_battery_estimate() -> Result<BatteryEstimate> {
+    if !read_device_property::<bool>("IsPresent")? {
+        return Err(AsusctlError::Unsupported("no battery present".to_string()));
+    }
+
+    let state = read_device_property("State")?;
+    let seconds = match state {
+        STATE_CHARGING => read_device_property("TimeToFull")?,
+        STATE_DISCHARGING => read_device_property("TimeToEmpty")?,
+        _ => 0,
+    };
+
+    Ok(estimate_from_state(state, seconds))
+}
+
+/// UPower reports 0 seconds while it is still sampling the discharge rate
+fn estimate_from_state(state: u32, seconds: i64) -> BatteryEstimate {
+    let time = Duration::from_secs(seconds.max(0) as u64);
+
+    match state {
+        STATE_FULLY_CHARGED => BatteryEstimate::FullyCharged,
+        _ if time.is_zero() => BatteryEstimate::Unknown,
+        STATE_CHARGING => BatteryEstimate::UntilFull(time),
+        STATE_DISCHARGING => BatteryEstimate::UntilEmpty(time),
+        _ => BatteryEstimate::Unknown,
+    }
+}
+
+const POWER_SUPPLY_PATH: &str = "/sys/class/power_supply";
+const CHARGE_LIMIT_FILE: &str = "charge_control_end_threshold";
+
+/// One system battery as reported by the kernel
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BatteryInfo {
+    /// Kernel name, e.g. "BAT0"
+    pub name: String,
+    /// Charge level in percent
+    pub capacity: Option<u8>,
+    /// Kernel status text, e.g. "Charging" or "Not charging"
+    pub status: String,
+    pub cycle_count: Option<u32>,
+    /// Full charge capacity as a percentage of the design capacity
+    pub health: Option<u8>,
+    /// Charge limit in percent, when the battery has one
+    pub charge_limit: Option<u8>,
+}
+
+/// Get every system battery, or an empty list when there is none
+pub fn get_batteries() -> Result<Vec<BatteryInfo>> {
+    scan_batteries(Path::new(POWER_SUPPLY_PATH))
+}
+
+fn scan_batteries(supplies_dir: &Path) -> Result<Vec<BatteryInfo>> {
+    let entries = fs::read_dir(supplies_dir)
+        .map_err(|e| AsusctlError::CommandFailed(format!("Failed to read power supplies: {e}")))?;
+
+    let mut batteries = Vec::new();
+
+    for entry in entries.flatten() {
+        let dir = entry.path();
+        let read = |file: &str| {
+            fs::read_to_string(dir.join(file))
+                .ok()
+                .map(|s| s.trim().to_string())
+        };
+        let read_number = |file: &str| read(file).and_then(|s| s.parse::<u64>().ok());
+
+        // Mouse and headset batteries have the "Device" scope
+        if read("type").as_deref() != Some("Battery") || read("scope").as_deref() == Some("Device")
+        {
+            continue;
+        }
+
+        // Drivers report either energy (µWh) or charge (µAh) attributes
+        let full = read_number("energy_full").or_else(|| read_number("charge_full"));
+        let design =
+            read_number("energy_full_design").or_else(|| read_number("charge_full_design"));
+
+        batteries.push(BatteryInfo {
+            name: entry.file_name().to_string_lossy().to_string(),
+            capacity: read_number("capacity").map(|c| c.min(100) as u8),
+            status: read("status").unwrap_or_else(|| "Unknown".to_string()),
+            // Some firmware reports 0 when it doesn't count cycles
+            cycle_count: read_number("cycle_count")
+                .filter(|&count| count > 0)
+                .map(|count| count as u32),
+            health: full
+                .zip(design)
+                .and_then(|(full, design)| health_percent(full, design)),
+            charge_limit: read_number(CHARGE_LIMIT_FILE).map(|limit| limit.min(100) as u8),
+        });
+    }
+
+    batteries.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(batteries)
+}
+
+/// The battery whose charge limit asusd manages
+///
+/// asusd takes the first battery it finds with a charge limit, which in the
+/// sorted list is the lowest-named one, e.g. BAT0 before BAT1.
+fn asusd_battery(batteries: &[BatteryInfo]) -> Option<&BatteryInfo> {
+    batteries
+        .iter()
+        .find(|battery| battery.charge_limit.is_some())
+}
+
+/// Set the charge limit of one battery, e.g. "BAT1"
+///
+/// The battery asusd manages is set through asusd, which would otherwise
+/// restore its own saved limit over a direct write, e.g. when it starts.
+/// The others are written to sysfs. That needs root, so without it the value
+/// goes through `pkexec tee`, which asks for authorization via polkit.
+pub fn set_battery_charge_limit(name: &str, limit: u8) -> Result<()> {
+    check_charge_limit(limit)?;
+    // The name becomes part of a path written as root
+    if name.is_empty() || name.contains(['/', '.']) {
+        return Err(AsusctlError::ParseError(format!(
+            "Invalid battery name: {name}"
+        )));
+    }
+    if asusd_battery(&get_batteries()?).is_some_and(|battery| battery.name == name) {
+        return set_charge_limit(limit);
+    }
+    if mock::enabled() {
+        log::debug!("Mock charge limit of {name} set to {limit}%");
+        return Ok(());
+    }
+
+    let path = Path::new(POWER_SUPPLY_PATH)
+        .join(name)
+        .join(CHARGE_LIMIT_FILE);
+    match fs::write(&path, limit.to_string()) {
+        Ok(()) => Ok(()),
+        Err(e) if e.kind() == std::io::ErrorKind::PermissionDenied => {
+            run_privileged(&["tee", &path.to_string_lossy()], Some(&limit.to_string()))
+        }
+        Err(e) => Err(AsusctlError::CommandFailed(format!(
+            "Failed to write {}: {e}",
+            path.display()
+        ))),
+    }
+}
+
+/// Full charge capacity relative to design, capped at 100%
+fn health_percent(full: u64, design: u64) -> Option<u8> {
+    (design > 0).then(|| (full.saturating_mul(100) / design).min(100) as u8)
+}
+
+/// Format a duration as "2h 15m", or "15m" below an hour
+pub fn format_duration(duration: Duration) -> String {
+    let minutes = duration.as_secs() / 60;
+    let (hours, minutes) = (minutes / 60, minutes % 60);
+
+    if hours > 0 {
+        format!("{hours}h {minutes}m")
+    } else {
+        format!("{minutes}m")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_duration() {
+        assert_eq!(format_duration(Duration::from_secs(8100)), "2h 15m");
+        assert_eq!(format_duration(Duration::from_secs(3600)), "1h 0m");
+        assert_eq!(format_duration(Duration::from_secs(59 * 60 + 59)), "59m");
+        assert_eq!(format_duration(Duration::from_secs(30)), "0m");
+    }
+
+    fn add_supply(root: &Path, name: &str, attributes: &[(&str, &str)]) {
+        let dir = root.join(name);
+        fs::create_dir_all(&dir).unwrap();
+        for (file, value) in attributes {
+            fs::write(dir.join(file), format!("{value}\n")).unwrap();
+        }
+    }
+
+    #[test]
+    fn test_scan_batteries() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path().to_path_buf();
+
+        add_supply(
+            &root,
+            "BAT1",
+            &[
+                ("type", "Battery"),
+                ("capacity", "45"),
+                ("status", "Not charging"),
+                ("charge_full", "3000000"),
+                ("charge_full_design", "4000000"),
+                ("cycle_count", "0"),
+                ("charge_control_end_threshold", "60"),
+            ],
+        );
+        add_supply(
+            &root,
+            "BAT0",
+            &[
+                ("type", "Battery"),
+                ("capacity", "80"),
+                ("status", "Charging"),
+                ("energy_full", "68000000"),
+                ("energy_full_design", "76000000"),
+                ("cycle_count", "123"),
+            ],
+        );
+        add_supply(&root, "ACAD", &[("type", "Mains")]);
+        add_supply(
+            &root,
+            "hidpp_battery_0",
+            &[("type", "Battery"), ("scope", "Device"), ("capacity", "60")],
+        );
+
+        let batteries = scan_batteries(&root).unwrap();
+        assert_eq!(
+            batteries,
+            [
+                BatteryInfo {
+                    name: "BAT0".to_string(),
+                    capacity: Some(80),
+                    status: "Charging".to_string(),
+                    cycle_count: Some(123),
+                    health: Some(89),
+                    charge_limit: None,
+                },
+                BatteryInfo {
+                    name: "BAT1".to_string(),
+                    capacity: Some(45),
+                    status: "Not charging".to_string(),
+                    cycle_count: None,
+                    health: Some(75),
+                    charge_limit: Some(60),
+                },
+            ]
+        );
+
+        dir.close().unwrap();
+        assert!(scan_batteries(&root).is_err());
+    }
+
+    #[test]
+    fn test_asusd_battery() {
+        let battery = |name: &str, charge_limit| BatteryInfo {
+            name: name.to_string(),
+            capacity: None,
+            status: "Unknown".to_string(),
+            cycle_count: None,
+            health: None,
+            charge_limit,
+        };
+
+        let batteries = [
+            battery("BAT0", None),
+            battery("BAT1", Some(60)),
+            battery("BAT2", Some(80)),
+        ];
+        assert_eq!(
+            asusd_battery(&batteries).map(|battery| battery.name.as_str()),
+            Some("BAT1")
+        );
+        assert_eq!(asusd_battery(&batteries[..1]), None);
+    }
+
+    #[test]
+    fn test_set_battery_charge_limit_rejects_bad_input() {
+        assert!(matches!(
+            set_battery_charge_limit("BAT0", 10),
+            Err(AsusctlError::ParseError(_))
+        ));
+        for name in ["", "../BAT0", "BAT0/uevent", ".."] {
+            assert!(matches!(
+                set_battery_charge_limit(name, 80),
+                Err(AsusctlError::ParseError(_))
+            ));
+        }
+    }
+
+    #[test]
+    fn test_health_percent() {
+        assert_eq!(health_percent(50, 100), Some(50));
+        // New batteries can exceed their design capacity
+        assert_eq!(health_percent(105, 100), Some(100));
+        assert_eq!(health_percent(50, 0), None);
+    }
+
+    #[test]
+    fn test_estimate_from_state() {
+        assert_eq!(
+            estimate_from_state(STATE_DISCHARGING, 5400),
+            BatteryEstimate::UntilEmpty(Duration::from_secs(5400))
+        );
+        assert_eq!(
+            estimate_from_state(STATE_CHARGING, 600),
+            BatteryEstimate::UntilFull(Duration::from_secs(600))
+        );
+        // Still sampling
+        assert_eq!(
+            estimate_from_state(STATE_DISCHARGING, 0),
+            BatteryEstimate::Unknown
+        );
+        assert_eq!(
+            estimate_from_state(STATE_FULLY_CHARGED, 0),
+            BatteryEstimate::FullyCharged
+        );
+        // Pending charge
+        assert_eq!(estimate_from_state(5, 0), BatteryEstimate::Unknown);
+    }
+}
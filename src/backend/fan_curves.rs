@@ -0,0 +1,188 @@
+//! Custom fan curves through asusd's FanCurves interface.
+//!
+//! Each platform profile has its own set of curves, one per fan the laptop
+//! exposes (CPU, GPU and on some models a mid fan). A curve has eight points
+//! of temperature and fan speed; asusd stores the speed as a PWM value
+//! (0-255), which is converted to a percentage here.
+
+use zbus::zvariant::Structure;
+
+use super::asusctl::{bus_names, call_asusd, query_asusd};
+use super::{AsusctlError, PowerProfile, Result};
+
+/// Number of points in every fan curve
+pub const FAN_CURVE_POINTS: usize = 8;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Fan {
+    Cpu,
+    Gpu,
+    Mid,
+}
+
+impl Fan {
+    fn from_u32(value: u32) -> Result<Self> {
+        match value {
+            0 => Ok(Self::Cpu),
+            1 => Ok(Self::Gpu),
+            2 => Ok(Self::Mid),
+            _ => Err(AsusctlError::ParseError(format!("Unknown fan: {value}"))),
+        }
+    }
+
+    fn as_u32(&self) -> u32 {
+        match self {
+            Self::Cpu => 0,
+            Self::Gpu => 1,
+            Self::Mid => 2,
+        }
+    }
+}
+
+impl std::fmt::Display for Fan {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Cpu => write!(f, "CPU Fan"),
+            Self::Gpu => write!(f, "GPU Fan"),
+            Self::Mid => write!(f, "Mid Fan"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct FanCurvePoint {
+    /// Temperature in °C
+    pub temp: u8,
+    /// Fan speed in percent
+    pub percent: u8,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FanCurve {
+    pub fan: Fan,
+    /// Whether asusd applies this curve instead of the firmware default
+    pub enabled: bool,
+    pub points: [FanCurvePoint; FAN_CURVE_POINTS],
+}
+
+fn pwm_to_percent(pwm: u8) -> u8 {
+    ((pwm as u32 * 100 + 127) / 255) as u8
+}
+
+fn percent_to_pwm(percent: u8) -> u8 {
+    ((percent.min(100) as u32 * 255 + 50) / 100) as u8
+}
+
+/// One curve as sent over D-Bus: fan, PWM values, temperatures, enabled
+type CurveData = (u32, Vec<u8>, Vec<u8>, bool);
+
+/// Get the curves of every fan for `profile`
+///
+/// Returns `Unsupported` when the laptop has no custom fan curve support.
+pub fn get_fan_curves(profile: PowerProfile) -> Result<Vec<FanCurve>> {
+    let names = bus_names();
+    let curves: Vec<CurveData> = query_asusd(
+        names.platform_path,
+        names.fan_curves_interface,
+        "FanCurveData",
+        &Structure::from((profile.platform_profile_value(),)),
+    )?;
+    curves.into_iter().map(curve_from_data).collect()
+}
+
+/// Replace one fan's curve for `profile`
+pub fn set_fan_curve(profile: PowerProfile, curve: &FanCurve) -> Result<()> {
+    let names = bus_names();
+    call_asusd(
+        names.platform_path,
+        names.fan_curves_interface,
+        "SetFanCurve",
+        &Structure::from((profile.platform_profile_value(), curve_to_data(curve))),
+    )
+}
+
+/// Restore the firmware's curves for `profile`
+pub fn reset_fan_curves(profile: PowerProfile) -> Result<()> {
+    let names = bus_names();
+    call_asusd(
+        names.platform_path,
+        names.fan_curves_interface,
+        "SetCurvesToDefaults",
+        &Structure::from((profile.platform_profile_value(),)),
+    )
+}
+
+fn curve_from_data((fan, pwm, temp, enabled): CurveData) -> Result<FanCurve> {
+    if pwm.len() != FAN_CURVE_POINTS || temp.len() != FAN_CURVE_POINTS {
+        return Err(AsusctlError::ParseError(format!(
+            "Fan curve has {} speeds and {} temperatures, expected {FAN_CURVE_POINTS}",
+            pwm.len(),
+            temp.len()
+        )));
+    }
+
+    let mut points = [FanCurvePoint::default(); FAN_CURVE_POINTS];
+    for (point, (&pwm, &temp)) in points.iter_mut().zip(pwm.iter().zip(&temp)) {
+        *point = FanCurvePoint {
+            temp,
+            percent: pwm_to_percent(pwm),
+        };
+    }
+
+    Ok(FanCurve {
+        fan: Fan::from_u32(fan)?,
+        enabled,
+        points,
+    })
+}
+
+fn curve_to_data(curve: &FanCurve) -> CurveData {
+    (
+        curve.fan.as_u32(),
+        curve
+            .points
+            .iter()
+            .map(|p| percent_to_pwm(p.percent))
+            .collect(),
+        curve.points.iter().map(|p| p.temp).collect(),
+        curve.enabled,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_curve_from_data() {
+        let data = (
+            0,
+            vec![3, 5, 15, 30, 51, 77, 102, 255],
+            vec![30, 40, 50, 60, 70, 80, 90, 100],
+            true,
+        );
+        let curve = curve_from_data(data.clone()).unwrap();
+
+        assert_eq!(curve.fan, Fan::Cpu);
+        assert!(curve.enabled);
+        assert_eq!(
+            curve.points[0],
+            FanCurvePoint {
+                temp: 30,
+                percent: 1
+            }
+        );
+        assert_eq!(curve.points[7].percent, 100);
+        assert_eq!(curve_to_data(&curve).2, data.2);
+
+        assert!(curve_from_data((1, vec![1, 2, 3, 4], vec![1, 2, 3, 4], false)).is_err());
+        assert!(curve_from_data((7, vec![0; 8], vec![0; 8], false)).is_err());
+    }
+
+    #[test]
+    fn test_percent_pwm_round_trip() {
+        for percent in 0..=100 {
+            assert_eq!(pwm_to_percent(percent_to_pwm(percent)), percent);
+        }
+    }
+}
@@ -0,0 +1,186 @@
+//! Graphics hardware detection from sysfs.
+//!
+//! GPUs are found by scanning `/sys/bus/pci/devices` for display class
+//! devices (PCI class 0x03xxxx), so no `lspci` binary is needed. Model names
+//! come from the pci.ids database when it is installed; otherwise only the
+//! vendor is named.
+
+use std::fs;
+use std::path::Path;
+
+use super::{AsusctlError, Result};
+
+const PCI_DEVICES_PATH: &str = "/sys/bus/pci/devices";
+const PCI_IDS_PATHS: &[&str] = &["/usr/share/hwdata/pci.ids", "/usr/share/misc/pci.ids"];
+
+/// PCI base class for display controllers
+const PCI_CLASS_DISPLAY: u32 = 0x03;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GpuInfo {
+    /// PCI slot, e.g. "0000:01:00.0"
+    pub slot: String,
+    /// Human readable name, "Unknown" for unrecognized vendors
+    pub name: String,
+}
+
+/// Get all display controllers on the PCI bus
+pub fn get_gpus() -> Result<Vec<GpuInfo>> {
+    let pci_ids = PCI_IDS_PATHS
+        .iter()
+        .find_map(|path| fs::read_to_string(path).ok());
+
+    scan_gpus(Path::new(PCI_DEVICES_PATH), pci_ids.as_deref())
+}
+
+fn scan_gpus(devices_dir: &Path, pci_ids: Option<&str>) -> Result<Vec<GpuInfo>> {
+    let entries = fs::read_dir(devices_dir)
+        .map_err(|e| AsusctlError::CommandFailed(format!("Failed to read PCI devices: {e}")))?;
+
+    let mut gpus = Vec::new();
+
+    for entry in entries.flatten() {
+        let dir = entry.path();
+        let read_id = |file: &str| {
+            fs::read_to_string(dir.join(file))
+                .ok()
+                .and_then(|s| parse_hex_id(&s))
+        };
+
+        let Some(class) = read_id("class") else {
+            continue;
+        };
+        if class >> 16 != PCI_CLASS_DISPLAY {
+            continue;
+        }
+
+        let (Some(vendor), Some(device)) = (read_id("vendor"), read_id("device")) else {
+            continue;
+        };
+
+        gpus.push(GpuInfo {
+            slot: entry.file_name().to_string_lossy().to_string(),
+            name: gpu_name(vendor as u16, device as u16, pci_ids),
+        });
+    }
+
+    gpus.sort_by(|a, b| a.slot.cmp(&b.slot));
+    Ok(gpus)
+}
+
+fn gpu_name(vendor: u16, device: u16, pci_ids: Option<&str>) -> String {
+    let Some(vendor_name) = vendor_name(vendor) else {
+        return "Unknown".to_string();
+    };
+
+    match pci_ids.and_then(|ids| lookup_device_name(ids, vendor, device)) {
+        Some(model) => format!("{vendor_name} {model}"),
+        None => format!("{vendor_name} (device {device:04x})"),
+    }
+}
+
+fn vendor_name(vendor: u16) -> Option<&'static str> {
+    match vendor {
+        0x8086 => Some("Intel"),
+        0x1002 | 0x1022 => Some("AMD"),
+        0x10de => Some("NVIDIA"),
+        _ => None,
+    }
+}
+
+/// Look up a device in pci.ids; prefers the marketing name in brackets
+///
+/// Vendors start at column 0 ("10de  NVIDIA Corporation"), their devices
+/// follow indented by one tab ("\t28a0  AD107M [GeForce RTX 4060 Max-Q / Mobile]").
+fn lookup_device_name(pci_ids: &str, vendor: u16, device: u16) -> Option<String> {
+    let vendor_prefix = format!("{vendor:04x}  ");
+    let device_prefix = format!("\t{device:04x}  ");
+    let mut in_vendor = false;
+
+    for line in pci_ids.lines() {
+        if line.starts_with('#') || line.is_empty() {
+            continue;
+        }
+
+        if !line.starts_with('\t') {
+            if in_vendor {
+                return None;
+            }
+            in_vendor = line.starts_with(&vendor_prefix);
+        } else if in_vendor && let Some(name) = line.strip_prefix(&device_prefix) {
+            let name = name.trim();
+            let marketing = name
+                .find('[')
+                .and_then(|start| name.rfind(']').map(|end| &name[start + 1..end]));
+            return Some(marketing.unwrap_or(name).to_string());
+        }
+    }
+
+    None
+}
+
+/// Parse a sysfs id like "0x10de\n"
+fn parse_hex_id(value: &str) -> Option<u32> {
+    u32::from_str_radix(value.trim().trim_start_matches("0x"), 16).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PCI_IDS: &str = "# comment
+10de  NVIDIA Corporation
+\t28a0  AD107M [GeForce RTX 4060 Max-Q / Mobile]
+\t28e0  AD107M
+1002  Advanced Micro Devices, Inc. [AMD/ATI]
+\t15bf  Phoenix1
+";
+
+    fn add_device(root: &Path, slot: &str, class: &str, vendor: &str, device: &str) {
+        let dir = root.join(slot);
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("class"), class).unwrap();
+        fs::write(dir.join("vendor"), vendor).unwrap();
+        fs::write(dir.join("device"), device).unwrap();
+    }
+
+    #[test]
+    fn test_lookup_device_name() {
+        assert_eq!(
+            lookup_device_name(PCI_IDS, 0x10de, 0x28a0).as_deref(),
+            Some("GeForce RTX 4060 Max-Q / Mobile")
+        );
+        assert_eq!(
+            lookup_device_name(PCI_IDS, 0x10de, 0x28e0).as_deref(),
+            Some("AD107M")
+        );
+        // Device ids are scoped to their vendor
+        assert_eq!(lookup_device_name(PCI_IDS, 0x1002, 0x28a0), None);
+    }
+
+    #[test]
+    fn test_scan_gpus() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+
+        add_device(root, "0000:01:00.0", "0x030200\n", "0x10de\n", "0x28a0\n");
+        add_device(root, "0000:65:00.0", "0x030000\n", "0x1002\n", "0x15bf\n");
+        add_device(root, "0000:66:00.0", "0x030000\n", "0x1234\n", "0x1111\n");
+        // Not a display controller
+        add_device(root, "0000:00:14.0", "0x0c0330\n", "0x8086\n", "0x7e7d\n");
+
+        let gpus = scan_gpus(root, Some(PCI_IDS)).unwrap();
+        let names: Vec<&str> = gpus.iter().map(|g| g.name.as_str()).collect();
+        assert_eq!(
+            names,
+            [
+                "NVIDIA GeForce RTX 4060 Max-Q / Mobile",
+                "AMD Phoenix1",
+                "Unknown"
+            ]
+        );
+
+        let gpus = scan_gpus(root, None).unwrap();
+        assert_eq!(gpus[0].name, "NVIDIA (device 28a0)");
+    }
+}
@@ -0,0 +1,123 @@
+//! Optional coupling of the keyboard lighting and the slash bar.
+//!
+//! When linked, switching the keyboard off also disables the slash bar and
+//! switching it back on enables the bar again, and vice versa. The keyboard
+//! brightness in use when the slash bar turned it off is remembered, so
+//! enabling the bar brings the keyboard back to where it was.
+
+use std::sync::{Mutex, PoisonError};
+
+use super::{
+    AsusctlError, KeyboardBrightness, Result, disable_slash, enable_slash,
+    get_keyboard_brightness_dbus, get_slash_enabled, set_keyboard_brightness,
+};
+
+/// Keyboard brightness saved when the slash bar switched the keyboard off
+static SAVED_KEYBOARD_BRIGHTNESS: Mutex<Option<KeyboardBrightness>> = Mutex::new(None);
+
+/// Make the slash bar follow the keyboard, which was just set to `level`
+pub fn sync_slash_to_keyboard(level: KeyboardBrightness) -> Result<()> {
+    let enabled = ignore_unsupported(get_slash_enabled())?;
+
+    match enabled.and_then(|enabled| slash_for_keyboard(level, enabled)) {
+        Some(true) => enable_slash(),
+        Some(false) => disable_slash(),
+        None => Ok(()),
+    }
+}
+
+/// Make the keyboard follow the slash bar, which was just switched on or off
+pub fn sync_keyboard_to_slash(enabled: bool) -> Result<()> {
+    let Some(current) = ignore_unsupported(get_keyboard_brightness_dbus())? else {
+        return Ok(());
+    };
+
+    let mut saved = SAVED_KEYBOARD_BRIGHTNESS
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner);
+
+    match keyboard_for_slash(enabled, current, &mut saved) {
+        Some(level) => set_keyboard_brightness(level),
+        None => Ok(()),
+    }
+}
+
+/// A laptop without one of the two devices has nothing to keep in sync
+fn ignore_unsupported<T>(result: Result<T>) -> Result<Option<T>> {
+    match result {
+        Ok(value) => Ok(Some(value)),
+        Err(AsusctlError::Unsupported(_)) => Ok(None),
+        Err(e) => Err(e),
+    }
+}
+
+/// Slash state to apply after the keyboard changed, if it needs to change
+fn slash_for_keyboard(level: KeyboardBrightness, slash_enabled: bool) -> Option<bool> {
+    let keyboard_on = level != KeyboardBrightness::Off;
+    (keyboard_on != slash_enabled).then_some(keyboard_on)
+}
+
+/// Keyboard brightness to apply after the slash bar changed, if it needs to change
+fn keyboard_for_slash(
+    slash_enabled: bool,
+    current: KeyboardBrightness,
+    saved: &mut Option<KeyboardBrightness>,
+) -> Option<KeyboardBrightness> {
+    let keyboard_on = current != KeyboardBrightness::Off;
+
+    match (slash_enabled, keyboard_on) {
+        (false, true) => {
+            *saved = Some(current);
+            Some(KeyboardBrightness::Off)
+        }
+        (true, false) => Some(saved.take().unwrap_or(KeyboardBrightness::Med)),
+        // Already in step; a stale saved level must not resurface later
+        _ => {
+            *saved = None;
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_slash_follows_keyboard() {
+        assert_eq!(
+            slash_for_keyboard(KeyboardBrightness::Off, true),
+            Some(false)
+        );
+        assert_eq!(
+            slash_for_keyboard(KeyboardBrightness::Low, false),
+            Some(true)
+        );
+        assert_eq!(slash_for_keyboard(KeyboardBrightness::High, true), None);
+        assert_eq!(slash_for_keyboard(KeyboardBrightness::Off, false), None);
+    }
+
+    #[test]
+    fn test_keyboard_follows_slash_and_restores_level() {
+        let mut saved = None;
+
+        assert_eq!(
+            keyboard_for_slash(false, KeyboardBrightness::High, &mut saved),
+            Some(KeyboardBrightness::Off)
+        );
+        assert_eq!(
+            keyboard_for_slash(true, KeyboardBrightness::Off, &mut saved),
+            Some(KeyboardBrightness::High)
+        );
+
+        // Nothing saved: fall back to a visible level
+        assert_eq!(
+            keyboard_for_slash(true, KeyboardBrightness::Off, &mut saved),
+            Some(KeyboardBrightness::Med)
+        );
+        assert_eq!(
+            keyboard_for_slash(true, KeyboardBrightness::Low, &mut saved),
+            None
+        );
+    }
+}
@@ -0,0 +1,455 @@
+//! Canned responses for running without ASUS hardware.
+//!
+//! With `ASUSCTL_GUI_MOCK=1` set, asusctl runs and system bus calls are
+//! answered here instead of spawning processes or talking to asusd. The
+//! fixture machine has a Platform, Aura and Slash device, a battery and
+//! supergfxd. Writes update an in-memory copy of the properties, so a page
+//! shows what was last set, as it would on a real laptop.
+
+use std::collections::HashMap;
+use std::str::FromStr;
+use std::sync::{LazyLock, Mutex, OnceLock, PoisonError};
+
+use zbus::zvariant::{OwnedValue, Structure, Value};
+
+use super::{AsusctlError, AuraMode, KeyboardBrightness, PowerProfile, Result, SlashMode};
+
+const ENV_VAR: &str = "ASUSCTL_GUI_MOCK";
+
+const PLATFORM: &str = "xyz.ljones.Platform";
+const FAN_CURVES: &str = "xyz.ljones.FanCurves";
+const AURA: &str = "xyz.ljones.Aura";
+const SLASH: &str = "xyz.ljones.Slash";
+const SUPERGFX: &str = "org.supergfxctl.Daemon";
+
+/// Services the fixture machine runs
+const SERVICES: &[&str] = &[
+    "xyz.ljones.Asusd",
+    SUPERGFX,
+    "org.freedesktop.UPower",
+    "org.freedesktop.systemd1",
+];
+
+/// asusd's objects and the interfaces they implement
+const OBJECTS: &[(&str, &[&str])] = &[
+    ("/xyz/ljones", &[PLATFORM, FAN_CURVES]),
+    ("/xyz/ljones/aura/19b6_4", &[AURA]),
+    ("/xyz/ljones/slash", &[SLASH]),
+];
+
+const VERSION: &str = "asusctl version: 6.1.12
+Product family: ROG Zephyrus G16 (mock)
+Board name: GU605MV
+";
+
+const SUPPORTED: &str = "Supported Core Functions:
+[
+    xyz.ljones.Aura,
+    xyz.ljones.FanCurves,
+    xyz.ljones.Platform,
+    xyz.ljones.Slash,
+]
+Supported Platform Properties:
+[
+    xyz.ljones.Platform: ChargeControlEndThreshold,
+    xyz.ljones.Platform: ThrottlePolicy,
+]
+Supported Keyboard Brightness:
+[
+    Off,
+    Low,
+    Med,
+    High,
+]
+Supported Aura Modes:
+[
+    Static,
+    Breathe,
+    Pulse,
+    RainbowWave,
+]
+Supported Aura Zones:
+[]
+";
+
+/// One fan curve as sent over D-Bus: fan, PWM values, temperatures, enabled
+type CurveData = (u32, Vec<u8>, Vec<u8>, bool);
+
+/// `.to_value()` for fixture data, which never holds file descriptors
+trait ToValue {
+    fn to_value(self) -> OwnedValue;
+}
+
+impl<'a, T: Into<Value<'a>>> ToValue for T {
+    fn to_value(self) -> OwnedValue {
+        self.into()
+            .try_into_owned()
+            .expect("mock values hold no file descriptors")
+    }
+}
+
+/// Whether the mock was asked for, read once per run
+pub(super) fn enabled() -> bool {
+    static ENABLED: OnceLock<bool> = OnceLock::new();
+
+    *ENABLED.get_or_init(|| {
+        let enabled = std::env::var_os(ENV_VAR).is_some_and(|value| value == "1");
+        if enabled {
+            log::info!("{ENV_VAR} is set, using canned responses instead of asusctl and D-Bus");
+        }
+        enabled
+    })
+}
+
+/// Properties by interface and name
+static PROPERTIES: LazyLock<Mutex<HashMap<(String, String), OwnedValue>>> =
+    LazyLock::new(|| Mutex::new(default_properties()));
+
+/// Fan curves by platform profile value
+static FAN_CURVE_DATA: LazyLock<Mutex<HashMap<u32, Vec<CurveData>>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+
+fn default_properties() -> HashMap<(String, String), OwnedValue> {
+    let profile = PowerProfile::Balanced.platform_profile_value();
+    let quiet = PowerProfile::Quiet.platform_profile_value();
+    // LedModeData: mode, zone, colour1, colour2, speed, direction
+    let led_mode_data = (
+        0u32,
+        0u32,
+        (255u8, 255u8, 255u8),
+        (0u8, 0u8, 0u8),
+        1u32,
+        0u32,
+    );
+
+    [
+        (PLATFORM, "PlatformProfile", profile.to_value()),
+        (PLATFORM, "PlatformProfileOnAc", profile.to_value()),
+        (PLATFORM, "PlatformProfileOnBattery", quiet.to_value()),
+        (PLATFORM, "ThrottlePolicy", profile.to_value()),
+        (PLATFORM, "ThrottlePolicyOnAc", profile.to_value()),
+        (PLATFORM, "ThrottlePolicyOnBattery", quiet.to_value()),
+        (PLATFORM, "ChargeControlEndThreshold", 80u8.to_value()),
+        (PLATFORM, "PanelOverdrive", false.to_value()),
+        (PLATFORM, "GpuMuxMode", 1u8.to_value()),
+        (AURA, "Brightness", 2u32.to_value()),
+        (AURA, "LedMode", 0u32.to_value()),
+        (AURA, "LedModeData", led_mode_data.to_value()),
+        // Keyboard zone: lit at boot, awake and shutdown, off while asleep
+        (
+            AURA,
+            "LedPower",
+            (vec![(1u32, true, true, false, true)],).to_value(),
+        ),
+        (SLASH, "Enabled", true.to_value()),
+        (SLASH, "Brightness", 128u8.to_value()),
+        (SLASH, "Interval", 0u8.to_value()),
+        (SLASH, "Mode", 0x19u8.to_value()),
+        (SLASH, "ShowOnBoot", true.to_value()),
+        (SLASH, "ShowOnShutdown", true.to_value()),
+        (SLASH, "ShowOnSleep", false.to_value()),
+        (SLASH, "ShowOnBattery", true.to_value()),
+        (SLASH, "ShowBatteryWarning", true.to_value()),
+        (SUPERGFX, "Mode", 0u32.to_value()),
+        (
+            "org.freedesktop.UPower.Device",
+            "IsPresent",
+            true.to_value(),
+        ),
+        ("org.freedesktop.UPower.Device", "State", 2u32.to_value()),
+        (
+            "org.freedesktop.UPower.Device",
+            "TimeToEmpty",
+            9000i64.to_value(),
+        ),
+        (
+            "org.freedesktop.UPower.Device",
+            "TimeToFull",
+            0i64.to_value(),
+        ),
+        (
+            "org.freedesktop.systemd1.Unit",
+            "LoadState",
+            "loaded".to_value(),
+        ),
+        (
+            "org.freedesktop.systemd1.Unit",
+            "ActiveState",
+            "active".to_value(),
+        ),
+    ]
+    .into_iter()
+    .map(|(interface, name, value)| ((interface.to_string(), name.to_string()), value))
+    .collect()
+}
+
+pub(super) fn get_property(interface: &str, name: &str) -> Result<OwnedValue> {
+    PROPERTIES
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner)
+        .get(&(interface.to_string(), name.to_string()))
+        .cloned()
+        .ok_or_else(|| AsusctlError::Unsupported(format!("No such property {interface}.{name}")))
+}
+
+pub(super) fn set_property(interface: &str, name: &str, value: OwnedValue) {
+    PROPERTIES
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner)
+        .insert((interface.to_string(), name.to_string()), value);
+}
+
+fn get_profile(name: &str) -> PowerProfile {
+    get_property(PLATFORM, name)
+        .ok()
+        .and_then(|value| u32::try_from(value).ok())
+        .and_then(PowerProfile::from_platform_profile_value)
+        .unwrap_or_default()
+}
+
+/// The raw value `from_raw` maps to `wanted`, for enums only decoded elsewhere
+fn raw_value<T: PartialEq>(wanted: T, from_raw: impl Fn(u8) -> Option<T>) -> Option<u8> {
+    (0..=u8::MAX).find(|&raw| from_raw(raw).is_some_and(|value| value == wanted))
+}
+
+fn parse<T: FromStr>(value: Option<&&str>) -> Result<T> {
+    value
+        .and_then(|value| value.parse().ok())
+        .ok_or_else(|| AsusctlError::ParseError(format!("Bad mock argument: {value:?}")))
+}
+
+/// Answer an asusctl run
+pub(super) fn run_asusctl(args: &[&str]) -> Result<String> {
+    log::debug!("Mock asusctl {}", args.join(" "));
+
+    match args {
+        ["--version"] => Ok(VERSION.to_string()),
+        ["--show-supported"] => Ok(SUPPORTED.to_string()),
+        ["profile", "--profile-get"] => Ok(format!(
+            "Active profile is {}\nProfile on AC is {}\nProfile on Battery is {}\n",
+            get_profile("PlatformProfile"),
+            get_profile("PlatformProfileOnAc"),
+            get_profile("PlatformProfileOnBattery"),
+        )),
+        ["profile", "--profile-set", profile] => {
+            let profile = PowerProfile::from_str(profile)?;
+            set_property(
+                PLATFORM,
+                "PlatformProfile",
+                profile.platform_profile_value().to_value(),
+            );
+            Ok(String::new())
+        }
+        ["--kbd-bright"] => {
+            let level = u32::try_from(get_property(AURA, "Brightness")?)
+                .ok()
+                .and_then(|value| u8::try_from(value).ok())
+                .ok_or_else(|| AsusctlError::ParseError("Bad mock brightness".to_string()))?;
+            let level = KeyboardBrightness::from_u8(level)?;
+            Ok(format!("Current keyboard led brightness: {level:?}\n"))
+        }
+        ["--kbd-bright", level] => {
+            let level = KeyboardBrightness::from_str(level)?;
+            set_property(AURA, "Brightness", u32::from(level.as_u8()).to_value());
+            Ok(String::new())
+        }
+        ["--chg-limit", limit] => {
+            set_property(
+                PLATFORM,
+                "ChargeControlEndThreshold",
+                parse::<u8>(Some(limit))?.to_value(),
+            );
+            Ok(String::new())
+        }
+        // The zone, speed and direction are ignored: the fixture keyboard
+        // has one colour and asusd doesn't report the effect
+        ["aura", mode, options @ ..] => {
+            let mode = AuraMode::from_str(mode)?;
+            let led_mode = raw_value(mode, |raw| AuraMode::from_led_mode(raw.into()))
+                .map(u32::from)
+                .unwrap_or_default();
+            let colour = match options.iter().position(|option| *option == "-c") {
+                Some(index) => {
+                    let colour = options.get(index + 1).copied().unwrap_or_default();
+                    u32::from_str_radix(colour, 16).map_err(|_| {
+                        AsusctlError::ParseError(format!("Bad mock colour: {colour}"))
+                    })?
+                }
+                None => 0,
+            };
+            let [_, r, g, b] = colour.to_be_bytes();
+            let data = (led_mode, 0u32, (r, g, b), (0u8, 0u8, 0u8), 1u32, 0u32);
+            set_property(AURA, "LedMode", led_mode.to_value());
+            set_property(AURA, "LedModeData", data.to_value());
+            Ok(String::new())
+        }
+        ["slash", option, value @ ..] => {
+            let (name, value) = match *option {
+                "--enable" => ("Enabled", true.to_value()),
+                "--disable" => ("Enabled", false.to_value()),
+                "--brightness" => ("Brightness", parse::<u8>(value.first())?.to_value()),
+                "--interval" => ("Interval", parse::<u8>(value.first())?.to_value()),
+                "--mode" => {
+                    let mode = parse::<SlashMode>(value.first())?;
+                    let raw = raw_value(mode, |raw| SlashMode::from_u8(raw).ok());
+                    ("Mode", raw.unwrap_or_default().to_value())
+                }
+                "--show-on-boot" => ("ShowOnBoot", parse::<bool>(value.first())?.to_value()),
+                "--show-on-shutdown" => {
+                    ("ShowOnShutdown", parse::<bool>(value.first())?.to_value())
+                }
+                "--show-on-sleep" => ("ShowOnSleep", parse::<bool>(value.first())?.to_value()),
+                "--show-on-battery" => ("ShowOnBattery", parse::<bool>(value.first())?.to_value()),
+                "--show-battery-warning" => (
+                    "ShowBatteryWarning",
+                    parse::<bool>(value.first())?.to_value(),
+                ),
+                _ => return Err(unknown_command(args)),
+            };
+            set_property(SLASH, name, value);
+            Ok(String::new())
+        }
+        _ => Err(unknown_command(args)),
+    }
+}
+
+fn unknown_command(args: &[&str]) -> AsusctlError {
+    AsusctlError::CommandFailed(format!("Not mocked: asusctl {}", args.join(" ")))
+}
+
+/// Whether a service of the fixture machine owns `name`
+pub(super) fn name_has_owner(name: &str) -> bool {
+    SERVICES.contains(&name)
+}
+
+/// Answer a method call on the system bus with its reply value, if any
+pub(super) fn call_dbus(
+    dest: &str,
+    path: &str,
+    interface: &str,
+    method: &str,
+    args: Option<&Structure<'_>>,
+) -> Result<Option<OwnedValue>> {
+    log::debug!("Mock D-Bus {interface}.{method} on {dest} {path}");
+
+    let missing_args = || AsusctlError::ParseError(format!("Missing arguments for {method}"));
+    let arg = |index: usize| {
+        args.and_then(|args| args.fields().get(index))
+            .ok_or_else(missing_args)
+    };
+    let profile_arg = || u32::try_from(arg(0)?).map_err(|_| missing_args());
+
+    match (interface, method) {
+        (FAN_CURVES, "FanCurveData") => Ok(Some(fan_curves(profile_arg()?).to_value())),
+        (FAN_CURVES, "SetFanCurve") => {
+            let profile = profile_arg()?;
+            let curve = CurveData::try_from(arg(1)?).map_err(|_| missing_args())?;
+            let mut curves = fan_curves(profile);
+            curves.retain(|(fan, ..)| *fan != curve.0);
+            curves.push(curve);
+            curves.sort_by_key(|(fan, ..)| *fan);
+            FAN_CURVE_DATA
+                .lock()
+                .unwrap_or_else(PoisonError::into_inner)
+                .insert(profile, curves);
+            Ok(None)
+        }
+        (FAN_CURVES, "SetCurvesToDefaults") => {
+            let profile = profile_arg()?;
+            FAN_CURVE_DATA
+                .lock()
+                .unwrap_or_else(PoisonError::into_inner)
+                .remove(&profile);
+            Ok(None)
+        }
+        (SUPERGFX, "Mode") => get_property(SUPERGFX, "Mode").map(Some),
+        // Hybrid and Integrated
+        (SUPERGFX, "Supported") => Ok(Some(vec![0u32, 1].to_value())),
+        (SUPERGFX, "SetMode") => {
+            set_property(
+                SUPERGFX,
+                "Mode",
+                arg(0)?.try_clone().map_err(|_| missing_args())?.to_value(),
+            );
+            // Log out to finish switching
+            Ok(Some(0u32.to_value()))
+        }
+        _ => Err(AsusctlError::Unsupported(format!(
+            "No such method {interface}.{method} on {path}"
+        ))),
+    }
+}
+
+/// Curves of the CPU and GPU fans for a profile, the defaults until one is set
+fn fan_curves(profile: u32) -> Vec<CurveData> {
+    FAN_CURVE_DATA
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner)
+        .get(&profile)
+        .cloned()
+        .unwrap_or_else(|| {
+            let temps = vec![30, 40, 50, 60, 70, 80, 90, 100];
+            let pwm = vec![10, 20, 40, 70, 110, 150, 200, 255];
+            vec![
+                (0, pwm.clone(), temps.clone(), false),
+                (1, pwm, temps, false),
+            ]
+        })
+}
+
+/// Introspection XML listing an object's interfaces and direct children
+pub(super) fn introspect(path: &str) -> String {
+    let prefix = format!("{}/", path.trim_end_matches('/'));
+    let mut xml = String::from("<node>\n");
+
+    for (_, interfaces) in OBJECTS.iter().filter(|(object, _)| *object == path) {
+        for interface in *interfaces {
+            xml.push_str(&format!("  <interface name=\"{interface}\"/>\n"));
+        }
+    }
+
+    let mut children: Vec<&str> = OBJECTS
+        .iter()
+        .filter_map(|(object, _)| object.strip_prefix(&prefix))
+        .filter_map(|rest| rest.split('/').next())
+        .collect();
+    children.sort();
+    children.dedup();
+    for child in children {
+        xml.push_str(&format!("  <node name=\"{child}\"/>\n"));
+    }
+
+    xml.push_str("</node>\n");
+    xml
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_introspect() {
+        let root = introspect("/");
+        assert!(root.contains("<node name=\"xyz\"/>"));
+
+        let platform = introspect("/xyz/ljones");
+        assert!(platform.contains(&format!("<interface name=\"{PLATFORM}\"/>")));
+        assert!(platform.contains("<node name=\"aura\"/>"));
+        assert!(platform.contains("<node name=\"slash\"/>"));
+
+        assert!(introspect("/xyz/ljones/aura").contains("<node name=\"19b6_4\"/>"));
+    }
+
+    #[test]
+    fn test_mock_slash_round_trip() {
+        run_asusctl(&["slash", "--mode", "Ramp"]).unwrap();
+        let mode = u8::try_from(get_property(SLASH, "Mode").unwrap()).unwrap();
+        assert_eq!(SlashMode::from_u8(mode).unwrap(), SlashMode::Ramp);
+
+        assert!(run_asusctl(&["slash", "--brightness", "bright"]).is_err());
+        assert!(matches!(
+            run_asusctl(&["anime", "--enable"]),
+            Err(AsusctlError::CommandFailed(_))
+        ));
+    }
+}
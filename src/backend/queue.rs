@@ -0,0 +1,122 @@
+//! Serialized execution of backend calls.
+//!
+//! asusd applies settings one at a time, and overlapping asusctl invocations
+//! can race and leave the hardware in a state no control shows. All setters
+//! go through a single worker thread, so they run in submission order and
+//! the last submitted value is the one that sticks. Page reads use the same
+//! thread, so they see every setter submitted before them.
+
+use futures_channel::oneshot;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::{OnceLock, mpsc};
+use std::thread;
+
+type Job = Box<dyn FnOnce() + Send>;
+
+static QUEUE: OnceLock<CommandQueue> = OnceLock::new();
+
+/// A FIFO of jobs executed one after another on a worker thread
+#[derive(Debug)]
+pub struct CommandQueue {
+    sender: mpsc::Sender<Job>,
+}
+
+impl CommandQueue {
+    pub fn new() -> Self {
+        let (sender, receiver) = mpsc::channel::<Job>();
+
+        thread::Builder::new()
+            .name("asusctl-queue".to_string())
+            .spawn(move || {
+                for job in receiver {
+                    // A panicking job must not take the queue down with it;
+                    // its caller sees a cancelled receiver instead
+                    let _ = panic::catch_unwind(AssertUnwindSafe(job));
+                }
+            })
+            .expect("failed to spawn backend command thread");
+
+        Self { sender }
+    }
+
+    /// Queue `job`; the receiver resolves with its result once it has run
+    pub fn submit<T, F>(&self, job: F) -> oneshot::Receiver<T>
+    where
+        T: Send + 'static,
+        F: FnOnce() -> T + Send + 'static,
+    {
+        let (result_sender, result_receiver) = oneshot::channel();
+        let _ = self.sender.send(Box::new(move || {
+            let _ = result_sender.send(job());
+        }));
+        result_receiver
+    }
+}
+
+impl Default for CommandQueue {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Queue `job` on the shared backend command queue
+pub fn submit<T, F>(job: F) -> oneshot::Receiver<T>
+where
+    T: Send + 'static,
+    F: FnOnce() -> T + Send + 'static,
+{
+    QUEUE.get_or_init(CommandQueue::new).submit(job)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::{Arc, Mutex};
+    use std::time::Duration;
+
+    #[test]
+    fn test_jobs_run_sequentially_in_order() {
+        let queue = CommandQueue::new();
+        let log = Arc::new(Mutex::new(Vec::new()));
+
+        let receivers: Vec<_> = (0..5)
+            .map(|i| {
+                let log = log.clone();
+                queue.submit(move || {
+                    log.lock().unwrap().push(format!("start {i}"));
+                    // Earlier jobs take longer, so overlap would reorder the log
+                    thread::sleep(Duration::from_millis(10 * (5 - i)));
+                    log.lock().unwrap().push(format!("end {i}"));
+                    i
+                })
+            })
+            .collect();
+
+        let results: Vec<u64> = receivers
+            .into_iter()
+            .map(|receiver| {
+                gtk4::glib::MainContext::default()
+                    .block_on(receiver)
+                    .unwrap()
+            })
+            .collect();
+        assert_eq!(results, [0, 1, 2, 3, 4]);
+
+        let expected: Vec<String> = (0..5)
+            .flat_map(|i| [format!("start {i}"), format!("end {i}")])
+            .collect();
+        assert_eq!(*log.lock().unwrap(), expected);
+    }
+
+    #[test]
+    fn test_panicking_job_does_not_stop_the_queue() {
+        let queue = CommandQueue::new();
+        let context = gtk4::glib::MainContext::default();
+
+        let failed = queue.submit(|| -> u8 { panic!("job failed") });
+        assert!(context.block_on(failed).is_err());
+
+        let next = queue.submit(|| 7);
+        assert_eq!(context.block_on(next), Ok(7));
+    }
+}
@@ -0,0 +1,170 @@
+//! Temperatures and fan speeds from the kernel's hwmon interface.
+//!
+//! Every sensor chip is a directory below `/sys/class/hwmon` whose `name`
+//! file identifies the driver. Temperatures are in millidegrees Celsius and
+//! fan speeds in RPM. ASUS laptops report their fans through the `asus`
+//! driver, labelled e.g. `cpu_fan`. Sensors a machine lacks are simply left
+//! out of the readings.
+
+use std::fs;
+use std::path::Path;
+
+use super::{AsusctlError, Result};
+
+const HWMON_PATH: &str = "/sys/class/hwmon";
+
+/// Drivers reporting the CPU package temperature as `temp1`
+const CPU_DRIVERS: &[&str] = &["k10temp", "zenpower", "coretemp"];
+/// Drivers of GPUs with a temperature sensor
+const GPU_DRIVERS: &[&str] = &["amdgpu", "nouveau"];
+/// Driver of the ASUS laptop fans
+const FAN_DRIVER: &str = "asus";
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct FanReading {
+    /// Kernel label, e.g. "cpu_fan"
+    pub label: String,
+    pub rpm: u32,
+}
+
+impl FanReading {
+    /// Readable fan name
+    pub fn title(&self) -> String {
+        match self.label.as_str() {
+            "cpu_fan" => "CPU Fan".to_string(),
+            "gpu_fan" => "GPU Fan".to_string(),
+            "mid_fan" => "Middle Fan".to_string(),
+            label => label.replace('_', " "),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SensorReadings {
+    /// CPU package temperature in °C
+    pub cpu_temp: Option<f64>,
+    /// Hottest GPU in °C, when the driver exposes one
+    pub gpu_temp: Option<f64>,
+    pub fans: Vec<FanReading>,
+}
+
+/// Read the current temperatures and fan speeds
+pub fn get_sensor_readings() -> Result<SensorReadings> {
+    scan_hwmon(Path::new(HWMON_PATH))
+}
+
+fn scan_hwmon(hwmon_dir: &Path) -> Result<SensorReadings> {
+    let entries = fs::read_dir(hwmon_dir)
+        .map_err(|e| AsusctlError::CommandFailed(format!("Failed to read hwmon: {e}")))?;
+
+    let mut readings = SensorReadings::default();
+    let mut chips: Vec<_> = entries.flatten().map(|entry| entry.path()).collect();
+    chips.sort();
+
+    for dir in chips {
+        let read = |file: &str| {
+            fs::read_to_string(dir.join(file))
+                .ok()
+                .map(|s| s.trim().to_string())
+        };
+        let read_temp = |file: &str| {
+            read(file)
+                .and_then(|s| s.parse::<i64>().ok())
+                .map(|millidegrees| millidegrees as f64 / 1000.0)
+        };
+        let Some(name) = read("name") else {
+            continue;
+        };
+
+        if CPU_DRIVERS.contains(&name.as_str()) {
+            readings.cpu_temp = readings.cpu_temp.or_else(|| read_temp("temp1_input"));
+        } else if GPU_DRIVERS.contains(&name.as_str()) {
+            if let Some(temp) = read_temp("temp1_input") {
+                readings.gpu_temp = Some(readings.gpu_temp.map_or(temp, |t| t.max(temp)));
+            }
+        } else if name == FAN_DRIVER {
+            for index in 1.. {
+                let Some(rpm) = read(&format!("fan{index}_input")) else {
+                    break;
+                };
+                let Ok(rpm) = rpm.parse() else {
+                    continue;
+                };
+                let label =
+                    read(&format!("fan{index}_label")).unwrap_or_else(|| format!("Fan {index}"));
+                readings.fans.push(FanReading { label, rpm });
+            }
+        }
+    }
+
+    Ok(readings)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn add_chip(root: &Path, dir: &str, attributes: &[(&str, &str)]) {
+        let dir = root.join(dir);
+        fs::create_dir_all(&dir).unwrap();
+        for (file, value) in attributes {
+            fs::write(dir.join(file), format!("{value}\n")).unwrap();
+        }
+    }
+
+    #[test]
+    fn test_scan_hwmon() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path().to_path_buf();
+
+        add_chip(&root, "hwmon0", &[("name", "ACAD")]);
+        add_chip(
+            &root,
+            "hwmon1",
+            &[("name", "k10temp"), ("temp1_input", "61250")],
+        );
+        // iGPU and dGPU both use amdgpu
+        add_chip(
+            &root,
+            "hwmon2",
+            &[("name", "amdgpu"), ("temp1_input", "45000")],
+        );
+        add_chip(
+            &root,
+            "hwmon3",
+            &[("name", "amdgpu"), ("temp1_input", "52000")],
+        );
+        add_chip(
+            &root,
+            "hwmon4",
+            &[
+                ("name", "asus"),
+                ("fan1_input", "2400"),
+                ("fan1_label", "cpu_fan"),
+                ("fan2_input", "0"),
+                ("fan2_label", "gpu_fan"),
+            ],
+        );
+
+        let readings = scan_hwmon(&root).unwrap();
+        assert_eq!(readings.cpu_temp, Some(61.25));
+        assert_eq!(readings.gpu_temp, Some(52.0));
+        assert_eq!(
+            readings.fans,
+            [
+                FanReading {
+                    label: "cpu_fan".to_string(),
+                    rpm: 2400,
+                },
+                FanReading {
+                    label: "gpu_fan".to_string(),
+                    rpm: 0,
+                },
+            ]
+        );
+        assert_eq!(readings.fans[0].title(), "CPU Fan");
+
+        dir.close().unwrap();
+        assert!(scan_hwmon(&root).is_err());
+    }
+}
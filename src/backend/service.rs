@@ -0,0 +1,153 @@
+//! Management of the asusd systemd service.
+//!
+//! The unit state is read from systemd over D-Bus; starting and restarting
+//! go through `pkexec systemctl`, which asks for authorization via polkit.
+
+use std::io::Write;
+use std::process::{Command, Stdio};
+
+use super::asusctl::read_dbus_property;
+use super::{AsusctlError, Result, mock};
+
+const ASUSD_UNIT: &str = "asusd.service";
+
+const SYSTEMD_DEST: &str = "org.freedesktop.systemd1";
+/// Object path of asusd.service; '.' is escaped as "_2e" in unit paths
+const ASUSD_UNIT_PATH: &str = "/org/freedesktop/systemd1/unit/asusd_2eservice";
+const UNIT_INTERFACE: &str = "org.freedesktop.systemd1.Unit";
+
+/// pkexec exit codes when the user dismisses or fails authentication
+const PKEXEC_NOT_AUTHORIZED: i32 = 127;
+const PKEXEC_DISMISSED: i32 = 126;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ServiceState {
+    Running,
+    /// Starting, stopping or reloading
+    Changing,
+    Stopped,
+    Failed,
+    /// No asusd.service unit is installed
+    NotInstalled,
+}
+
+impl ServiceState {
+    fn from_unit_states(load_state: &str, active_state: &str) -> Self {
+        if load_state == "not-found" {
+            return Self::NotInstalled;
+        }
+
+        match active_state {
+            "active" => Self::Running,
+            "failed" => Self::Failed,
+            "activating" | "deactivating" | "reloading" | "refreshing" => Self::Changing,
+            _ => Self::Stopped,
+        }
+    }
+}
+
+impl std::fmt::Display for ServiceState {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Running => write!(f, "Running"),
+            Self::Changing => write!(f, "Starting or stopping"),
+            Self::Stopped => write!(f, "Stopped"),
+            Self::Failed => write!(f, "Failed"),
+            Self::NotInstalled => write!(f, "Not installed"),
+        }
+    }
+}
+
+/// Get the state of asusd.service from systemd
+pub fn get_service_state() -> Result<ServiceState> {
+    let read = |property| {
+        read_dbus_property::<String>(SYSTEMD_DEST, ASUSD_UNIT_PATH, UNIT_INTERFACE, property)
+    };
+
+    Ok(ServiceState::from_unit_states(
+        &read("LoadState")?,
+        &read("ActiveState")?,
+    ))
+}
+
+/// Start asusd, asking for authorization through polkit
+pub fn start_service() -> Result<()> {
+    run_systemctl_privileged("start")
+}
+
+/// Restart asusd, asking for authorization through polkit
+pub fn restart_service() -> Result<()> {
+    run_systemctl_privileged("restart")
+}
+
+fn run_systemctl_privileged(verb: &str) -> Result<()> {
+    if mock::enabled() {
+        log::debug!("Mock systemctl {verb} {ASUSD_UNIT}");
+        return Ok(());
+    }
+
+    run_privileged(&["systemctl", verb, ASUSD_UNIT], None)
+}
+
+/// Run a command as root through pkexec, feeding it `input` if given
+pub(super) fn run_privileged(args: &[&str], input: Option<&str>) -> Result<()> {
+    let mut child = Command::new("pkexec")
+        .args(args)
+        .stdin(if input.is_some() {
+            Stdio::piped()
+        } else {
+            Stdio::null()
+        })
+        .stdout(Stdio::null())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| {
+            if e.kind() == std::io::ErrorKind::NotFound {
+                AsusctlError::CommandFailed("pkexec is not installed".to_string())
+            } else {
+                AsusctlError::CommandFailed(format!("pkexec failed: {e}"))
+            }
+        })?;
+
+    if let (Some(input), Some(mut stdin)) = (input, child.stdin.take()) {
+        stdin
+            .write_all(input.as_bytes())
+            .map_err(|e| AsusctlError::CommandFailed(format!("pkexec failed: {e}")))?;
+    }
+    let output = child
+        .wait_with_output()
+        .map_err(|e| AsusctlError::CommandFailed(format!("pkexec failed: {e}")))?;
+
+    match output.status.code() {
+        Some(0) => Ok(()),
+        Some(PKEXEC_NOT_AUTHORIZED | PKEXEC_DISMISSED) => Err(AsusctlError::CommandFailed(
+            "authorization was cancelled".to_string(),
+        )),
+        _ => Err(AsusctlError::CommandFailed(
+            String::from_utf8_lossy(&output.stderr).trim().to_string(),
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_service_state_from_unit_states() {
+        let cases = [
+            ("loaded", "active", ServiceState::Running),
+            ("loaded", "activating", ServiceState::Changing),
+            ("loaded", "inactive", ServiceState::Stopped),
+            ("loaded", "failed", ServiceState::Failed),
+            ("not-found", "inactive", ServiceState::NotInstalled),
+        ];
+
+        for (load_state, active_state, expected) in cases {
+            assert_eq!(
+                ServiceState::from_unit_states(load_state, active_state),
+                expected
+            );
+        }
+    }
+}
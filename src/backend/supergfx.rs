@@ -0,0 +1,159 @@
+//! Graphics mode switching through supergfxd.
+//!
+//! supergfxd is a separate daemon from asusd and often isn't installed, so
+//! every call first checks that it owns its bus name and reports
+//! `Unsupported` otherwise. Switching modes usually only takes effect after
+//! the user logs out; supergfxd says which action it needs.
+
+use zbus::zvariant::Structure;
+
+use super::asusctl::{FromDbus, bus_name_has_owner, call_dbus};
+use super::{AsusctlError, Result};
+
+const SUPERGFX_DEST: &str = "org.supergfxctl.Daemon";
+const SUPERGFX_PATH: &str = "/org/supergfxctl/Gfx";
+const SUPERGFX_INTERFACE: &str = "org.supergfxctl.Daemon";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GfxMode {
+    /// iGPU drives the display, the dGPU renders on demand
+    Hybrid,
+    /// dGPU powered off
+    Integrated,
+    NvidiaNoModeset,
+    /// dGPU handed to virtual machines
+    Vfio,
+    /// ASUS XG Mobile external GPU
+    AsusEgpu,
+    /// dGPU drives the display through the MUX switch
+    AsusMuxDgpu,
+}
+
+impl GfxMode {
+    fn from_u32(value: u32) -> Option<Self> {
+        match value {
+            0 => Some(Self::Hybrid),
+            1 => Some(Self::Integrated),
+            2 => Some(Self::NvidiaNoModeset),
+            3 => Some(Self::Vfio),
+            4 => Some(Self::AsusEgpu),
+            5 => Some(Self::AsusMuxDgpu),
+            _ => None,
+        }
+    }
+
+    fn as_u32(&self) -> u32 {
+        match self {
+            Self::Hybrid => 0,
+            Self::Integrated => 1,
+            Self::NvidiaNoModeset => 2,
+            Self::Vfio => 3,
+            Self::AsusEgpu => 4,
+            Self::AsusMuxDgpu => 5,
+        }
+    }
+}
+
+impl std::fmt::Display for GfxMode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Hybrid => write!(f, "Hybrid"),
+            Self::Integrated => write!(f, "Integrated"),
+            Self::NvidiaNoModeset => write!(f, "NVIDIA (no modeset)"),
+            Self::Vfio => write!(f, "VFIO"),
+            Self::AsusEgpu => write!(f, "External GPU"),
+            Self::AsusMuxDgpu => write!(f, "Dedicated"),
+        }
+    }
+}
+
+/// What the user has to do before a mode switch takes effect
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GfxAction {
+    Logout,
+    Reboot,
+    /// Switch to Integrated first, then to the requested mode
+    SwitchToIntegrated,
+    /// Turn off the external GPU first
+    AsusEgpuDisable,
+    Nothing,
+}
+
+impl GfxAction {
+    fn from_u32(value: u32) -> Result<Self> {
+        match value {
+            0 => Ok(Self::Logout),
+            1 => Ok(Self::Reboot),
+            2 => Ok(Self::SwitchToIntegrated),
+            3 => Ok(Self::AsusEgpuDisable),
+            4 => Ok(Self::Nothing),
+            _ => Err(AsusctlError::ParseError(format!(
+                "Unknown graphics action: {value}"
+            ))),
+        }
+    }
+}
+
+impl std::fmt::Display for GfxAction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Logout => write!(f, "Log out to finish switching"),
+            Self::Reboot => write!(f, "Reboot to finish switching"),
+            Self::SwitchToIntegrated => write!(f, "Switch to Integrated first"),
+            Self::AsusEgpuDisable => write!(f, "Turn off the external GPU first"),
+            Self::Nothing => write!(f, "Graphics mode switched"),
+        }
+    }
+}
+
+/// Call a supergfxd method that replies with a single value
+fn call_supergfx<T: FromDbus>(method: &str, args: Option<&Structure<'_>>) -> Result<T> {
+    if !bus_name_has_owner(SUPERGFX_DEST) {
+        return Err(AsusctlError::Unsupported(
+            "supergfxd is not running".to_string(),
+        ));
+    }
+
+    call_dbus(
+        SUPERGFX_DEST,
+        SUPERGFX_PATH,
+        SUPERGFX_INTERFACE,
+        method,
+        args,
+    )
+}
+
+pub fn get_gfx_mode() -> Result<GfxMode> {
+    let value = call_supergfx("Mode", None)?;
+    GfxMode::from_u32(value)
+        .ok_or_else(|| AsusctlError::ParseError(format!("Unknown graphics mode: {value}")))
+}
+
+/// Modes this laptop can switch to, skipping ones this version doesn't know
+pub fn get_gfx_supported_modes() -> Result<Vec<GfxMode>> {
+    let modes: Vec<u32> = call_supergfx("Supported", None)?;
+    Ok(modes.into_iter().filter_map(GfxMode::from_u32).collect())
+}
+
+/// Request a mode switch, returning what the user must do to complete it
+pub fn set_gfx_mode(mode: GfxMode) -> Result<GfxAction> {
+    GfxAction::from_u32(call_supergfx(
+        "SetMode",
+        Some(&Structure::from((mode.as_u32(),))),
+    )?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_gfx_mode_round_trip() {
+        for value in 0..6 {
+            let mode = GfxMode::from_u32(value).unwrap();
+            assert_eq!(mode.as_u32(), value);
+        }
+        // supergfxd's "None" and anything newer
+        assert_eq!(GfxMode::from_u32(6), None);
+    }
+}
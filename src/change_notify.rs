@@ -0,0 +1,94 @@
+//! Desktop notifications for profile and charge limit changes made outside the app.
+//!
+//! Hotkeys and other tools change asusd's properties directly. While enabled,
+//! this watches asusd's PropertiesChanged signal, so changes are noticed
+//! whether or not a window is open. Writes made by the app itself are
+//! recognised with `backend::is_own_change` and stay quiet. The status
+//! icon uses the same watch to keep its profile current.
+
+use gtk4::gio;
+use gtk4::glib;
+use gtk4::prelude::*;
+use std::collections::HashMap;
+
+use crate::backend::{self, PowerProfile};
+
+const PROPERTIES_INTERFACE: &str = "org.freedesktop.DBus.Properties";
+
+/// Active change watch; dropping it unsubscribes
+#[derive(Debug)]
+pub struct ChangeNotifier {
+    _subscription: gio::SignalSubscription,
+}
+
+impl ChangeNotifier {
+    /// Connect to the system bus and start watching asusd's platform properties
+    pub fn start() -> Result<Self, glib::Error> {
+        let subscription = watch_platform_properties(|changed| {
+            if let Some(value) = changed.get("PlatformProfile").and_then(|v| v.get::<u32>())
+                && !backend::is_own_change("PlatformProfile", value)
+                && let Some(profile) = PowerProfile::from_platform_profile_value(value)
+            {
+                notify(
+                    "profile-changed",
+                    "Power profile changed",
+                    &format!("Now using the {profile} profile"),
+                );
+            }
+
+            if let Some(limit) = changed
+                .get("ChargeControlEndThreshold")
+                .and_then(|v| v.get::<u8>())
+                && !backend::is_own_change("ChargeControlEndThreshold", limit.into())
+            {
+                notify(
+                    "charge-limit-changed",
+                    "Charge limit changed",
+                    &format!("Battery now charges up to {limit}%"),
+                );
+            }
+        })?;
+
+        Ok(Self {
+            _subscription: subscription,
+        })
+    }
+}
+
+/// Call `on_change` with the changed values whenever asusd's platform
+/// properties change, until the subscription is dropped
+pub fn watch_platform_properties(
+    on_change: impl Fn(&HashMap<String, glib::Variant>) + 'static,
+) -> Result<gio::SignalSubscription, glib::Error> {
+    let connection = gio::bus_get_sync(gio::BusType::System, None::<&gio::Cancellable>)?;
+    let asusd = backend::bus_names();
+
+    Ok(connection.subscribe_to_signal(
+        Some(asusd.dest),
+        Some(PROPERTIES_INTERFACE),
+        Some("PropertiesChanged"),
+        Some(asusd.platform_path),
+        Some(asusd.platform_interface),
+        gio::DBusSignalFlags::NONE,
+        move |signal| {
+            if let Some((_, changed, _)) =
+                signal
+                    .parameters
+                    .get::<(String, HashMap<String, glib::Variant>, Vec<String>)>()
+            {
+                on_change(&changed);
+            }
+        },
+    ))
+}
+
+/// Send a desktop notification; each `id` replaces its previous one
+fn notify(id: &str, title: &str, body: &str) {
+    let Some(app) = gio::Application::default() else {
+        return;
+    };
+
+    let notification = gio::Notification::new(title);
+    notification.set_body(Some(body));
+    app.send_notification(Some(id), &notification);
+}
@@ -1,11 +1,17 @@
 mod app;
 mod backend;
+mod change_notify;
+mod profile_sync;
+mod tray;
 mod ui;
 
 use gtk4::gio;
 use gtk4::prelude::*;
 
 fn main() -> gtk4::glib::ExitCode {
+    // Warnings and errors by default; RUST_LOG=debug also shows every backend call
+    env_logger::Builder::from_env(env_logger::Env::default().default_filter_or("warn")).init();
+
     // Register resources (this is fine before init)
     gio::resources_register_include!("asusctl-gui.gresource")
         .expect("Failed to register resources.");
@@ -0,0 +1,224 @@
+//! Mirror the GNOME power profile and the asusd platform profile.
+//!
+//! power-profiles-daemon (used by GNOME Settings) and asusd both expose a
+//! profile control. When enabled, a change on either side is applied to the
+//! other one so the two never disagree.
+//!
+//! Loop guard: every profile we mirror is remembered in `last_synced`. The
+//! echo of our own write arrives as a PropertiesChanged signal carrying the
+//! same profile and is ignored. A failed write clears it again, so the next
+//! change to that profile is still mirrored.
+//!
+//! On start, asusd's current profile is mirrored to GNOME once, so the two
+//! agree before either side changes.
+
+use gtk4::gio;
+use gtk4::glib;
+use gtk4::prelude::*;
+use std::cell::Cell;
+use std::collections::HashMap;
+use std::rc::Rc;
+
+use crate::backend::{self, PowerProfile};
+
+const PPD_DEST: &str = "net.hadess.PowerProfiles";
+const PPD_PATH: &str = "/net/hadess/PowerProfiles";
+const PPD_INTERFACE: &str = "net.hadess.PowerProfiles";
+const PPD_PROPERTY: &str = "ActiveProfile";
+
+const ASUSD_PROPERTY: &str = "PlatformProfile";
+
+const PROPERTIES_INTERFACE: &str = "org.freedesktop.DBus.Properties";
+
+#[derive(Debug)]
+struct SyncState {
+    connection: gio::DBusConnection,
+    last_synced: Cell<Option<PowerProfile>>,
+}
+
+/// Active profile mirroring; dropping it unsubscribes from both services
+#[derive(Debug)]
+pub struct ProfileSync {
+    _subscriptions: [gio::SignalSubscription; 2],
+}
+
+impl ProfileSync {
+    /// Connect to the system bus and start watching both profile properties
+    pub fn start() -> Result<Self, glib::Error> {
+        let connection = gio::bus_get_sync(gio::BusType::System, None::<&gio::Cancellable>)?;
+        let asusd = backend::bus_names();
+        let state = Rc::new(SyncState {
+            connection: connection.clone(),
+            last_synced: Cell::new(None),
+        });
+
+        // GNOME -> asusd
+        let ppd_state = state.clone();
+        let ppd_subscription = Self::watch_property(
+            &connection,
+            PPD_DEST,
+            PPD_PATH,
+            PPD_INTERFACE,
+            PPD_PROPERTY,
+            move |value| {
+                let Some(profile) = value.str().and_then(PowerProfile::from_ppd_name) else {
+                    return;
+                };
+                if ppd_state.claim(profile) {
+                    ppd_state.set_property(
+                        asusd.dest,
+                        asusd.platform_path,
+                        asusd.platform_interface,
+                        ASUSD_PROPERTY,
+                        profile.platform_profile_value().to_variant(),
+                    );
+                }
+            },
+        );
+
+        // asusd -> GNOME
+        let asusd_state = state.clone();
+        let asusd_subscription = Self::watch_property(
+            &connection,
+            asusd.dest,
+            asusd.platform_path,
+            asusd.platform_interface,
+            ASUSD_PROPERTY,
+            move |value| {
+                let Some(profile) = value
+                    .get::<u32>()
+                    .and_then(PowerProfile::from_platform_profile_value)
+                else {
+                    return;
+                };
+                if asusd_state.claim(profile) {
+                    asusd_state.set_property(
+                        PPD_DEST,
+                        PPD_PATH,
+                        PPD_INTERFACE,
+                        PPD_PROPERTY,
+                        profile.ppd_name().to_variant(),
+                    );
+                }
+            },
+        );
+
+        state.sync_from_asusd();
+
+        Ok(Self {
+            _subscriptions: [ppd_subscription, asusd_subscription],
+        })
+    }
+
+    /// Subscribe to PropertiesChanged and call `on_change` with the new value of `property`
+    fn watch_property<F: Fn(&glib::Variant) + 'static>(
+        connection: &gio::DBusConnection,
+        dest: &str,
+        path: &str,
+        interface: &str,
+        property: &'static str,
+        on_change: F,
+    ) -> gio::SignalSubscription {
+        connection.subscribe_to_signal(
+            Some(dest),
+            Some(PROPERTIES_INTERFACE),
+            Some("PropertiesChanged"),
+            Some(path),
+            Some(interface),
+            gio::DBusSignalFlags::NONE,
+            move |signal| {
+                let Some((_, changed, _)) =
+                    signal
+                        .parameters
+                        .get::<(String, HashMap<String, glib::Variant>, Vec<String>)>()
+                else {
+                    return;
+                };
+
+                if let Some(value) = changed.get(property) {
+                    on_change(value);
+                }
+            },
+        )
+    }
+}
+
+impl SyncState {
+    /// Record `profile` as mirrored; returns false if it is the echo of our own write
+    fn claim(&self, profile: PowerProfile) -> bool {
+        self.last_synced.replace(Some(profile)) != Some(profile)
+    }
+
+    /// Mirror asusd's current profile to GNOME
+    fn sync_from_asusd(self: &Rc<Self>) {
+        let asusd = backend::bus_names();
+        let state = self.clone();
+
+        self.connection.call(
+            Some(asusd.dest),
+            asusd.platform_path,
+            PROPERTIES_INTERFACE,
+            "Get",
+            Some(&(asusd.platform_interface, ASUSD_PROPERTY).to_variant()),
+            None,
+            gio::DBusCallFlags::NONE,
+            -1,
+            None::<&gio::Cancellable>,
+            move |result| {
+                let profile = match result {
+                    Ok(reply) => reply
+                        .get::<(glib::Variant,)>()
+                        .and_then(|(value,)| value.get::<u32>())
+                        .and_then(PowerProfile::from_platform_profile_value),
+                    Err(e) => {
+                        log::warn!("Failed to read the asusd profile for the initial sync: {e}");
+                        return;
+                    }
+                };
+                if let Some(profile) = profile
+                    && state.claim(profile)
+                {
+                    state.set_property(
+                        PPD_DEST,
+                        PPD_PATH,
+                        PPD_INTERFACE,
+                        PPD_PROPERTY,
+                        profile.ppd_name().to_variant(),
+                    );
+                }
+            },
+        );
+    }
+
+    fn set_property(
+        self: &Rc<Self>,
+        dest: &str,
+        path: &str,
+        interface: &str,
+        property: &str,
+        value: glib::Variant,
+    ) {
+        let parameters = (interface, property, value).to_variant();
+        let target = format!("{interface}.{property}");
+        let state = self.clone();
+
+        self.connection.call(
+            Some(dest),
+            path,
+            PROPERTIES_INTERFACE,
+            "Set",
+            Some(&parameters),
+            None,
+            gio::DBusCallFlags::NONE,
+            -1,
+            None::<&gio::Cancellable>,
+            move |result| {
+                if let Err(e) = result {
+                    log::warn!("Failed to mirror power profile to {target}: {e}");
+                    // No echo will come, and the next change must not be taken for one
+                    state.last_synced.set(None);
+                }
+            },
+        );
+    }
+}
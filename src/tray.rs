@@ -0,0 +1,204 @@
+//! Status icon for running in the background.
+//!
+//! The icon is a StatusNotifierItem, shown by KDE, most other desktops and
+//! GNOME with the AppIndicator extension. ksni serves it from its own
+//! thread; menu actions are handed to the GTK main thread, where the
+//! application and its window live.
+
+use gtk4::prelude::*;
+use gtk4::{gio, glib};
+use ksni::blocking::TrayMethods;
+use ksni::menu::{MenuItem, RadioGroup, RadioItem, StandardItem};
+
+use crate::backend::{self, PowerProfile};
+use crate::change_notify;
+use crate::ui::AsusctlGuiWindow;
+
+/// Tray state, owned by ksni's thread
+#[derive(Debug, Default)]
+struct TrayIcon {
+    /// Active profile, kept current from asusd's change signal
+    profile: Option<PowerProfile>,
+}
+
+impl ksni::Tray for TrayIcon {
+    fn id(&self) -> String {
+        "asusctl-gui".to_string()
+    }
+
+    fn title(&self) -> String {
+        "asusctl".to_string()
+    }
+
+    fn icon_name(&self) -> String {
+        "preferences-other-symbolic".to_string()
+    }
+
+    fn tool_tip(&self) -> ksni::ToolTip {
+        ksni::ToolTip {
+            title: "asusctl".to_string(),
+            description: self
+                .profile
+                .map(|profile| format!("{profile} profile"))
+                .unwrap_or_default(),
+            ..Default::default()
+        }
+    }
+
+    fn activate(&mut self, _x: i32, _y: i32) {
+        on_main_thread(show_window);
+    }
+
+    fn menu_about_to_show(&mut self) {
+        // A D-Bus property read, quick enough for ksni's thread
+        self.profile = backend::get_profile_dbus().ok();
+    }
+
+    fn menu(&self) -> Vec<MenuItem<Self>> {
+        vec![
+            RadioGroup {
+                // Out of range selects nothing while the profile is unknown
+                selected: self
+                    .profile
+                    .and_then(|profile| PowerProfile::ALL.iter().position(|p| *p == profile))
+                    .unwrap_or(usize::MAX),
+                select: Box::new(|tray: &mut Self, index| {
+                    let Some(&profile) = PowerProfile::ALL.get(index) else {
+                        return;
+                    };
+                    tray.profile = Some(profile);
+                    on_main_thread(move || set_profile(profile));
+                }),
+                options: PowerProfile::ALL
+                    .iter()
+                    .map(|profile| RadioItem {
+                        label: profile.to_string(),
+                        ..Default::default()
+                    })
+                    .collect(),
+            }
+            .into(),
+            MenuItem::Separator,
+            StandardItem {
+                label: "Show Window".to_string(),
+                activate: Box::new(|_| on_main_thread(show_window)),
+                ..Default::default()
+            }
+            .into(),
+            StandardItem {
+                label: "Quit".to_string(),
+                icon_name: "application-exit-symbolic".to_string(),
+                activate: Box::new(|_| {
+                    on_main_thread(|| {
+                        if let Some(app) = gio::Application::default() {
+                            app.quit();
+                        }
+                    })
+                }),
+                ..Default::default()
+            }
+            .into(),
+        ]
+    }
+}
+
+/// Run `f` on the GTK main thread
+fn on_main_thread(f: impl FnOnce() + Send + 'static) {
+    glib::MainContext::default().invoke(f);
+}
+
+/// Bring the window back, opening a new one if it was closed
+fn show_window() {
+    let Some(app) = gio::Application::default().and_downcast::<gtk4::Application>() else {
+        return;
+    };
+
+    match app.active_window() {
+        Some(window) => window.present(),
+        None => app.activate(),
+    }
+}
+
+fn set_profile(profile: PowerProfile) {
+    glib::spawn_future_local(async move {
+        match backend::queue::submit(move || backend::set_profile(profile)).await {
+            Ok(Ok(())) => {}
+            Ok(Err(e)) => {
+                log::warn!("Failed to set profile from the tray: {e}");
+                return;
+            }
+            Err(_) => {
+                log::error!("Backend call panicked");
+                return;
+            }
+        }
+
+        if let Some(window) = gio::Application::default()
+            .and_downcast::<gtk4::Application>()
+            .and_then(|app| app.active_window())
+            .and_downcast::<AsusctlGuiWindow>()
+        {
+            window.profile_applied(profile);
+        }
+    });
+}
+
+/// A running status icon; dropping it removes the icon
+pub struct StatusIcon {
+    handle: ksni::blocking::Handle<TrayIcon>,
+    _profile_watch: Option<gio::SignalSubscription>,
+}
+
+impl std::fmt::Debug for StatusIcon {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("StatusIcon")
+            .field("closed", &self.handle.is_closed())
+            .finish()
+    }
+}
+
+impl StatusIcon {
+    /// Show the icon, failing when the desktop has no StatusNotifierItem host
+    ///
+    /// The tooltip follows the active profile, whether it was changed in the
+    /// app, by hotkey or by another tool.
+    pub fn start() -> Result<Self, ksni::Error> {
+        let handle = TrayIcon::default().spawn()?;
+
+        let tray = handle.clone();
+        glib::spawn_future_local(async move {
+            match backend::queue::submit(backend::get_profile_dbus).await {
+                Ok(Ok(profile)) => {
+                    tray.update(|tray| tray.profile = Some(profile));
+                }
+                Ok(Err(e)) => log::warn!("Failed to read the profile for the status icon: {e}"),
+                Err(_) => log::error!("Backend read panicked"),
+            }
+        });
+
+        let tray = handle.clone();
+        let profile_watch = change_notify::watch_platform_properties(move |changed| {
+            if let Some(profile) = changed
+                .get("PlatformProfile")
+                .and_then(|value| value.get::<u32>())
+                .and_then(PowerProfile::from_platform_profile_value)
+            {
+                tray.update(|tray| tray.profile = Some(profile));
+            }
+        })
+        .inspect_err(|e| log::warn!("Status icon won't follow profile changes: {e}"))
+        .ok();
+
+        Ok(Self {
+            handle,
+            _profile_watch: profile_watch,
+        })
+    }
+}
+
+impl Drop for StatusIcon {
+    fn drop(&mut self) {
+        // The service thread finishes on its own
+        let _ = self.handle.shutdown();
+    }
+}
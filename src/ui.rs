@@ -1,18 +1,30 @@
+mod bind;
 mod pages;
 mod preferences_dialog;
 mod theme_switcher;
 mod window;
 
-pub use pages::{AboutPage, AuraPage, PowerPage, SlashPage};
+pub use pages::{AboutPage, AnimePage, AuraPage, FanCurvesPage, PowerPage, SlashPage};
 pub use preferences_dialog::PreferencesDialog;
-pub use theme_switcher::ThemeSwitcher;
-pub use window::AsusctlGuiWindow;
+pub use theme_switcher::{ThemeSwitcher, color_scheme_from_setting};
+pub use window::{AsusctlGuiWindow, PROFILE_ACCELS};
 
+use gtk4::prelude::*;
 use std::fmt;
+use std::rc::Rc;
+
+use crate::backend::SupportedFeatures;
+use bind::PageLoader;
+
+/// Version of this build, from Cargo.toml
+pub const APP_VERSION: &str = env!("CARGO_PKG_VERSION");
 
 /// Trait for pages that can refresh their data
 pub trait Refreshable {
     fn refresh(&self);
+
+    /// The loader `refresh` goes through, to follow a load in flight
+    fn loader(&self) -> Rc<PageLoader>;
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
@@ -21,18 +33,29 @@ pub enum Page {
     About,
     Aura,
     Power,
+    FanCurves,
     Slash,
+    Anime,
 }
 
 impl Page {
-    pub const ALL: [Page; 4] = [Page::About, Page::Aura, Page::Power, Page::Slash];
+    pub const ALL: [Page; 6] = [
+        Page::About,
+        Page::Aura,
+        Page::Power,
+        Page::FanCurves,
+        Page::Slash,
+        Page::Anime,
+    ];
 
     pub fn as_str(&self) -> &'static str {
         match self {
             Page::About => "about",
             Page::Aura => "aura",
             Page::Power => "power",
+            Page::FanCurves => "fan-curves",
             Page::Slash => "slash",
+            Page::Anime => "anime",
         }
     }
 
@@ -41,7 +64,9 @@ impl Page {
             Page::About => "About",
             Page::Aura => "Aura",
             Page::Power => "Power",
+            Page::FanCurves => "Fan Curves",
             Page::Slash => "Slash",
+            Page::Anime => "AniMe Matrix",
         }
     }
 
@@ -50,7 +75,29 @@ impl Page {
             Page::About => "computer-symbolic",
             Page::Aura => "keyboard-brightness-symbolic",
             Page::Power => "gnome-power-manager-symbolic",
+            Page::FanCurves => "weather-windy-symbolic",
             Page::Slash => "display-brightness-symbolic",
+            Page::Anime => "view-app-grid-symbolic",
+        }
+    }
+
+    /// Whether `features` leave anything to control on this page
+    ///
+    /// About and Power always show something (system and battery info).
+    pub fn is_supported(&self, features: &SupportedFeatures) -> bool {
+        match self {
+            Page::About | Page::Power => true,
+            Page::Aura => {
+                features.has_aura
+                    || !features.aura_modes.is_empty()
+                    || features
+                        .keyboard_brightness_levels
+                        .as_ref()
+                        .is_some_and(|levels| !levels.is_empty())
+            }
+            Page::FanCurves => features.has_fan_curves,
+            Page::Slash => features.has_slash,
+            Page::Anime => features.has_anime,
         }
     }
 
@@ -59,7 +106,9 @@ impl Page {
             Page::About => 0,
             Page::Aura => 1,
             Page::Power => 2,
-            Page::Slash => 3,
+            Page::FanCurves => 3,
+            Page::Slash => 4,
+            Page::Anime => 5,
         }
     }
 
@@ -68,10 +117,33 @@ impl Page {
             0 => Some(Page::About),
             1 => Some(Page::Aura),
             2 => Some(Page::Power),
-            3 => Some(Page::Slash),
+            3 => Some(Page::FanCurves),
+            4 => Some(Page::Slash),
+            5 => Some(Page::Anime),
             _ => None,
         }
     }
+
+    /// Refresh this page's widget in `stack`, where it is added under `as_str()`
+    ///
+    /// Returns the page's loader, to wait for the refresh to finish.
+    pub fn refresh_in_stack(&self, stack: &gtk4::Stack) -> Option<Rc<PageLoader>> {
+        let child = stack.child_by_name(self.as_str())?;
+
+        let page: Option<&dyn Refreshable> = match self {
+            Page::About => child.downcast_ref::<AboutPage>().map(|p| p as _),
+            Page::Aura => child.downcast_ref::<AuraPage>().map(|p| p as _),
+            Page::Power => child.downcast_ref::<PowerPage>().map(|p| p as _),
+            Page::FanCurves => child.downcast_ref::<FanCurvesPage>().map(|p| p as _),
+            Page::Slash => child.downcast_ref::<SlashPage>().map(|p| p as _),
+            Page::Anime => child.downcast_ref::<AnimePage>().map(|p| p as _),
+        };
+
+        page.map(|page| {
+            page.refresh();
+            page.loader()
+        })
+    }
 }
 
 impl TryFrom<&str> for Page {
@@ -82,7 +154,9 @@ impl TryFrom<&str> for Page {
             "about" => Ok(Page::About),
             "aura" => Ok(Page::Aura),
             "power" => Ok(Page::Power),
+            "fan-curves" => Ok(Page::FanCurves),
             "slash" => Ok(Page::Slash),
+            "anime" => Ok(Page::Anime),
             _ => Err(()),
         }
     }
@@ -93,3 +167,19 @@ impl fmt::Display for Page {
         write!(f, "{}", self.as_str())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_page_lookups_stay_in_sync() {
+        for (position, page) in Page::ALL.iter().enumerate() {
+            assert_eq!(page.index(), position as u32);
+            assert_eq!(Page::from_index(page.index()), Some(*page));
+            assert_eq!(Page::try_from(page.as_str()), Ok(*page));
+        }
+
+        assert_eq!(Page::from_index(Page::ALL.len() as u32), None);
+    }
+}
@@ -0,0 +1,656 @@
+//! Helpers for binding controls to backend setters and reads.
+//!
+//! Backend calls spawn asusctl or wait on D-Bus and can take a noticeable
+//! amount of time, so they run on the backend command queue, one at a time.
+//! Results are delivered back on the GTK main thread, where errors are
+//! reported as toasts. Reads share the queue, so a refresh never sees the
+//! state from before a setter the user already triggered.
+
+use gtk4::prelude::*;
+use gtk4::{gio, glib};
+use libadwaita as adw;
+use std::cell::{Cell, RefCell};
+use std::fmt;
+use std::rc::Rc;
+use std::time::Duration;
+
+use crate::backend;
+
+/// Flag raised while a page writes backend state into its widgets
+///
+/// Signal handlers check `is_active()` and skip the backend call, so
+/// restoring the UI from hardware never writes the same value back.
+#[derive(Debug, Default)]
+pub struct UpdateGuard(Cell<bool>);
+
+impl UpdateGuard {
+    pub fn is_active(&self) -> bool {
+        self.0.get()
+    }
+
+    /// Raise the flag until the returned token is dropped
+    pub fn hold(&self) -> UpdateGuardToken<'_> {
+        UpdateGuardToken {
+            guard: self,
+            was_active: self.0.replace(true),
+        }
+    }
+}
+
+#[must_use = "the guard is released as soon as the token is dropped"]
+pub struct UpdateGuardToken<'a> {
+    guard: &'a UpdateGuard,
+    was_active: bool,
+}
+
+impl Drop for UpdateGuardToken<'_> {
+    fn drop(&mut self) {
+        self.guard.0.set(self.was_active);
+    }
+}
+
+/// Last value seen for one control, to notice changes made outside the app
+///
+/// Refreshes record what they read with `update()`; handlers record what the
+/// user picked with `set()` so the read-back of a user change isn't flagged.
+pub struct KnownValue<T>(Cell<Option<T>>);
+
+impl<T: Copy + fmt::Debug> fmt::Debug for KnownValue<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("KnownValue").field(&self.0.get()).finish()
+    }
+}
+
+impl<T> Default for KnownValue<T> {
+    fn default() -> Self {
+        Self(Cell::new(None))
+    }
+}
+
+impl<T: Copy + PartialEq> KnownValue<T> {
+    /// Record a value read from the hardware; true if it changed since the last one
+    pub fn update(&self, value: T) -> bool {
+        self.0
+            .replace(Some(value))
+            .is_some_and(|previous| previous != value)
+    }
+
+    /// Record a value the user just applied
+    pub fn set(&self, value: T) {
+        self.0.set(Some(value));
+    }
+}
+
+/// Last value a setter successfully applied to one control
+///
+/// Refreshes compare the hardware against it; while the two differ, e.g.
+/// after a change through the CLI, the control's row is marked.
+pub struct AppliedValue<T>(Cell<Option<T>>);
+
+impl<T: Copy + fmt::Debug> fmt::Debug for AppliedValue<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("AppliedValue").field(&self.0.get()).finish()
+    }
+}
+
+impl<T> Default for AppliedValue<T> {
+    fn default() -> Self {
+        Self(Cell::new(None))
+    }
+}
+
+impl<T: Copy + PartialEq> AppliedValue<T> {
+    pub fn set(&self, value: T) {
+        self.0.set(Some(value));
+    }
+
+    /// Whether `current` differs from the last applied value
+    ///
+    /// Before anything was applied there is nothing to differ from.
+    pub fn diverges(&self, current: T) -> bool {
+        self.0.get().is_some_and(|applied| applied != current)
+    }
+
+    /// Record a value just applied to `widget`, which now matches the hardware
+    pub fn record(&self, widget: &impl IsA<gtk4::Widget>, value: T) {
+        self.set(value);
+        mark_diverged(widget, false);
+    }
+
+    /// Mark `widget` while the hardware's `current` value differs from the applied one
+    pub fn check(&self, widget: &impl IsA<gtk4::Widget>, current: T) {
+        mark_diverged(widget, self.diverges(current));
+    }
+}
+
+/// Mark or unmark the row around `widget` as differing from what was applied
+pub fn mark_diverged(widget: &impl IsA<gtk4::Widget>, diverged: bool) {
+    let target = widget
+        .ancestor(adw::PreferencesRow::static_type())
+        .unwrap_or_else(|| widget.clone().upcast());
+
+    if diverged == target.has_css_class("differs-from-applied") {
+        return;
+    }
+    if diverged {
+        target.add_css_class("differs-from-applied");
+        target.set_tooltip_text(Some("Changed outside the app since it was last set here"));
+    } else {
+        target.remove_css_class("differs-from-applied");
+        target.set_tooltip_text(None);
+    }
+}
+
+/// Delays a call until its trigger has been quiet for a while
+///
+/// Used for controls that change rapidly, like a dragged scale, so only the
+/// final value reaches asusctl.
+#[derive(Debug, Default)]
+pub struct Debouncer(RefCell<Option<glib::SourceId>>);
+
+impl Debouncer {
+    /// Run `f` after `delay`, replacing any call still waiting
+    pub fn call(self: &Rc<Self>, delay: Duration, f: impl FnOnce() + 'static) {
+        if let Some(pending) = self.0.take() {
+            pending.remove();
+        }
+
+        let this = Rc::downgrade(self);
+        let source_id = glib::timeout_add_local_once(delay, move || {
+            // The source is gone once it has fired, so never remove it again
+            if let Some(this) = this.upgrade() {
+                this.0.take();
+            }
+            f();
+        });
+
+        self.0.replace(Some(source_id));
+    }
+}
+
+/// Loads a page's backend state in the background
+///
+/// Only one load is in flight at a time. A load requested meanwhile waits
+/// until it finishes, replacing any other waiting one. Every finished result
+/// is still applied, so a page polled faster than it loads keeps updating.
+/// Until the first load finishes the page ignores input, so its placeholder
+/// values can't be edited. Sensitivity is left to the window, which greys
+/// out pages while asusd is unavailable.
+#[derive(Default)]
+pub struct PageLoader {
+    in_flight: Cell<bool>,
+    loaded: Cell<bool>,
+    waiting: RefCell<Option<Box<dyn FnOnce()>>>,
+    on_idle: RefCell<Vec<Box<dyn FnOnce()>>>,
+}
+
+impl fmt::Debug for PageLoader {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PageLoader")
+            .field("in_flight", &self.in_flight.get())
+            .field("loaded", &self.loaded.get())
+            .field("waiting", &self.waiting.borrow().is_some())
+            .field("on_idle", &self.on_idle.borrow().len())
+            .finish()
+    }
+}
+
+impl PageLoader {
+    /// Run `load` on the backend queue, then `apply` its result to `page`
+    pub fn load<W, T, L, A>(self: &Rc<Self>, page: &W, load: L, apply: A)
+    where
+        W: IsA<gtk4::Widget>,
+        T: Send + 'static,
+        L: FnOnce() -> T + Send + 'static,
+        A: FnOnce(&W, T) + 'static,
+    {
+        if self.in_flight.get() {
+            let this = self.clone();
+            let page = page.clone();
+            self.waiting
+                .replace(Some(Box::new(move || this.load(&page, load, apply))));
+            return;
+        }
+
+        self.in_flight.set(true);
+        if !self.loaded.get() {
+            set_accepts_input(page.upcast_ref(), false);
+        }
+
+        let this = self.clone();
+        let page = page.downgrade();
+        glib::spawn_future_local(async move {
+            let result = backend::queue::submit(load).await;
+            this.in_flight.set(false);
+
+            if let Some(page) = page.upgrade() {
+                set_accepts_input(page.upcast_ref(), true);
+
+                match result {
+                    Ok(state) => {
+                        this.loaded.set(true);
+                        apply(&page, state);
+                    }
+                    Err(_) => log::error!("Loading page state panicked"),
+                }
+            }
+
+            if let Some(next) = this.waiting.take() {
+                next();
+            }
+            if !this.in_flight.get() {
+                for f in this.on_idle.take() {
+                    f();
+                }
+            }
+        });
+    }
+
+    /// Run `f` once no load is in flight or waiting, right away if none is
+    pub fn when_idle(&self, f: impl FnOnce() + 'static) {
+        if self.in_flight.get() {
+            self.on_idle.borrow_mut().push(Box::new(f));
+        } else {
+            f();
+        }
+    }
+}
+
+fn set_accepts_input(widget: &gtk4::Widget, accepts: bool) {
+    widget.set_can_target(accepts);
+    widget.set_can_focus(accepts);
+}
+
+/// Run a one-off backend read on the queue and hand its result to `apply`
+pub fn load_then<T, L, A>(load: L, apply: A)
+where
+    T: Send + 'static,
+    L: FnOnce() -> T + Send + 'static,
+    A: FnOnce(T) + 'static,
+{
+    glib::spawn_future_local(async move {
+        match backend::queue::submit(load).await {
+            Ok(value) => apply(value),
+            Err(_) => log::error!("Backend read panicked"),
+        }
+    });
+}
+
+/// How long a control stays highlighted after an external change
+const EXTERNAL_CHANGE_HIGHLIGHT: Duration = Duration::from_millis(1500);
+
+/// Briefly highlight the row around `widget` to show it changed externally
+pub fn flash_changed(widget: &impl IsA<gtk4::Widget>) {
+    let target = widget
+        .ancestor(adw::PreferencesRow::static_type())
+        .unwrap_or_else(|| widget.clone().upcast());
+
+    target.add_css_class("externally-changed");
+    glib::timeout_add_local_once(EXTERNAL_CHANGE_HIGHLIGHT, move || {
+        target.remove_css_class("externally-changed");
+    });
+}
+
+/// Run `setter` off the main thread, keeping `busy` insensitive until it returns
+///
+/// Use this for discrete controls (buttons, switches, combos) so fast
+/// clicking can't queue up conflicting commands.
+pub fn apply_setter<W, F>(busy: &W, error_context: &'static str, setter: F)
+where
+    W: IsA<gtk4::Widget>,
+    F: FnOnce() -> backend::Result<()> + Send + 'static,
+{
+    apply_setter_then(busy, error_context, setter, |()| {});
+}
+
+/// Like `apply_setter`, handing the setter's result to `on_success` on the
+/// main thread once it worked
+pub fn apply_setter_then<W, T, F, S>(
+    busy: &W,
+    error_context: &'static str,
+    setter: F,
+    on_success: S,
+) where
+    W: IsA<gtk4::Widget>,
+    T: Send + 'static,
+    F: FnOnce() -> backend::Result<T> + Send + 'static,
+    S: FnOnce(T) + 'static,
+{
+    apply_setter_then_else(busy, error_context, setter, on_success, || {});
+}
+
+/// Like `apply_setter_then`, calling `on_failure` after the error toast,
+/// e.g. to show the previous value again
+pub fn apply_setter_then_else<W, T, F, S, E>(
+    busy: &W,
+    error_context: &'static str,
+    setter: F,
+    on_success: S,
+    on_failure: E,
+) where
+    W: IsA<gtk4::Widget>,
+    T: Send + 'static,
+    F: FnOnce() -> backend::Result<T> + Send + 'static,
+    S: FnOnce(T) + 'static,
+    E: FnOnce() + 'static,
+{
+    let busy = busy.clone().upcast::<gtk4::Widget>();
+    busy.set_sensitive(false);
+
+    glib::spawn_future_local(async move {
+        let result = run_blocking(setter).await;
+        busy.set_sensitive(true);
+
+        match result {
+            Ok(value) => on_success(value),
+            Err(e) => {
+                show_error_toast(&busy, error_context, &e);
+                on_failure();
+            }
+        }
+    });
+}
+
+/// Like `apply_setter` for a switch, flipping it back if `setter` fails
+///
+/// The switch is restored under `guard`, so its handler doesn't write the
+/// old value again.
+pub fn apply_switch<F>(
+    switch: &adw::SwitchRow,
+    guard: &Rc<UpdateGuard>,
+    error_context: &'static str,
+    setter: F,
+) where
+    F: FnOnce(bool) -> backend::Result<()> + Send + 'static,
+{
+    apply_switch_then(switch, guard, error_context, setter, |_| {});
+}
+
+/// Like `apply_switch`, calling `on_success` with the applied state once it worked
+pub fn apply_switch_then<F, S>(
+    switch: &adw::SwitchRow,
+    guard: &Rc<UpdateGuard>,
+    error_context: &'static str,
+    setter: F,
+    on_success: S,
+) where
+    F: FnOnce(bool) -> backend::Result<()> + Send + 'static,
+    S: FnOnce(bool) + 'static,
+{
+    let active = switch.is_active();
+    let switch = switch.clone();
+    let guard = guard.clone();
+    switch.set_sensitive(false);
+
+    glib::spawn_future_local(async move {
+        let result = run_blocking(move || setter(active)).await;
+        switch.set_sensitive(true);
+
+        match result {
+            Ok(()) => on_success(active),
+            Err(e) => {
+                show_error_toast(&switch, error_context, &e);
+                let _guard = guard.hold();
+                switch.set_active(!active);
+            }
+        }
+    });
+}
+
+/// Run `setter` off the main thread without a busy state
+///
+/// Use this for continuous controls like scales, where making the widget
+/// insensitive would interrupt a drag.
+pub fn spawn_setter<W, F>(widget: &W, error_context: &'static str, setter: F)
+where
+    W: IsA<gtk4::Widget>,
+    F: FnOnce() -> backend::Result<()> + Send + 'static,
+{
+    spawn_setter_then(widget, error_context, setter, |()| {});
+}
+
+/// Like `spawn_setter`, handing the setter's result to `on_success` on the
+/// main thread once it worked
+pub fn spawn_setter_then<W, T, F, S>(
+    widget: &W,
+    error_context: &'static str,
+    setter: F,
+    on_success: S,
+) where
+    W: IsA<gtk4::Widget>,
+    T: Send + 'static,
+    F: FnOnce() -> backend::Result<T> + Send + 'static,
+    S: FnOnce(T) + 'static,
+{
+    let widget = widget.clone().upcast::<gtk4::Widget>();
+
+    glib::spawn_future_local(async move {
+        match run_blocking(setter).await {
+            Ok(value) => on_success(value),
+            Err(e) => show_error_toast(&widget, error_context, &e),
+        }
+    });
+}
+
+/// Like `spawn_setter`, for setters that may ask for authorization
+///
+/// A polkit prompt can stay open for as long as the user likes, so these run
+/// on their own thread instead of the backend queue, where they would hold
+/// up every other setter and page load.
+pub fn spawn_privileged_setter<W, F>(widget: &W, error_context: &'static str, setter: F)
+where
+    W: IsA<gtk4::Widget>,
+    F: FnOnce() -> backend::Result<()> + Send + 'static,
+{
+    let widget = widget.clone().upcast::<gtk4::Widget>();
+
+    glib::spawn_future_local(async move {
+        let result = gio::spawn_blocking(setter).await.unwrap_or_else(|_| {
+            Err(backend::AsusctlError::CommandFailed(
+                "backend call panicked".to_string(),
+            ))
+        });
+        if let Err(e) = result {
+            show_error_toast(&widget, error_context, &e);
+        }
+    });
+}
+
+/// Unwrap a backend read, hiding `widget` when the hardware lacks the feature
+///
+/// The enclosing row is hidden if there is one. Other errors are logged and
+/// leave the widget untouched, so a transient failure doesn't make controls
+/// disappear.
+pub fn read_or_hide<T>(
+    widget: &impl IsA<gtk4::Widget>,
+    context: &str,
+    result: backend::Result<T>,
+) -> Option<T> {
+    let target = widget
+        .ancestor(adw::PreferencesRow::static_type())
+        .unwrap_or_else(|| widget.clone().upcast());
+
+    match result {
+        Ok(value) => {
+            target.set_visible(true);
+            Some(value)
+        }
+        Err(backend::AsusctlError::Unsupported(_)) => {
+            target.set_visible(false);
+            None
+        }
+        Err(e) => {
+            log::warn!("{context}: {e}");
+            None
+        }
+    }
+}
+
+/// Where asusctl's installation instructions live
+pub const INSTALL_DOCS_URL: &str = "https://gitlab.com/asus-linux/asusctl";
+
+/// Most toasts shown or waiting at once; older ones are dropped beyond this
+const MAX_PENDING_TOASTS: usize = 3;
+
+thread_local! {
+    // Toasts added by `show_toast` that haven't been dismissed yet
+    static PENDING_TOASTS: RefCell<Vec<adw::Toast>> = const { RefCell::new(Vec::new()) };
+}
+
+/// Show a toast in the nearest toast overlay above `widget`
+///
+/// A message that is already pending isn't repeated, and only the newest
+/// few are kept, so a burst of failures doesn't queue up a long backlog.
+pub fn show_toast(widget: &impl IsA<gtk4::Widget>, message: &str) {
+    add_toast(widget, plain_toast(message));
+}
+
+/// Show a toast for a failed backend call
+///
+/// When asusctl is missing or asusd is stopped, the toast's button leads to
+/// the fix rather than only stating the error.
+pub fn show_error_toast(
+    widget: &impl IsA<gtk4::Widget>,
+    context: &str,
+    error: &backend::AsusctlError,
+) {
+    let toast = plain_toast(&format!("{context}: {error}"));
+
+    match error {
+        backend::AsusctlError::NotInstalled => {
+            toast.set_button_label(Some("Install Guide"));
+            let widget = widget.as_ref().downgrade();
+            toast.connect_button_clicked(move |_| {
+                if let Some(widget) = widget.upgrade() {
+                    open_install_docs(&widget);
+                }
+            });
+        }
+        backend::AsusctlError::ServiceNotRunning => {
+            toast.set_button_label(Some("Start asusd"));
+            toast.set_action_name(Some("win.start-service"));
+        }
+        _ => {}
+    }
+
+    add_toast(widget, toast);
+}
+
+/// Open the asusctl installation instructions in the browser
+pub fn open_install_docs(widget: &impl IsA<gtk4::Widget>) {
+    let parent = widget.root().and_downcast::<gtk4::Window>();
+    gtk4::UriLauncher::new(INSTALL_DOCS_URL).launch(
+        parent.as_ref(),
+        gio::Cancellable::NONE,
+        |result| {
+            if let Err(e) = result {
+                log::warn!("Failed to open {INSTALL_DOCS_URL}: {e}");
+            }
+        },
+    );
+}
+
+fn plain_toast(message: &str) -> adw::Toast {
+    // Error text may contain '<' or '&', so never parse it as markup
+    adw::Toast::builder()
+        .title(message)
+        .use_markup(false)
+        .build()
+}
+
+fn add_toast(widget: &impl IsA<gtk4::Widget>, toast: adw::Toast) {
+    let message = toast.title().unwrap_or_default();
+    let Some(overlay) = widget
+        .ancestor(adw::ToastOverlay::static_type())
+        .and_downcast::<adw::ToastOverlay>()
+    else {
+        log::warn!("{message}");
+        return;
+    };
+
+    let already_pending = PENDING_TOASTS.with_borrow(|pending| {
+        pending
+            .iter()
+            .any(|toast| toast.title().is_some_and(|title| title == message))
+    });
+    if already_pending {
+        return;
+    }
+
+    toast.connect_dismissed(|toast| {
+        PENDING_TOASTS.with_borrow_mut(|pending| pending.retain(|t| t != toast));
+    });
+
+    let oldest = PENDING_TOASTS.with_borrow_mut(|pending| {
+        pending.push(toast.clone());
+        (pending.len() > MAX_PENDING_TOASTS).then(|| pending.remove(0))
+    });
+    // Dismissing re-enters the handler above, so do it outside the borrow
+    if let Some(oldest) = oldest {
+        oldest.dismiss();
+    }
+
+    overlay.add_toast(toast);
+}
+
+async fn run_blocking<T, F>(f: F) -> backend::Result<T>
+where
+    T: Send + 'static,
+    F: FnOnce() -> backend::Result<T> + Send + 'static,
+{
+    backend::queue::submit(f).await.unwrap_or_else(|_| {
+        Err(backend::AsusctlError::CommandFailed(
+            "backend call panicked".to_string(),
+        ))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_known_value_flags_only_external_changes() {
+        let known = KnownValue::default();
+
+        // First read has nothing to compare against
+        assert!(!known.update(80));
+        assert!(!known.update(80));
+
+        // The user's own change is not external
+        known.set(60);
+        assert!(!known.update(60));
+
+        assert!(known.update(100));
+    }
+
+    #[test]
+    fn test_applied_value_diverges() {
+        let applied = AppliedValue::default();
+
+        // Nothing applied yet, so any hardware value is fine
+        assert!(!applied.diverges(80));
+
+        applied.set(60);
+        assert!(!applied.diverges(60));
+        assert!(applied.diverges(80));
+    }
+
+    #[test]
+    fn test_update_guard_nests() {
+        let guard = UpdateGuard::default();
+        assert!(!guard.is_active());
+
+        {
+            let _outer = guard.hold();
+            {
+                let _inner = guard.hold();
+                assert!(guard.is_active());
+            }
+            // Releasing the inner hold keeps the outer one raised
+            assert!(guard.is_active());
+        }
+
+        assert!(!guard.is_active());
+    }
+}
@@ -1,9 +1,13 @@
 mod about;
+mod anime;
 mod aura;
+mod fan_curves;
 mod power;
 mod slash;
 
 pub use about::AboutPage;
+pub use anime::AnimePage;
 pub use aura::AuraPage;
+pub use fan_curves::FanCurvesPage;
 pub use power::PowerPage;
 pub use slash::SlashPage;
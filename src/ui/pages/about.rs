@@ -1,12 +1,15 @@
 use adw::prelude::*;
+use gtk4::gio;
 use gtk4::glib;
 use gtk4::prelude::*;
 use gtk4::subclass::prelude::*;
 use libadwaita as adw;
-use std::cell::RefCell;
+use std::cell::{Cell, RefCell};
+use std::rc::Rc;
 
-use crate::backend;
-use crate::ui::Refreshable;
+use crate::backend::{self, ServiceState};
+use crate::ui::bind::{self, PageLoader};
+use crate::ui::{APP_VERSION, Refreshable};
 
 mod imp {
     use super::*;
@@ -16,6 +19,11 @@ mod imp {
         pub model_row: RefCell<Option<adw::ActionRow>>,
         pub driver_row: RefCell<Option<adw::ActionRow>>,
         pub asusctl_row: RefCell<Option<adw::ActionRow>>,
+        pub service_row: RefCell<Option<adw::ActionRow>>,
+        pub service_button: RefCell<Option<gtk4::Button>>,
+        /// Groups showing a spinner until the first refresh finishes
+        pub loading_groups: RefCell<Vec<adw::PreferencesGroup>>,
+        pub loader: Rc<PageLoader>,
     }
 
     #[glib::object_subclass]
@@ -72,59 +80,212 @@ impl AboutPage {
             .title("Laptop Information")
             .build();
 
-        let model_row = adw::ActionRow::builder()
-            .title("Model")
-            .subtitle("Loading...")
-            .build();
+        let model_row = adw::ActionRow::builder().title("Model").build();
 
-        let driver_row = adw::ActionRow::builder()
-            .title("Board Name")
-            .subtitle("Loading...")
-            .build();
+        let driver_row = adw::ActionRow::builder().title("Board Name").build();
 
-        let asusctl_row = adw::ActionRow::builder()
-            .title("asusctl Version")
-            .subtitle("Loading...")
-            .build();
+        let asusctl_row = adw::ActionRow::builder().title("asusctl Version").build();
 
         laptop_group.add(&model_row);
         laptop_group.add(&driver_row);
         laptop_group.add(&asusctl_row);
 
+        // Known at build time, so not part of the refresh
+        let app_row = adw::ActionRow::builder()
+            .title("asusctl-gui Version")
+            .subtitle(format!("v{APP_VERSION}"))
+            .build();
+        laptop_group.add(&app_row);
+
         // Store references
         imp.model_row.replace(Some(model_row));
         imp.driver_row.replace(Some(driver_row));
         imp.asusctl_row.replace(Some(asusctl_row));
 
+        show_spinner(&laptop_group);
         self.append(&laptop_group);
 
+        // Service group
+        let service_group = adw::PreferencesGroup::builder().title("Service").build();
+
+        let service_row = adw::ActionRow::builder().title("asusd").build();
+
+        // Action is chosen in refresh_data based on the service state
+        let service_button = gtk4::Button::builder()
+            .valign(gtk4::Align::Center)
+            .visible(false)
+            .build();
+
+        service_row.add_suffix(&service_button);
+        service_group.add(&service_row);
+
+        imp.service_row.replace(Some(service_row));
+        imp.service_button.replace(Some(service_button));
+
+        show_spinner(&service_group);
+        self.append(&service_group);
+        imp.loading_groups
+            .replace(vec![laptop_group, service_group]);
+
         // Supported features group (loaded once, static data)
         let features_group = adw::PreferencesGroup::builder()
             .title("Supported Features")
             .build();
 
-        match backend::get_supported_features() {
-            Ok(features) => {
-                Self::populate_features(&features_group, &features);
+        show_spinner(&features_group);
+        let group = features_group.clone();
+        bind::load_then(backend::get_supported_features, move |result| {
+            hide_spinner(&group);
+            match result {
+                Ok(features) => {
+                    Self::populate_features(&group, &features);
+                }
+                Err(e) => {
+                    let error_row = adw::ActionRow::builder()
+                        .title("Error loading features")
+                        .subtitle(e.to_string())
+                        .build();
+                    group.add(&error_row);
+                }
+            }
+        });
+
+        self.append(&features_group);
+
+        // Graphics group (loaded once, static data)
+        let graphics_group = adw::PreferencesGroup::builder().title("Graphics").build();
+        show_spinner(&graphics_group);
+        let group = graphics_group.clone();
+        bind::load_then(
+            || (backend::get_gpus(), backend::get_gpu_mux_mode_dbus()),
+            move |(gpus, mux_mode)| {
+                hide_spinner(&group);
+                Self::populate_graphics(&group, gpus, mux_mode);
+            },
+        );
+        self.append(&graphics_group);
+
+        // D-Bus objects group (developer mode, loaded when first shown)
+        let objects_group = adw::PreferencesGroup::builder()
+            .title("D-Bus Objects")
+            .description("Paths and interfaces exported by asusd")
+            .build();
+        gio::Settings::new("com.github.bl4ckspell7.asusctl-gui")
+            .bind("developer-mode", &objects_group, "visible")
+            .get()
+            .build();
+
+        let loaded = Cell::new(false);
+        objects_group.connect_map(move |group| {
+            if !loaded.replace(true) {
+                Self::populate_objects(group);
+            }
+        });
+
+        self.append(&objects_group);
+    }
+
+    /// Fill the group with asusd's objects and their interfaces
+    ///
+    /// Walking the object tree takes one D-Bus round trip per object, so it
+    /// runs on its own thread instead of holding up the backend queue.
+    fn populate_objects(group: &adw::PreferencesGroup) {
+        let group = group.clone();
+        show_spinner(&group);
+
+        glib::spawn_future_local(async move {
+            let result = gio::spawn_blocking(backend::list_asusd_objects)
+                .await
+                .unwrap_or_else(|_| {
+                    Err(backend::AsusctlError::CommandFailed(
+                        "listing objects panicked".to_string(),
+                    ))
+                });
+            hide_spinner(&group);
+            match result {
+                Ok(objects) => {
+                    for object in objects {
+                        let row = adw::ExpanderRow::builder()
+                            .title(&object.path)
+                            .subtitle(format!("{} interfaces", object.interfaces.len()))
+                            .build();
+                        for interface in &object.interfaces {
+                            row.add_row(&adw::ActionRow::builder().title(interface).build());
+                        }
+                        group.add(&row);
+                    }
+                }
+                Err(e) => {
+                    let row = adw::ActionRow::builder()
+                        .title("Error listing objects")
+                        .subtitle(e.to_string())
+                        .build();
+                    group.add(&row);
+                }
+            }
+        });
+    }
+
+    fn populate_graphics(
+        group: &adw::PreferencesGroup,
+        gpus: backend::Result<Vec<backend::GpuInfo>>,
+        mux_mode: backend::Result<backend::GpuMuxMode>,
+    ) {
+        match gpus {
+            Ok(gpus) if !gpus.is_empty() => {
+                for gpu in gpus {
+                    let row = adw::ActionRow::builder()
+                        .title(&gpu.name)
+                        .subtitle(&gpu.slot)
+                        .build();
+                    group.add(&row);
+                }
+            }
+            Ok(_) => {
+                let row = adw::ActionRow::builder()
+                    .title("GPU")
+                    .subtitle("Unknown")
+                    .build();
+                group.add(&row);
             }
             Err(e) => {
-                let error_row = adw::ActionRow::builder()
-                    .title("Error loading features")
-                    .subtitle(&e.to_string())
+                let row = adw::ActionRow::builder()
+                    .title("Error loading GPUs")
+                    .subtitle(e.to_string())
                     .build();
-                features_group.add(&error_row);
+                group.add(&row);
             }
         }
 
-        self.append(&features_group);
+        // Only laptops with a MUX switch expose this property
+        if let Ok(mode) = mux_mode {
+            let row = adw::ActionRow::builder()
+                .title("MUX Mode")
+                .subtitle(mode.to_string())
+                .build();
+            group.add(&row);
+        }
     }
 
     /// Refresh/reload all data on this page
     fn refresh_data(&self) {
+        self.imp().loader.load(
+            self,
+            || (backend::get_system_info(), backend::get_service_state()),
+            |page, (info, state)| {
+                for group in page.imp().loading_groups.take() {
+                    hide_spinner(&group);
+                }
+                page.show_system_info(info);
+                page.show_service_state(state);
+            },
+        );
+    }
+
+    fn show_system_info(&self, info: backend::Result<backend::SystemInfo>) {
         let imp = self.imp();
 
-        // Load system info
-        match backend::get_system_info() {
+        match info {
             Ok(info) => {
                 if let Some(row) = imp.model_row.borrow().as_ref() {
                     row.set_subtitle(&info.product_family);
@@ -136,21 +297,54 @@ impl AboutPage {
                     row.set_subtitle(&format!("v{}", info.asusctl_version));
                 }
             }
+            // The window's service banner carries the error itself
             Err(e) => {
-                let error_msg = e.to_string();
-                if let Some(row) = imp.model_row.borrow().as_ref() {
-                    row.set_subtitle(&error_msg);
-                }
-                if let Some(row) = imp.driver_row.borrow().as_ref() {
-                    row.set_subtitle(&error_msg);
-                }
-                if let Some(row) = imp.asusctl_row.borrow().as_ref() {
-                    row.set_subtitle(&error_msg);
+                log::warn!("Failed to get system info: {e}");
+                for row in [&imp.model_row, &imp.driver_row, &imp.asusctl_row] {
+                    if let Some(row) = row.borrow().as_ref() {
+                        row.set_subtitle("Unavailable");
+                    }
                 }
             }
         }
     }
 
+    fn show_service_state(&self, state: backend::Result<ServiceState>) {
+        let imp = self.imp();
+        let (Some(row), Some(button)) = (
+            imp.service_row.borrow().clone(),
+            imp.service_button.borrow().clone(),
+        ) else {
+            return;
+        };
+
+        let state = match state {
+            Ok(state) => state,
+            Err(e) => {
+                row.set_subtitle(&e.to_string());
+                button.set_visible(false);
+                return;
+            }
+        };
+
+        row.set_subtitle(&state.to_string());
+
+        let action = match state {
+            ServiceState::Running => Some(("Restart", "win.restart-service")),
+            ServiceState::Stopped | ServiceState::Failed => Some(("Start", "win.start-service")),
+            ServiceState::Changing | ServiceState::NotInstalled => None,
+        };
+
+        match action {
+            Some((label, action_name)) => {
+                button.set_label(label);
+                button.set_action_name(Some(action_name));
+                button.set_visible(true);
+            }
+            None => button.set_visible(false),
+        }
+    }
+
     fn populate_features(group: &adw::PreferencesGroup, features: &backend::SupportedFeatures) {
         // Core features
         let core_features = [
@@ -158,6 +352,7 @@ impl AboutPage {
             ("Platform Control", features.has_platform),
             ("Fan Curves", features.has_fan_curves),
             ("Slash (LED Bar)", features.has_slash),
+            ("AniMe Matrix", features.has_anime),
         ];
 
         for (name, supported) in core_features {
@@ -207,16 +402,14 @@ impl AboutPage {
         }
 
         // Keyboard brightness levels
-        if !features.keyboard_brightness_levels.is_empty() {
-            let levels: Vec<String> = features
-                .keyboard_brightness_levels
-                .iter()
-                .map(|l| format!("{l}"))
-                .collect();
+        if let Some(levels) = &features.keyboard_brightness_levels
+            && !levels.is_empty()
+        {
+            let levels: Vec<String> = levels.iter().map(|l| format!("{l}")).collect();
 
             let row = adw::ActionRow::builder()
                 .title("Keyboard Brightness Levels")
-                .subtitle(&levels.join(", "))
+                .subtitle(levels.join(", "))
                 .build();
 
             group.add(&row);
@@ -228,7 +421,7 @@ impl AboutPage {
 
             let row = adw::ActionRow::builder()
                 .title("Aura Modes")
-                .subtitle(&modes.join(", "))
+                .subtitle(modes.join(", "))
                 .build();
 
             group.add(&row);
@@ -246,4 +439,17 @@ impl Refreshable for AboutPage {
     fn refresh(&self) {
         self.refresh_data();
     }
+
+    fn loader(&self) -> Rc<PageLoader> {
+        self.imp().loader.clone()
+    }
+}
+
+/// Mark `group` as loading until `hide_spinner` is called
+fn show_spinner(group: &adw::PreferencesGroup) {
+    group.set_header_suffix(Some(&adw::Spinner::new()));
+}
+
+fn hide_spinner(group: &adw::PreferencesGroup) {
+    group.set_header_suffix(None::<&gtk4::Widget>);
 }
@@ -0,0 +1,284 @@
+use adw::prelude::*;
+use gtk4::glib;
+use gtk4::prelude::*;
+use gtk4::subclass::prelude::*;
+use libadwaita as adw;
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use crate::backend::{self, AnimeBrightness, AnimeBuiltins, AnimeEvent};
+use crate::ui::Refreshable;
+use crate::ui::bind::{self, PageLoader, UpdateGuard};
+
+mod imp {
+    use super::*;
+
+    #[derive(Debug, Default)]
+    pub struct AnimePage {
+        pub enable_switch: RefCell<Option<adw::SwitchRow>>,
+        pub brightness_combo: RefCell<Option<adw::ComboRow>>,
+        pub builtins_switch: RefCell<Option<adw::SwitchRow>>,
+        /// One combo per event, in `AnimeEvent::ALL` order
+        pub builtin_combos: RefCell<Vec<adw::ComboRow>>,
+        /// Raised while `apply_state` writes hardware state into the widgets
+        pub updating: Rc<UpdateGuard>,
+        pub loader: Rc<PageLoader>,
+    }
+
+    #[glib::object_subclass]
+    impl ObjectSubclass for AnimePage {
+        const NAME: &'static str = "AnimePage";
+        type Type = super::AnimePage;
+        type ParentType = gtk4::Box;
+    }
+
+    impl ObjectImpl for AnimePage {
+        fn constructed(&self) {
+            self.parent_constructed();
+            self.obj().setup_ui();
+            self.obj().refresh_data();
+        }
+    }
+
+    impl WidgetImpl for AnimePage {}
+    impl BoxImpl for AnimePage {}
+}
+
+/// Everything the page shows, read off the main thread
+struct AnimeState {
+    enabled: backend::Result<bool>,
+    brightness: backend::Result<AnimeBrightness>,
+    builtins_enabled: backend::Result<bool>,
+    builtins: backend::Result<AnimeBuiltins>,
+}
+
+impl AnimeState {
+    fn read() -> Self {
+        Self {
+            enabled: backend::get_anime_enabled(),
+            brightness: backend::get_anime_brightness(),
+            builtins_enabled: backend::get_anime_builtins_enabled(),
+            builtins: backend::get_anime_builtins(),
+        }
+    }
+}
+
+glib::wrapper! {
+    pub struct AnimePage(ObjectSubclass<imp::AnimePage>)
+        @extends gtk4::Box, gtk4::Widget,
+        @implements gtk4::Accessible, gtk4::Buildable, gtk4::ConstraintTarget, gtk4::Orientable;
+}
+
+impl AnimePage {
+    pub fn new() -> Self {
+        glib::Object::builder()
+            .property("orientation", gtk4::Orientation::Vertical)
+            .property("spacing", 24)
+            .property("margin-top", 24)
+            .property("margin-bottom", 24)
+            .property("margin-start", 24)
+            .property("margin-end", 24)
+            .build()
+    }
+
+    fn setup_ui(&self) {
+        let imp = self.imp();
+
+        // Page title
+        let title = gtk4::Label::builder()
+            .label("AniMe Matrix")
+            .css_classes(["title-1"])
+            .halign(gtk4::Align::Start)
+            .build();
+
+        self.append(&title);
+
+        let description = gtk4::Label::builder()
+            .label("Control the LED matrix on the laptop lid")
+            .css_classes(["dim-label"])
+            .halign(gtk4::Align::Start)
+            .build();
+
+        self.append(&description);
+
+        // Display group
+        let display_group = adw::PreferencesGroup::builder().title("Display").build();
+
+        let enable_row = adw::SwitchRow::builder()
+            .title("Enable Display")
+            .subtitle("Turn the LED matrix on or off")
+            .build();
+
+        let guard = imp.updating.clone();
+        enable_row.connect_active_notify(move |switch| {
+            if guard.is_active() {
+                return;
+            }
+            let active = switch.is_active();
+            bind::apply_setter(switch, "Failed to toggle AniMe display", move || {
+                backend::set_anime_enabled(active)
+            });
+        });
+
+        imp.enable_switch.replace(Some(enable_row.clone()));
+        display_group.add(&enable_row);
+
+        let brightness_names: Vec<String> =
+            AnimeBrightness::ALL.iter().map(|b| b.to_string()).collect();
+        let brightness_names: Vec<&str> = brightness_names.iter().map(String::as_str).collect();
+        let brightness_combo = adw::ComboRow::builder()
+            .title("Brightness")
+            .model(&gtk4::StringList::new(&brightness_names))
+            .build();
+
+        let guard = imp.updating.clone();
+        brightness_combo.connect_selected_notify(move |combo| {
+            if guard.is_active() {
+                return;
+            }
+            let Some(&brightness) = AnimeBrightness::ALL.get(combo.selected() as usize) else {
+                return;
+            };
+            bind::apply_setter(combo, "Failed to set AniMe brightness", move || {
+                backend::set_anime_brightness(brightness)
+            });
+        });
+
+        imp.brightness_combo.replace(Some(brightness_combo.clone()));
+        display_group.add(&brightness_combo);
+        self.append(&display_group);
+
+        // Built-in animations group
+        let builtins_group = adw::PreferencesGroup::builder()
+            .title("Built-in Animations")
+            .description("Animations asusd plays on system events")
+            .build();
+
+        let builtins_row = adw::SwitchRow::builder()
+            .title("Play Built-in Animations")
+            .build();
+
+        let guard = imp.updating.clone();
+        builtins_row.connect_active_notify(move |switch| {
+            if guard.is_active() {
+                return;
+            }
+            let active = switch.is_active();
+            bind::apply_setter(switch, "Failed to toggle built-in animations", move || {
+                backend::set_anime_builtins_enabled(active)
+            });
+        });
+
+        imp.builtins_switch.replace(Some(builtins_row.clone()));
+        builtins_group.add(&builtins_row);
+
+        let mut combos = Vec::new();
+        for event in AnimeEvent::ALL {
+            let combo = adw::ComboRow::builder()
+                .title(event.to_string())
+                .model(&gtk4::StringList::new(&event.animations()))
+                .build();
+
+            // asusd takes all four choices at once
+            let guard = imp.updating.clone();
+            let page_weak = self.downgrade();
+            combo.connect_selected_notify(move |combo| {
+                if guard.is_active() {
+                    return;
+                }
+                let Some(page) = page_weak.upgrade() else {
+                    return;
+                };
+                let builtins = page.selected_builtins();
+                bind::apply_setter(combo, "Failed to set built-in animation", move || {
+                    backend::set_anime_builtins(builtins)
+                });
+            });
+
+            builtins_group.add(&combo);
+            combos.push(combo);
+        }
+
+        imp.builtin_combos.replace(combos);
+        self.append(&builtins_group);
+    }
+
+    /// Animation choices as currently shown in the combos
+    fn selected_builtins(&self) -> AnimeBuiltins {
+        let mut builtins = AnimeBuiltins::default();
+        for (slot, combo) in builtins
+            .iter_mut()
+            .zip(self.imp().builtin_combos.borrow().iter())
+        {
+            *slot = combo.selected() as u8;
+        }
+        builtins
+    }
+
+    /// Refresh/reload all data on this page
+    fn refresh_data(&self) {
+        self.imp()
+            .loader
+            .load(self, AnimeState::read, |page, state| {
+                page.apply_state(state)
+            });
+    }
+
+    fn apply_state(&self, state: AnimeState) {
+        let imp = self.imp();
+        let _guard = imp.updating.hold();
+
+        if let Some(switch) = imp.enable_switch.borrow().as_ref()
+            && let Some(enabled) =
+                bind::read_or_hide(switch, "Failed to get AniMe display state", state.enabled)
+        {
+            switch.set_active(enabled);
+        }
+
+        if let Some(combo) = imp.brightness_combo.borrow().as_ref()
+            && let Some(brightness) =
+                bind::read_or_hide(combo, "Failed to get AniMe brightness", state.brightness)
+            && let Some(index) = AnimeBrightness::ALL.iter().position(|b| *b == brightness)
+        {
+            combo.set_selected(index as u32);
+        }
+
+        if let Some(switch) = imp.builtins_switch.borrow().as_ref()
+            && let Some(enabled) = bind::read_or_hide(
+                switch,
+                "Failed to get built-in animation state",
+                state.builtins_enabled,
+            )
+        {
+            switch.set_active(enabled);
+        }
+
+        let combos = imp.builtin_combos.borrow();
+        if let Some(group) = combos
+            .first()
+            .and_then(|combo| combo.ancestor(adw::PreferencesGroup::static_type()))
+            && let Some(builtins) =
+                bind::read_or_hide(&group, "Failed to get built-in animations", state.builtins)
+        {
+            for (combo, index) in combos.iter().zip(builtins) {
+                combo.set_selected(index as u32);
+            }
+        }
+    }
+}
+
+impl Default for AnimePage {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Refreshable for AnimePage {
+    fn refresh(&self) {
+        self.refresh_data();
+    }
+
+    fn loader(&self) -> Rc<PageLoader> {
+        self.imp().loader.clone()
+    }
+}
@@ -1,12 +1,18 @@
 use adw::prelude::*;
+use gtk4::gio;
 use gtk4::glib;
 use gtk4::prelude::*;
 use gtk4::subclass::prelude::*;
 use libadwaita as adw;
-use std::cell::RefCell;
+use std::cell::{Cell, RefCell};
+use std::rc::Rc;
+use std::time::Duration;
 
-use crate::backend::{self, KeyboardBrightness};
+use crate::backend::{
+    self, AuraConfig, AuraDirection, AuraEffect, AuraMode, AuraSpeed, AuraZone, KeyboardBrightness,
+};
 use crate::ui::Refreshable;
+use crate::ui::bind::{self, Debouncer, KnownValue, PageLoader, UpdateGuard};
 
 mod imp {
     use super::*;
@@ -14,6 +20,31 @@ mod imp {
     #[derive(Debug, Default)]
     pub struct AuraPage {
         pub brightness_buttons: RefCell<Vec<gtk4::ToggleButton>>,
+        /// Alternative to the buttons, shown with the brightness-as-slider setting
+        pub brightness_scale: RefCell<Option<gtk4::Scale>>,
+        pub brightness_value: RefCell<Option<gtk4::Label>>,
+        /// Levels the keyboard accepts; empty until asusctl has listed them
+        pub brightness_levels: RefCell<Vec<KeyboardBrightness>>,
+        pub known_brightness: Rc<KnownValue<KeyboardBrightness>>,
+        /// Mode rows with their checkmarks
+        pub mode_rows: RefCell<Vec<(AuraMode, adw::ActionRow, gtk4::Image)>>,
+        pub known_mode: Rc<KnownValue<AuraMode>>,
+        /// Mode last read or set, applied again when the effect changes
+        pub active_mode: Cell<Option<AuraMode>>,
+        /// Effect speed and direction, shown for the modes that use them
+        pub speed_combo: RefCell<Option<adw::ComboRow>>,
+        pub direction_combo: RefCell<Option<adw::ComboRow>>,
+        /// Set once modes and zones are adjusted to the supported features
+        pub features_checked: Cell<bool>,
+        pub color_button: RefCell<Option<gtk4::ColorDialogButton>>,
+        pub colour_commit: Rc<Debouncer>,
+        pub color_group: RefCell<Option<adw::PreferencesGroup>>,
+        /// Per-zone colour buttons, only on keyboards with several zones
+        pub zone_buttons: RefCell<Vec<(AuraZone, gtk4::ColorDialogButton)>>,
+        pub sleep_switch: RefCell<Option<adw::SwitchRow>>,
+        /// Raised while `refresh_data` writes hardware state into the widgets
+        pub updating: Rc<UpdateGuard>,
+        pub loader: Rc<PageLoader>,
     }
 
     #[glib::object_subclass]
@@ -41,6 +72,67 @@ glib::wrapper! {
         @implements gtk4::Accessible, gtk4::Buildable, gtk4::ConstraintTarget, gtk4::Orientable;
 }
 
+/// Everything the page shows, read off the main thread
+struct AuraState {
+    brightness: backend::Result<KeyboardBrightness>,
+    colour: backend::Result<[u8; 3]>,
+    mode: backend::Result<Option<AuraMode>>,
+    /// Whether the keyboard stays lit while asleep
+    lit_on_sleep: backend::Result<bool>,
+    /// Only read until asusctl answered once
+    features: Option<backend::Result<backend::SupportedFeatures>>,
+}
+
+impl AuraState {
+    fn read(check_features: bool) -> Self {
+        Self {
+            brightness: backend::get_keyboard_brightness(),
+            colour: backend::get_aura_colour_dbus(),
+            mode: backend::get_aura_mode_dbus(),
+            lit_on_sleep: backend::get_keyboard_lit_on_sleep(),
+            features: check_features.then(backend::get_supported_features),
+        }
+    }
+}
+
+/// Effect speeds with their combo labels
+const SPEEDS: [(AuraSpeed, &str); 3] = [
+    (AuraSpeed::Low, "Slow"),
+    (AuraSpeed::Med, "Medium"),
+    (AuraSpeed::High, "Fast"),
+];
+
+/// Wave directions with their combo labels
+const DIRECTIONS: [(AuraDirection, &str); 4] = [
+    (AuraDirection::Right, "Right"),
+    (AuraDirection::Left, "Left"),
+    (AuraDirection::Up, "Up"),
+    (AuraDirection::Down, "Down"),
+];
+
+/// Quiet time after the last colour change before it is written
+const COLOUR_DEBOUNCE: Duration = Duration::from_millis(300);
+
+/// RGB bytes as an opaque colour for a color button
+fn colour_rgba([r, g, b]: [u8; 3]) -> gtk4::gdk::RGBA {
+    gtk4::gdk::RGBA::new(r as f32 / 255.0, g as f32 / 255.0, b as f32 / 255.0, 1.0)
+}
+
+/// Colour shown by `button`, as RGB bytes
+fn button_colour(button: &gtk4::ColorDialogButton) -> [u8; 3] {
+    let rgba = button.rgba();
+    [rgba.red(), rgba.green(), rgba.blue()].map(|c| (c.clamp(0.0, 1.0) * 255.0).round() as u8)
+}
+
+/// Set the keyboard brightness, and the Slash bar with it when linked
+fn set_brightness(level: KeyboardBrightness, link_slash: bool) -> backend::Result<()> {
+    backend::set_keyboard_brightness(level)?;
+    if link_slash {
+        backend::sync_slash_to_keyboard(level)?;
+    }
+    Ok(())
+}
+
 impl AuraPage {
     pub fn new() -> Self {
         glib::Object::builder()
@@ -61,9 +153,32 @@ impl AuraPage {
             .label("Aura Lighting")
             .css_classes(["title-1"])
             .halign(gtk4::Align::Start)
+            .hexpand(true)
+            .build();
+
+        // Page menu
+        let menu = gio::Menu::new();
+        menu.append(
+            Some("Restore Default Lighting"),
+            Some("aura.restore-default"),
+        );
+
+        let menu_button = gtk4::MenuButton::builder()
+            .icon_name("view-more-symbolic")
+            .menu_model(&menu)
+            .tooltip_text("Lighting Options")
+            .valign(gtk4::Align::Center)
+            .css_classes(["flat"])
             .build();
 
-        self.append(&title);
+        let header = gtk4::Box::builder()
+            .orientation(gtk4::Orientation::Horizontal)
+            .build();
+        header.append(&title);
+        header.append(&menu_button);
+
+        self.append(&header);
+        self.setup_actions();
 
         // Keyboard brightness group
         let brightness_group = adw::PreferencesGroup::builder()
@@ -79,25 +194,29 @@ impl AuraPage {
             .valign(gtk4::Align::Center)
             .build();
 
-        let levels = [
-            (KeyboardBrightness::Off, "Off"),
-            (KeyboardBrightness::Low, "Low"),
-            (KeyboardBrightness::Med, "Med"),
-            (KeyboardBrightness::High, "High"),
-        ];
-
+        let settings = gio::Settings::new("com.github.bl4ckspell7.asusctl-gui");
         let mut buttons: Vec<gtk4::ToggleButton> = Vec::new();
 
-        for (level, label) in levels {
-            let btn = gtk4::ToggleButton::builder().label(label).build();
+        for level in KeyboardBrightness::ALL {
+            let btn = gtk4::ToggleButton::builder().label(level.label()).build();
 
             // Connect click handler to set brightness
-            let level_clone = level;
-            btn.connect_clicked(move |button| {
+            let brightness_box_clone = brightness_box.clone();
+            let settings_clone = settings.clone();
+            let known_brightness = imp.known_brightness.clone();
+            let guard = imp.updating.clone();
+            btn.connect_toggled(move |button| {
+                if guard.is_active() {
+                    return;
+                }
                 if button.is_active() {
-                    if let Err(e) = backend::set_keyboard_brightness(level_clone) {
-                        eprintln!("Failed to set brightness: {e}");
-                    }
+                    known_brightness.set(level);
+                    let link_slash = settings_clone.boolean("link-keyboard-slash");
+                    bind::apply_setter(
+                        &brightness_box_clone,
+                        "Failed to set brightness",
+                        move || set_brightness(level, link_slash),
+                    );
                 }
             });
 
@@ -112,7 +231,98 @@ impl AuraPage {
 
         imp.brightness_buttons.replace(buttons);
 
+        let brightness_scale = gtk4::Scale::builder()
+            .orientation(gtk4::Orientation::Horizontal)
+            .adjustment(&gtk4::Adjustment::new(0.0, 0.0, 3.0, 1.0, 1.0, 0.0))
+            .round_digits(0)
+            .width_request(200)
+            .valign(gtk4::Align::Center)
+            .build();
+        for level in KeyboardBrightness::ALL {
+            brightness_scale.add_mark(
+                level.as_u8() as f64,
+                gtk4::PositionType::Bottom,
+                Some(level.label()),
+            );
+        }
+
+        let settings_clone = settings.clone();
+        let known_brightness = imp.known_brightness.clone();
+        let guard = imp.updating.clone();
+        let page_weak = self.downgrade();
+        brightness_scale.connect_value_changed(move |scale| {
+            if guard.is_active() {
+                return;
+            }
+            let Ok(level) = KeyboardBrightness::from_u8(scale.value().round() as u8) else {
+                return;
+            };
+            // Snap to the nearest level the keyboard accepts; that move sends it
+            if let Some(page) = page_weak.upgrade()
+                && let Some(supported) = page.nearest_supported_brightness(level)
+                && supported != level
+            {
+                scale.set_value(supported.as_u8() as f64);
+                return;
+            }
+            known_brightness.set(level);
+            let link_slash = settings_clone.boolean("link-keyboard-slash");
+            bind::spawn_setter(scale, "Failed to set brightness", move || {
+                set_brightness(level, link_slash)
+            });
+        });
+
+        // Only one of the two controls is shown, following the preference live
+        settings
+            .bind("brightness-as-slider", &brightness_scale, "visible")
+            .get()
+            .build();
+        settings
+            .bind("brightness-as-slider", &brightness_box, "visible")
+            .get()
+            .invert_boolean()
+            .build();
+
+        imp.brightness_scale.replace(Some(brightness_scale.clone()));
+
+        // Raw 0-3 level as reported by asusd, shown in developer mode
+        let brightness_value = gtk4::Label::builder()
+            .css_classes(["dim-label", "numeric"])
+            .valign(gtk4::Align::Center)
+            .build();
+        settings
+            .bind("developer-mode", &brightness_value, "visible")
+            .get()
+            .build();
+
+        imp.brightness_value.replace(Some(brightness_value.clone()));
+
+        // One level at a time, like the Fn keys
+        let [dimmer_button, brighter_button] = [
+            ("list-remove-symbolic", "Dimmer", false),
+            ("list-add-symbolic", "Brighter", true),
+        ]
+        .map(|(icon, tooltip, brighter)| {
+            let button = gtk4::Button::builder()
+                .icon_name(icon)
+                .tooltip_text(tooltip)
+                .valign(gtk4::Align::Center)
+                .css_classes(["flat"])
+                .build();
+            let page_weak = self.downgrade();
+            button.connect_clicked(move |button| {
+                if let Some(page) = page_weak.upgrade() {
+                    page.step_brightness(button, brighter);
+                }
+            });
+            button
+        });
+
+        brightness_row.add_suffix(&brightness_value);
+        brightness_row.add_suffix(&dimmer_button);
         brightness_row.add_suffix(&brightness_box);
+        brightness_row.add_suffix(&brightness_scale);
+        brightness_row.add_suffix(&brighter_button);
         brightness_group.add(&brightness_row);
 
         self.append(&brightness_group);
@@ -123,14 +333,17 @@ impl AuraPage {
             .build();
 
         let modes = [
-            ("Static", "Single color"),
-            ("Breathe", "Pulsing effect"),
-            ("Pulse", "Rapid pulse"),
+            (AuraMode::Static, "Single color"),
+            (AuraMode::Breathe, "Pulsing effect"),
+            (AuraMode::Pulse, "Rapid pulse"),
+            (AuraMode::RainbowWave, "Colors sweeping across the keyboard"),
         ];
 
+        let mut mode_rows = Vec::new();
+
         for (mode, description) in modes {
             let row = adw::ActionRow::builder()
-                .title(mode)
+                .title(mode.to_string())
                 .subtitle(description)
                 .activatable(true)
                 .build();
@@ -139,9 +352,74 @@ impl AuraPage {
             checkmark.set_visible(false);
             row.add_suffix(&checkmark);
 
+            let page_weak = self.downgrade();
+            let mode_group_clone = mode_group.clone();
+            let known_mode = imp.known_mode.clone();
+            row.connect_activated(move |_| {
+                let Some(page) = page_weak.upgrade() else {
+                    return;
+                };
+                let colour = page.selected_colour();
+                let effect = page.selected_effect();
+
+                let page_weak = page.downgrade();
+                let known_mode = known_mode.clone();
+                bind::apply_setter_then(
+                    &mode_group_clone,
+                    "Failed to set lighting mode",
+                    move || backend::set_aura_mode(mode, colour, effect),
+                    move |()| {
+                        known_mode.set(mode);
+                        if let Some(page) = page_weak.upgrade() {
+                            page.show_active_mode(mode);
+                        }
+                    },
+                );
+            });
+
             mode_group.add(&row);
+            mode_rows.push((mode, row, checkmark));
+        }
+
+        imp.mode_rows.replace(mode_rows);
+
+        // Revealed by show_active_mode for the modes that use them
+        let speed_labels = SPEEDS.map(|(_, label)| label);
+        let speed_combo = adw::ComboRow::builder()
+            .title("Speed")
+            .model(&gtk4::StringList::new(&speed_labels))
+            .selected(
+                SPEEDS
+                    .iter()
+                    .position(|(speed, _)| *speed == AuraSpeed::default())
+                    .unwrap_or_default() as u32,
+            )
+            .visible(false)
+            .build();
+        let direction_labels = DIRECTIONS.map(|(_, label)| label);
+        let direction_combo = adw::ComboRow::builder()
+            .title("Direction")
+            .model(&gtk4::StringList::new(&direction_labels))
+            .visible(false)
+            .build();
+
+        for combo in [&speed_combo, &direction_combo] {
+            let guard = imp.updating.clone();
+            let page_weak = self.downgrade();
+            combo.connect_selected_notify(move |combo| {
+                if guard.is_active() {
+                    return;
+                }
+                if let Some(page) = page_weak.upgrade() {
+                    page.apply_effect(combo);
+                }
+            });
+            mode_group.add(combo);
         }
 
+        imp.speed_combo.replace(Some(speed_combo));
+        imp.direction_combo.replace(Some(direction_combo));
+
         self.append(&mode_group);
 
         // Color selection group
@@ -153,39 +431,458 @@ impl AuraPage {
             .build();
 
         let color_dialog = gtk4::ColorDialog::builder().build();
+        // Shown until asusd reports a colour, and kept for modes without one
         let color_button = gtk4::ColorDialogButton::builder()
             .dialog(&color_dialog)
+            .rgba(&colour_rgba(AuraConfig::default().colour))
             .valign(gtk4::Align::Center)
             .build();
 
+        let guard = imp.updating.clone();
+        let page_weak = self.downgrade();
+        color_button.connect_rgba_notify(move |_| {
+            if guard.is_active() {
+                return;
+            }
+            if let Some(page) = page_weak.upgrade() {
+                page.commit_colour_later();
+            }
+        });
+
+        imp.color_button.replace(Some(color_button.clone()));
         color_row.add_suffix(&color_button);
         color_row.set_activatable_widget(Some(&color_button));
         color_group.add(&color_row);
 
+        imp.color_group.replace(Some(color_group.clone()));
         self.append(&color_group);
+
+        // Sleep group, revealed once asusd reports the keyboard's power states
+        let sleep_group = adw::PreferencesGroup::builder()
+            .title("Sleep")
+            .visible(false)
+            .build();
+
+        let sleep_switch = adw::SwitchRow::builder()
+            .title("Turn Off While Asleep")
+            .subtitle("Switch the keyboard lighting off during suspend")
+            .build();
+
+        let guard = imp.updating.clone();
+        sleep_switch.connect_active_notify(move |switch| {
+            if guard.is_active() {
+                return;
+            }
+
+            let toast_target = switch.clone();
+            bind::apply_switch_then(
+                switch,
+                &guard,
+                "Failed to change lighting during sleep",
+                |off| backend::set_keyboard_lit_on_sleep(!off),
+                move |off| {
+                    let message = if off {
+                        "Keyboard lighting turns off during sleep"
+                    } else {
+                        "Keyboard lighting stays on during sleep"
+                    };
+                    bind::show_toast(&toast_target, message);
+                },
+            );
+        });
+
+        sleep_group.add(&sleep_switch);
+        imp.sleep_switch.replace(Some(sleep_switch));
+        self.append(&sleep_group);
+    }
+
+    /// Replace the single colour row with one row per zone
+    ///
+    /// asusd only reports one colour for the whole keyboard, so the zone
+    /// buttons start at that colour and aren't updated on refresh.
+    fn show_zone_colours(&self, zones: &[AuraZone]) {
+        let imp = self.imp();
+        let (Some(group), Some(single_button)) = (
+            imp.color_group.borrow().clone(),
+            imp.color_button.borrow().clone(),
+        ) else {
+            return;
+        };
+
+        if let Some(single_row) = single_button.ancestor(adw::PreferencesRow::static_type()) {
+            single_row.set_visible(false);
+        }
+
+        let mut zone_buttons = Vec::new();
+        for &zone in zones {
+            let row = adw::ActionRow::builder().title(zone.to_string()).build();
+            let button = gtk4::ColorDialogButton::builder()
+                .dialog(&gtk4::ColorDialog::new())
+                .rgba(&single_button.rgba())
+                .valign(gtk4::Align::Center)
+                .build();
+
+            let commit = Rc::new(Debouncer::default());
+            button.connect_rgba_notify(move |button| {
+                let button = button.clone();
+                commit.call(COLOUR_DEBOUNCE, move || {
+                    let colour = button_colour(&button);
+                    bind::spawn_setter(&button, "Failed to set zone color", move || {
+                        backend::set_aura_zone_colour(zone, colour)
+                    });
+                });
+            });
+
+            row.add_suffix(&button);
+            row.set_activatable_widget(Some(&button));
+            group.add(&row);
+            zone_buttons.push((zone, button));
+        }
+
+        imp.zone_buttons.replace(zone_buttons);
+    }
+
+    /// Colour picked in the color button, as RGB bytes
+    fn selected_colour(&self) -> [u8; 3] {
+        match self.imp().color_button.borrow().as_ref() {
+            Some(button) => button_colour(button),
+            None => AuraConfig::default().colour,
+        }
+    }
+
+    /// Send the picked colour once the color button has settled
+    fn commit_colour_later(&self) {
+        let page_weak = self.downgrade();
+        self.imp().colour_commit.call(COLOUR_DEBOUNCE, move || {
+            let Some(page) = page_weak.upgrade() else {
+                return;
+            };
+
+            let colour = page.selected_colour();
+            let effect = page.selected_effect();
+            if let Some(button) = page.imp().color_button.borrow().as_ref() {
+                bind::spawn_setter(button, "Failed to set keyboard color", move || {
+                    backend::set_aura_colour(colour, effect)
+                });
+            }
+        });
+    }
+
+    /// Move the checkmark to `mode`'s row and offer its effect settings
+    fn show_active_mode(&self, mode: AuraMode) {
+        let imp = self.imp();
+        imp.active_mode.set(Some(mode));
+        for (row_mode, _, checkmark) in imp.mode_rows.borrow().iter() {
+            checkmark.set_visible(*row_mode == mode);
+        }
+
+        if let Some(combo) = imp.speed_combo.borrow().as_ref() {
+            combo.set_visible(mode.has_speed());
+        }
+        if let Some(combo) = imp.direction_combo.borrow().as_ref() {
+            combo.set_visible(mode.has_direction());
+        }
+    }
+
+    /// Speed and direction picked in the effect combos
+    fn selected_effect(&self) -> AuraEffect {
+        let imp = self.imp();
+        let selected = |combo: &RefCell<Option<adw::ComboRow>>| {
+            combo
+                .borrow()
+                .as_ref()
+                .map(|combo| combo.selected() as usize)
+        };
+
+        AuraEffect {
+            speed: selected(&imp.speed_combo)
+                .and_then(|index| SPEEDS.get(index))
+                .map(|(speed, _)| *speed),
+            direction: selected(&imp.direction_combo)
+                .and_then(|index| DIRECTIONS.get(index))
+                .map(|(direction, _)| *direction),
+        }
+    }
+
+    /// Apply the active mode again with the effect just picked in `combo`
+    fn apply_effect(&self, combo: &adw::ComboRow) {
+        let Some(mode) = self.imp().active_mode.get() else {
+            return;
+        };
+        let colour = self.selected_colour();
+        let effect = self.selected_effect();
+
+        bind::apply_setter(combo, "Failed to set lighting effect", move || {
+            backend::set_aura_mode(mode, colour, effect)
+        });
+    }
+
+    /// Offer only the brightness levels asusctl lists for this keyboard
+    ///
+    /// Without a list every level stays available; an empty list means the
+    /// brightness can't be set at all.
+    fn filter_supported_brightness(&self, levels: Option<&[KeyboardBrightness]>) {
+        let imp = self.imp();
+        let Some(levels) = levels else {
+            return;
+        };
+
+        let buttons = imp.brightness_buttons.borrow();
+        if levels.is_empty() {
+            if let Some(group) = buttons
+                .first()
+                .and_then(|button| button.ancestor(adw::PreferencesGroup::static_type()))
+            {
+                group.set_sensitive(false);
+            }
+            return;
+        }
+
+        for (level, button) in KeyboardBrightness::ALL.iter().zip(buttons.iter()) {
+            button.set_visible(levels.contains(level));
+        }
+        if let Some(scale) = imp.brightness_scale.borrow().as_ref() {
+            scale.clear_marks();
+            for level in KeyboardBrightness::ALL {
+                if levels.contains(&level) {
+                    scale.add_mark(
+                        level.as_u8() as f64,
+                        gtk4::PositionType::Bottom,
+                        Some(level.label()),
+                    );
+                }
+            }
+        }
+        imp.brightness_levels.replace(levels.to_vec());
+    }
+
+    /// Level shown by whichever brightness control is visible
+    fn shown_brightness(&self) -> Option<KeyboardBrightness> {
+        let imp = self.imp();
+        if let Some(scale) = imp.brightness_scale.borrow().as_ref()
+            && scale.is_visible()
+        {
+            return KeyboardBrightness::from_u8(scale.value().round() as u8).ok();
+        }
+
+        let index = imp
+            .brightness_buttons
+            .borrow()
+            .iter()
+            .position(|button| button.is_active())?;
+        KeyboardBrightness::from_u8(index as u8).ok()
+    }
+
+    /// Set the next supported level up or down, stopping at the ends
+    fn step_brightness(&self, busy: &gtk4::Button, brighter: bool) {
+        let imp = self.imp();
+        let current = self.shown_brightness().unwrap_or_default();
+
+        let supported = imp.brightness_levels.borrow().clone();
+        let supported = if supported.is_empty() {
+            KeyboardBrightness::ALL.to_vec()
+        } else {
+            supported
+        };
+        let next = if brighter {
+            supported
+                .into_iter()
+                .filter(|level| level.as_u8() > current.as_u8())
+                .min_by_key(KeyboardBrightness::as_u8)
+        } else {
+            supported
+                .into_iter()
+                .filter(|level| level.as_u8() < current.as_u8())
+                .max_by_key(KeyboardBrightness::as_u8)
+        };
+        let Some(level) = next else {
+            return;
+        };
+
+        // Show the new level without the controls sending it a second time
+        {
+            let _guard = imp.updating.hold();
+            if let Some(button) = imp.brightness_buttons.borrow().get(level.as_u8() as usize) {
+                button.set_active(true);
+            }
+            if let Some(scale) = imp.brightness_scale.borrow().as_ref() {
+                scale.set_value(level.as_u8() as f64);
+            }
+            if let Some(label) = imp.brightness_value.borrow().as_ref() {
+                label.set_label(&level.as_u8().to_string());
+            }
+        }
+
+        imp.known_brightness.set(level);
+        let link_slash =
+            gio::Settings::new("com.github.bl4ckspell7.asusctl-gui").boolean("link-keyboard-slash");
+        bind::apply_setter(busy, "Failed to set brightness", move || {
+            set_brightness(level, link_slash)
+        });
+    }
+
+    /// Closest accepted level to `level`, or `None` while all levels are accepted
+    fn nearest_supported_brightness(
+        &self,
+        level: KeyboardBrightness,
+    ) -> Option<KeyboardBrightness> {
+        self.imp()
+            .brightness_levels
+            .borrow()
+            .iter()
+            .min_by_key(|supported| supported.as_u8().abs_diff(level.as_u8()))
+            .copied()
+    }
+
+    /// Hide the modes asusctl doesn't list for this keyboard
+    ///
+    /// Without a usable list every mode stays visible.
+    fn filter_supported_modes(&self, supported: &[AuraMode]) {
+        if supported.is_empty() {
+            return;
+        }
+
+        for (mode, row, _) in self.imp().mode_rows.borrow().iter() {
+            row.set_visible(supported.contains(mode));
+        }
+    }
+
+    fn setup_actions(&self) {
+        let actions = gio::SimpleActionGroup::new();
+
+        // Recover from a lighting setup the user can't get back out of
+        let restore_action = gio::SimpleAction::new("restore-default", None);
+        let page = self.downgrade();
+        restore_action.connect_activate(move |_, _| {
+            if let Some(page) = page.upgrade() {
+                bind::apply_setter(&page, "Failed to restore default lighting", || {
+                    backend::apply_aura(&AuraConfig::default())
+                });
+            }
+        });
+        actions.add_action(&restore_action);
+
+        self.insert_action_group("aura", Some(&actions));
     }
 
     /// Refresh/reload all data on this page
     fn refresh_data(&self) {
         let imp = self.imp();
+        // Supported features need a separate asusctl call, so ask until one works
+        let check_features = !imp.features_checked.get();
+
+        imp.loader.load(
+            self,
+            move || AuraState::read(check_features),
+            |page, state| page.apply_state(state),
+        );
+    }
+
+    fn apply_state(&self, state: AuraState) {
+        let imp = self.imp();
+        let _guard = imp.updating.hold();
 
         // Get current brightness via D-Bus and update buttons
-        match backend::get_keyboard_brightness_dbus() {
-            Ok(current_brightness) => {
-                let buttons = imp.brightness_buttons.borrow();
-                let index = match current_brightness {
-                    KeyboardBrightness::Off => 0,
-                    KeyboardBrightness::Low => 1,
-                    KeyboardBrightness::Med => 2,
-                    KeyboardBrightness::High => 3,
-                };
+        let buttons = imp.brightness_buttons.borrow();
+        let Some(first) = buttons.first() else {
+            return;
+        };
+
+        if let Some(current_brightness) =
+            bind::read_or_hide(first, "Failed to get keyboard brightness", state.brightness)
+        {
+            let index = current_brightness.as_u8() as usize;
+
+            let changed = imp.known_brightness.update(current_brightness);
+            if let Some(btn) = buttons.get(index) {
+                btn.set_active(true);
+                if changed {
+                    bind::flash_changed(btn);
+                }
+            }
+            if let Some(scale) = imp.brightness_scale.borrow().as_ref() {
+                scale.set_value(index as f64);
+                if changed {
+                    bind::flash_changed(scale);
+                }
+            }
 
-                if let Some(btn) = buttons.get(index) {
-                    btn.set_active(true);
+            if let Some(label) = imp.brightness_value.borrow().as_ref() {
+                label.set_label(&index.to_string());
+            }
+        } else {
+            // Don't suggest a level that wasn't read
+            for btn in buttons.iter() {
+                btn.set_active(false);
+            }
+        }
+
+        if let Some(button) = imp.color_button.borrow().as_ref() {
+            match state.colour {
+                Ok(colour) => {
+                    let rgba = colour_rgba(colour);
+                    // Only touch the button on a real change, so an open dialog isn't reset
+                    if button.rgba() != rgba {
+                        button.set_rgba(&rgba);
+                    }
                 }
+                // Not every mode has a colour; keep whatever the button shows
+                Err(e) => log::debug!("No keyboard colour to show: {e}"),
             }
-            Err(e) => {
-                eprintln!("Failed to get keyboard brightness: {e}");
+        }
+
+        if let Some(switch) = imp.sleep_switch.borrow().as_ref()
+            && let Some(group) = switch.ancestor(adw::PreferencesGroup::static_type())
+            && let Some(lit) = bind::read_or_hide(
+                &group,
+                "Failed to get lighting during sleep",
+                state.lit_on_sleep,
+            )
+        {
+            switch.set_active(!lit);
+        }
+
+        // Zone buttons start from the colour just read
+        match state.features {
+            Some(Ok(features)) => {
+                imp.features_checked.set(true);
+                self.filter_supported_modes(&features.aura_modes);
+                self.filter_supported_brightness(features.keyboard_brightness_levels.as_deref());
+                if features.aura_zones.len() > 1 {
+                    self.show_zone_colours(&features.aura_zones);
+                }
+            }
+            // Asked again on the next refresh
+            Some(Err(e)) => log::warn!("Failed to get supported lighting features: {e}"),
+            None => {}
+        }
+
+        // Load the active lighting mode
+        let mode_group = imp
+            .mode_rows
+            .borrow()
+            .first()
+            .and_then(|(_, row, _)| row.ancestor(adw::PreferencesGroup::static_type()));
+        if let Some(mode_group) = mode_group
+            && let Some(mode) =
+                bind::read_or_hide(&mode_group, "Failed to get lighting mode", state.mode)
+        {
+            match mode {
+                Some(mode) => {
+                    self.show_active_mode(mode);
+                    if imp.known_mode.update(mode)
+                        && let Some((_, row, _)) =
+                            imp.mode_rows.borrow().iter().find(|(m, _, _)| *m == mode)
+                    {
+                        bind::flash_changed(row);
+                    }
+                }
+                // A mode set elsewhere that isn't listed here
+                None => {
+                    for (_, _, checkmark) in imp.mode_rows.borrow().iter() {
+                        checkmark.set_visible(false);
+                    }
+                }
             }
         }
     }
@@ -201,4 +898,8 @@ impl Refreshable for AuraPage {
     fn refresh(&self) {
         self.refresh_data();
     }
+
+    fn loader(&self) -> Rc<PageLoader> {
+        self.imp().loader.clone()
+    }
 }
@@ -0,0 +1,489 @@
+use adw::prelude::*;
+use gtk4::glib;
+use gtk4::prelude::*;
+use gtk4::subclass::prelude::*;
+use libadwaita as adw;
+use std::cell::{Cell, RefCell};
+use std::rc::Rc;
+
+use crate::backend::{self, AsusctlError, FanCurve, PowerProfile};
+use crate::ui::Refreshable;
+use crate::ui::bind::{self, PageLoader, UpdateGuard};
+
+mod imp {
+    use super::*;
+
+    #[derive(Debug, Default)]
+    pub struct FanCurvesPage {
+        pub profile_combo: RefCell<Option<adw::ComboRow>>,
+        /// Holds one group per fan, rebuilt whenever curves are loaded
+        pub fans_box: RefCell<Option<gtk4::Box>>,
+        pub actions_box: RefCell<Option<gtk4::Box>>,
+        /// Curves as edited on the page, written on Apply
+        pub curves: RefCell<Vec<FanCurve>>,
+        /// Set once the user edits a curve; refreshes leave edits alone
+        pub dirty: Cell<bool>,
+        /// Raised while the profile combo is set from the active profile
+        pub updating: Rc<UpdateGuard>,
+        pub loader: Rc<PageLoader>,
+    }
+
+    #[glib::object_subclass]
+    impl ObjectSubclass for FanCurvesPage {
+        const NAME: &'static str = "FanCurvesPage";
+        type Type = super::FanCurvesPage;
+        type ParentType = gtk4::Box;
+    }
+
+    impl ObjectImpl for FanCurvesPage {
+        fn constructed(&self) {
+            self.parent_constructed();
+            self.obj().setup_ui();
+            self.obj().refresh_data();
+        }
+    }
+
+    impl WidgetImpl for FanCurvesPage {}
+    impl BoxImpl for FanCurvesPage {}
+}
+
+glib::wrapper! {
+    pub struct FanCurvesPage(ObjectSubclass<imp::FanCurvesPage>)
+        @extends gtk4::Box, gtk4::Widget,
+        @implements gtk4::Accessible, gtk4::Buildable, gtk4::ConstraintTarget, gtk4::Orientable;
+}
+
+// Profile combo order
+const PROFILES: [PowerProfile; 3] = [
+    PowerProfile::Quiet,
+    PowerProfile::Balanced,
+    PowerProfile::Performance,
+];
+
+// Temperature range drawn in the curve preview, in °C
+const PREVIEW_MIN_TEMP: f64 = 20.0;
+const PREVIEW_MAX_TEMP: f64 = 110.0;
+
+impl FanCurvesPage {
+    pub fn new() -> Self {
+        glib::Object::builder()
+            .property("orientation", gtk4::Orientation::Vertical)
+            .property("spacing", 24)
+            .property("margin-top", 24)
+            .property("margin-bottom", 24)
+            .property("margin-start", 24)
+            .property("margin-end", 24)
+            .build()
+    }
+
+    fn setup_ui(&self) {
+        let imp = self.imp();
+
+        // Page title
+        let title = gtk4::Label::builder()
+            .label("Fan Curves")
+            .css_classes(["title-1"])
+            .halign(gtk4::Align::Start)
+            .build();
+
+        self.append(&title);
+
+        // Profile group
+        let profile_group = adw::PreferencesGroup::builder()
+            .description("Each power profile has its own fan curves")
+            .build();
+
+        let profile_names: Vec<String> = PROFILES.iter().map(|p| p.to_string()).collect();
+        let profile_names: Vec<&str> = profile_names.iter().map(String::as_str).collect();
+        let profile_combo = adw::ComboRow::builder()
+            .title("Profile")
+            .model(&gtk4::StringList::new(&profile_names))
+            .build();
+
+        let guard = imp.updating.clone();
+        let page_weak = self.downgrade();
+        profile_combo.connect_selected_notify(move |_| {
+            if guard.is_active() {
+                return;
+            }
+            if let Some(page) = page_weak.upgrade() {
+                // Switching profiles discards edits to the previous one
+                page.imp().dirty.set(false);
+                page.refresh_data();
+            }
+        });
+
+        imp.profile_combo.replace(Some(profile_combo.clone()));
+        profile_group.add(&profile_combo);
+        self.append(&profile_group);
+
+        let fans_box = gtk4::Box::builder()
+            .orientation(gtk4::Orientation::Vertical)
+            .spacing(24)
+            .build();
+
+        imp.fans_box.replace(Some(fans_box.clone()));
+        self.append(&fans_box);
+
+        // Apply / reset buttons
+        let actions_box = gtk4::Box::builder()
+            .orientation(gtk4::Orientation::Horizontal)
+            .spacing(12)
+            .halign(gtk4::Align::End)
+            .build();
+
+        let reset_button = gtk4::Button::builder().label("Reset to Defaults").build();
+
+        let page_weak = self.downgrade();
+        reset_button.connect_clicked(move |_| {
+            let Some(page) = page_weak.upgrade() else {
+                return;
+            };
+            let Some(profile) = page.selected_profile() else {
+                return;
+            };
+            let Some(actions_box) = page.imp().actions_box.borrow().clone() else {
+                return;
+            };
+
+            let page_weak = page.downgrade();
+            bind::apply_setter_then(
+                &actions_box,
+                "Failed to reset fan curves",
+                move || backend::reset_fan_curves(profile),
+                move |()| {
+                    if let Some(page) = page_weak.upgrade() {
+                        page.imp().dirty.set(false);
+                        page.refresh_data();
+                    }
+                },
+            );
+        });
+
+        let apply_button = gtk4::Button::builder()
+            .label("Apply")
+            .css_classes(["suggested-action"])
+            .build();
+
+        let page_weak = self.downgrade();
+        apply_button.connect_clicked(move |_| {
+            let Some(page) = page_weak.upgrade() else {
+                return;
+            };
+            let Some(profile) = page.selected_profile() else {
+                return;
+            };
+            let Some(actions_box) = page.imp().actions_box.borrow().clone() else {
+                return;
+            };
+
+            let curves = page.imp().curves.borrow().clone();
+            let page_weak = page.downgrade();
+            bind::apply_setter_then(
+                &actions_box,
+                "Failed to set fan curve",
+                move || {
+                    for curve in &curves {
+                        backend::set_fan_curve(profile, curve)?;
+                    }
+                    Ok(())
+                },
+                move |()| {
+                    if let Some(page) = page_weak.upgrade() {
+                        page.imp().dirty.set(false);
+                    }
+                },
+            );
+        });
+
+        actions_box.append(&reset_button);
+        actions_box.append(&apply_button);
+
+        imp.actions_box.replace(Some(actions_box.clone()));
+        self.append(&actions_box);
+    }
+
+    fn selected_profile(&self) -> Option<PowerProfile> {
+        let combo = self.imp().profile_combo.borrow().clone()?;
+        PROFILES.get(combo.selected() as usize).copied()
+    }
+
+    /// Replace the fan groups with controls for `curves`
+    fn show_curves(&self, curves: Vec<FanCurve>) {
+        let imp = self.imp();
+        let Some(fans_box) = imp.fans_box.borrow().clone() else {
+            return;
+        };
+
+        while let Some(child) = fans_box.first_child() {
+            fans_box.remove(&child);
+        }
+
+        // Laptops with a single fan only report one curve
+        for (fan_index, curve) in curves.iter().enumerate() {
+            fans_box.append(&self.build_fan_group(fan_index, curve));
+        }
+
+        imp.curves.replace(curves);
+    }
+
+    fn build_fan_group(&self, fan_index: usize, curve: &FanCurve) -> adw::PreferencesGroup {
+        let group = adw::PreferencesGroup::builder()
+            .title(curve.fan.to_string())
+            .build();
+
+        let enabled_row = adw::SwitchRow::builder()
+            .title("Custom Curve")
+            .subtitle("Use this curve instead of the firmware default")
+            .active(curve.enabled)
+            .build();
+
+        let page_weak = self.downgrade();
+        enabled_row.connect_active_notify(move |row| {
+            if let Some(page) = page_weak.upgrade() {
+                page.edit_curve(fan_index, |curve| curve.enabled = row.is_active());
+            }
+        });
+
+        group.add(&enabled_row);
+
+        // Curve preview
+        let preview = gtk4::DrawingArea::builder()
+            .content_height(120)
+            .margin_top(12)
+            .margin_bottom(12)
+            .build();
+
+        let page_weak = self.downgrade();
+        preview.set_draw_func(move |area, cr, width, height| {
+            let Some(page) = page_weak.upgrade() else {
+                return;
+            };
+            if let Some(curve) = page.imp().curves.borrow().get(fan_index) {
+                draw_curve(area, cr, width, height, curve);
+            }
+        });
+
+        let preview_row = adw::PreferencesRow::builder()
+            .activatable(false)
+            .child(&preview)
+            .build();
+        group.add(&preview_row);
+
+        for (point_index, point) in curve.points.iter().enumerate() {
+            let row = adw::ActionRow::builder()
+                .title(format!("{} °C", point.temp))
+                .build();
+
+            let scale = gtk4::Scale::builder()
+                .orientation(gtk4::Orientation::Horizontal)
+                .adjustment(&gtk4::Adjustment::new(
+                    point.percent as f64,
+                    0.0,
+                    100.0,
+                    1.0,
+                    10.0,
+                    0.0,
+                ))
+                .width_request(200)
+                .valign(gtk4::Align::Center)
+                .draw_value(true)
+                .digits(0)
+                .build();
+            scale.set_format_value_func(|_, value| format!("{value:.0}%"));
+
+            let page_weak = self.downgrade();
+            let preview = preview.clone();
+            scale.connect_value_changed(move |scale| {
+                let Some(page) = page_weak.upgrade() else {
+                    return;
+                };
+                let percent = scale.value() as u8;
+                page.edit_curve(fan_index, |curve| {
+                    curve.points[point_index].percent = percent;
+                });
+                preview.queue_draw();
+            });
+
+            row.add_suffix(&scale);
+            group.add(&row);
+        }
+
+        group
+    }
+
+    /// Apply an edit to the curve of one fan and mark the page as modified
+    fn edit_curve(&self, fan_index: usize, edit: impl FnOnce(&mut FanCurve)) {
+        let imp = self.imp();
+        if let Some(curve) = imp.curves.borrow_mut().get_mut(fan_index) {
+            edit(curve);
+            imp.dirty.set(true);
+        }
+    }
+
+    /// Show a status message in place of the fan groups
+    fn show_status(&self, title: &str, description: &str) {
+        let imp = self.imp();
+        let Some(fans_box) = imp.fans_box.borrow().clone() else {
+            return;
+        };
+
+        while let Some(child) = fans_box.first_child() {
+            fans_box.remove(&child);
+        }
+
+        let status = adw::StatusPage::builder()
+            .icon_name("dialog-information-symbolic")
+            .title(title)
+            .description(description)
+            .css_classes(["compact"])
+            .build();
+        fans_box.append(&status);
+
+        imp.curves.replace(Vec::new());
+    }
+
+    /// Refresh/reload all data on this page
+    ///
+    /// Unsaved edits are kept until they are applied or reset.
+    fn refresh_data(&self) {
+        let imp = self.imp();
+        if imp.dirty.get() {
+            return;
+        }
+
+        // Start on the active profile the first time the page loads
+        let first_load = imp.curves.borrow().is_empty();
+        let selected = self.selected_profile();
+
+        imp.loader.load(
+            self,
+            move || {
+                let active = first_load
+                    .then(|| {
+                        backend::get_profile_state()
+                            .ok()
+                            .and_then(|state| state.active)
+                    })
+                    .flatten();
+                let profile = active.or(selected).unwrap_or_default();
+                (active, profile, backend::get_fan_curves(profile))
+            },
+            |page, (active, profile, curves)| page.apply_curves(active, profile, curves),
+        );
+    }
+
+    fn apply_curves(
+        &self,
+        active: Option<PowerProfile>,
+        profile: PowerProfile,
+        curves: backend::Result<Vec<FanCurve>>,
+    ) {
+        let imp = self.imp();
+        // Edits made while the curves were loading win
+        if imp.dirty.get() {
+            return;
+        }
+
+        if let Some(active) = active
+            && let Some(combo) = imp.profile_combo.borrow().as_ref()
+            && let Some(index) = PROFILES.iter().position(|p| *p == active)
+        {
+            let _guard = imp.updating.hold();
+            combo.set_selected(index as u32);
+        }
+
+        // The user switched profiles meanwhile; their load follows
+        if self.selected_profile() != Some(profile) {
+            return;
+        }
+
+        let has_curves = match curves {
+            Ok(curves) if curves.is_empty() => {
+                self.show_status("No Fans Reported", "asusd didn't report any fan curves");
+                false
+            }
+            Ok(curves) => {
+                self.show_curves(curves);
+                true
+            }
+            Err(AsusctlError::Unsupported(_)) => {
+                self.show_status(
+                    "Fan Curves Not Supported",
+                    "This laptop doesn't support custom fan curves",
+                );
+                false
+            }
+            Err(e) => {
+                log::warn!("Failed to get fan curves: {e}");
+                false
+            }
+        };
+
+        if let Some(actions_box) = imp.actions_box.borrow().as_ref() {
+            actions_box.set_visible(has_curves);
+        }
+    }
+}
+
+/// Draw `curve` as a line over the preview's temperature range
+fn draw_curve(
+    area: &gtk4::DrawingArea,
+    cr: &gtk4::cairo::Context,
+    width: i32,
+    height: i32,
+    curve: &FanCurve,
+) {
+    let (width, height) = (width as f64, height as f64);
+    let x_for = |temp: u8| {
+        let t = (temp as f64 - PREVIEW_MIN_TEMP) / (PREVIEW_MAX_TEMP - PREVIEW_MIN_TEMP);
+        t.clamp(0.0, 1.0) * width
+    };
+    let y_for = |percent: u8| height - (percent.min(100) as f64 / 100.0) * height;
+
+    let color = area.color();
+    cr.set_source_rgba(
+        color.red() as f64,
+        color.green() as f64,
+        color.blue() as f64,
+        if curve.enabled { 1.0 } else { 0.4 },
+    );
+    cr.set_line_width(2.0);
+
+    for (i, point) in curve.points.iter().enumerate() {
+        let (x, y) = (x_for(point.temp), y_for(point.percent));
+        if i == 0 {
+            cr.move_to(x, y);
+        } else {
+            cr.line_to(x, y);
+        }
+    }
+    let _ = cr.stroke();
+
+    for point in &curve.points {
+        cr.arc(
+            x_for(point.temp),
+            y_for(point.percent),
+            3.0,
+            0.0,
+            std::f64::consts::TAU,
+        );
+        let _ = cr.fill();
+    }
+}
+
+impl Default for FanCurvesPage {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Refreshable for FanCurvesPage {
+    fn refresh(&self) {
+        self.refresh_data();
+    }
+
+    fn loader(&self) -> Rc<PageLoader> {
+        self.imp().loader.clone()
+    }
+}
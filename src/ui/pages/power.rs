@@ -3,10 +3,13 @@ use gtk4::glib;
 use gtk4::prelude::*;
 use gtk4::subclass::prelude::*;
 use libadwaita as adw;
-use std::cell::RefCell;
+use std::cell::{Cell, RefCell};
+use std::rc::Rc;
+use std::time::Duration;
 
-use crate::backend::{self, PowerProfile};
+use crate::backend::{self, GfxMode, PowerProfile};
 use crate::ui::Refreshable;
+use crate::ui::bind::{self, AppliedValue, Debouncer, KnownValue, PageLoader, UpdateGuard};
 
 mod imp {
     use super::*;
@@ -17,6 +20,32 @@ mod imp {
         pub ac_combo: RefCell<Option<adw::ComboRow>>,
         pub battery_combo: RefCell<Option<adw::ComboRow>>,
         pub charge_scale: RefCell<Option<gtk4::Scale>>,
+        pub charge_limits_group: RefCell<Option<adw::PreferencesGroup>>,
+        /// Used instead of `charge_scale` when several batteries have a limit
+        pub battery_charge_limits: RefCell<Vec<super::BatteryChargeLimit>>,
+        pub estimate_row: RefCell<Option<adw::ActionRow>>,
+        pub battery_group: RefCell<Option<adw::PreferencesGroup>>,
+        /// Charge, cycle and health rows, `BATTERY_ROWS` per battery
+        pub battery_rows: RefCell<Vec<adw::ActionRow>>,
+        pub sensors_group: RefCell<Option<adw::PreferencesGroup>>,
+        /// One row per temperature or fan the machine reports
+        pub sensor_rows: RefCell<Vec<adw::ActionRow>>,
+        pub throttle_group: RefCell<Option<adw::PreferencesGroup>>,
+        pub throttle_combos: RefCell<Vec<(adw::ComboRow, super::ThrottleGetter)>>,
+        pub throttle_checked: Cell<bool>,
+        pub overdrive_switch: RefCell<Option<adw::SwitchRow>>,
+        pub gfx_group: RefCell<Option<adw::PreferencesGroup>>,
+        pub gfx_combo: RefCell<Option<adw::ComboRow>>,
+        /// Modes listed in `gfx_combo`, in order
+        pub gfx_modes: RefCell<Vec<GfxMode>>,
+        /// Mode supergfxd last reported
+        pub gfx_mode: Cell<Option<GfxMode>>,
+        pub known_profile: Rc<KnownValue<PowerProfile>>,
+        pub known_charge_limit: Rc<KnownValue<u8>>,
+        pub applied_profile: Rc<AppliedValue<PowerProfile>>,
+        pub applied_charge_limit: Rc<AppliedValue<u8>>,
+        pub updating: Rc<UpdateGuard>,
+        pub loader: Rc<PageLoader>,
     }
 
     #[glib::object_subclass]
@@ -44,6 +73,110 @@ glib::wrapper! {
         @implements gtk4::Accessible, gtk4::Buildable, gtk4::ConstraintTarget, gtk4::Orientable;
 }
 
+/// Everything the page shows, read off the main thread
+struct PowerState {
+    profiles: backend::Result<backend::ProfileState>,
+    profile_on_ac: backend::Result<PowerProfile>,
+    profile_on_battery: backend::Result<PowerProfile>,
+    charge_limit: backend::Result<u8>,
+    estimate: backend::Result<backend::BatteryEstimate>,
+    batteries: backend::Result<Vec<backend::BatteryInfo>>,
+    sensors: backend::Result<backend::SensorReadings>,
+    /// Only set on the first load, when support is checked
+    throttle_supported: Option<bool>,
+    /// One entry per throttle combo, or empty when not supported
+    throttle: Vec<backend::Result<PowerProfile>>,
+    panel_overdrive: backend::Result<bool>,
+    gfx_modes: backend::Result<Vec<GfxMode>>,
+    gfx_mode: backend::Result<GfxMode>,
+}
+
+impl PowerState {
+    fn read(
+        check_throttle: bool,
+        read_throttle: bool,
+        throttle_getters: &[ThrottleGetter],
+    ) -> Self {
+        let throttle_supported = check_throttle.then(|| {
+            backend::get_supported_features().is_ok_and(|features| features.has_throttle_policy)
+        });
+        let throttle = if throttle_supported.unwrap_or(read_throttle) {
+            throttle_getters.iter().map(|getter| getter()).collect()
+        } else {
+            Vec::new()
+        };
+
+        Self {
+            profiles: backend::get_profile_state(),
+            profile_on_ac: backend::get_profile_on_ac_dbus(),
+            profile_on_battery: backend::get_profile_on_battery_dbus(),
+            charge_limit: backend::get_charge_limit_dbus(),
+            estimate: backend::get_battery_estimate(),
+            batteries: backend::get_batteries(),
+            sensors: backend::get_sensor_readings(),
+            throttle_supported,
+            throttle,
+            panel_overdrive: backend::get_panel_overdrive(),
+            gfx_modes: backend::get_gfx_supported_modes(),
+            gfx_mode: backend::get_gfx_mode(),
+        }
+    }
+}
+
+/// Rows shown for each battery: charge, cycle count and health
+const BATTERY_ROWS: usize = 3;
+
+type ThrottleGetter = fn() -> backend::Result<PowerProfile>;
+type ThrottleSetter = fn(PowerProfile) -> backend::Result<()>;
+
+/// Combo model listing the profiles in `PowerProfile::ALL` order
+fn profile_model() -> gtk4::StringList {
+    let titles = PowerProfile::ALL.map(|profile| profile.to_string());
+    gtk4::StringList::new(&titles.each_ref().map(String::as_str))
+}
+
+/// Charge limit control of one battery, for machines with several
+#[derive(Debug)]
+pub struct BatteryChargeLimit {
+    /// Kernel name, e.g. "BAT1"
+    name: String,
+    row: adw::ActionRow,
+    scale: gtk4::Scale,
+}
+
+/// Quiet time before a per-battery limit is written, as it may ask for a password
+const BATTERY_CHARGE_LIMIT_DEBOUNCE: Duration = Duration::from_millis(500);
+
+/// Written to sysfs directly, so asusd can put its own limit back
+const CHARGE_LIMITS_DESCRIPTION: &str = "Each battery stops charging at its own limit. asusd may \
+     restore its saved limit on the battery it manages, e.g. when it starts.";
+
+fn charge_limit_scale() -> gtk4::Scale {
+    gtk4::Scale::builder()
+        .orientation(gtk4::Orientation::Horizontal)
+        .adjustment(&gtk4::Adjustment::new(
+            80.0,
+            *backend::CHARGE_LIMIT_RANGE.start() as f64,
+            *backend::CHARGE_LIMIT_RANGE.end() as f64,
+            5.0,
+            10.0,
+            0.0,
+        ))
+        .width_request(200)
+        .valign(gtk4::Align::Center)
+        .draw_value(true)
+        .build()
+}
+
+/// Explain what a charge limit does to the battery
+fn charge_limit_summary(limit: u8) -> String {
+    match limit {
+        100.. => "Battery charges fully, without wear protection".to_string(),
+        81..=99 => format!("Battery will stop charging at {limit}%"),
+        _ => format!("Battery will stop charging at {limit}%, reducing long-term wear"),
+    }
+}
+
 impl PowerPage {
     pub fn new() -> Self {
         glib::Object::builder()
@@ -56,6 +189,25 @@ impl PowerPage {
             .build()
     }
 
+    /// Record a profile the app applied outside this page, e.g. by a shortcut
+    ///
+    /// The next refresh then doesn't report it as an external change.
+    pub fn remember_profile(&self, profile: PowerProfile) {
+        let imp = self.imp();
+        imp.known_profile.set(profile);
+        imp.applied_profile.set(profile);
+        self.mark_profile(profile);
+    }
+
+    /// Mark the `active` profile's row if it isn't the one last applied
+    fn mark_profile(&self, active: PowerProfile) {
+        let imp = self.imp();
+        let diverged = imp.applied_profile.diverges(active);
+        for (radio, profile) in imp.profile_radios.borrow().iter().zip(PowerProfile::ALL) {
+            bind::mark_diverged(radio, diverged && profile == active);
+        }
+    }
+
     fn setup_ui(&self) {
         let imp = self.imp();
 
@@ -119,12 +271,25 @@ impl PowerPage {
             }
 
             // Connect toggled handler to set profile
-            let profile_clone = profile;
+            let group_clone = current_group.clone();
+            let known_profile = imp.known_profile.clone();
+            let guard = imp.updating.clone();
+            let page_weak = self.downgrade();
             radio.connect_toggled(move |button| {
-                if button.is_active() {
-                    if let Err(e) = backend::set_profile(profile_clone) {
-                        eprintln!("Failed to set profile: {e}");
-                    }
+                if button.is_active() && !guard.is_active() {
+                    known_profile.set(profile);
+                    let page_weak = page_weak.clone();
+                    bind::apply_setter_then(
+                        &group_clone,
+                        "Failed to set profile",
+                        move || backend::set_profile(profile),
+                        move |()| {
+                            if let Some(page) = page_weak.upgrade() {
+                                page.imp().applied_profile.set(profile);
+                                page.mark_profile(profile);
+                            }
+                        },
+                    );
                 }
             });
 
@@ -147,14 +312,23 @@ impl PowerPage {
 
         let ac_combo = adw::ComboRow::builder()
             .title("Power Profile")
-            .model(&gtk4::StringList::new(&[
-                "Quiet",
-                "Balanced",
-                "Performance",
-            ]))
+            .model(&profile_model())
             .selected(2) // Performance by default on AC
             .build();
 
+        let guard = imp.updating.clone();
+        ac_combo.connect_selected_notify(move |combo| {
+            if guard.is_active() {
+                return;
+            }
+            let Some(&profile) = PowerProfile::ALL.get(combo.selected() as usize) else {
+                return;
+            };
+            bind::apply_setter(combo, "Failed to set AC profile", move || {
+                backend::set_profile_on_ac(profile)
+            });
+        });
+
         imp.ac_combo.replace(Some(ac_combo.clone()));
         ac_group.add(&ac_combo);
         self.append(&ac_group);
@@ -167,18 +341,31 @@ impl PowerPage {
 
         let battery_combo = adw::ComboRow::builder()
             .title("Power Profile")
-            .model(&gtk4::StringList::new(&[
-                "Quiet",
-                "Balanced",
-                "Performance",
-            ]))
+            .model(&profile_model())
             .selected(0) // Quiet by default on battery
             .build();
 
+        let guard = imp.updating.clone();
+        battery_combo.connect_selected_notify(move |combo| {
+            if guard.is_active() {
+                return;
+            }
+            let Some(&profile) = PowerProfile::ALL.get(combo.selected() as usize) else {
+                return;
+            };
+            bind::apply_setter(combo, "Failed to set battery profile", move || {
+                backend::set_profile_on_battery(profile)
+            });
+        });
+
         imp.battery_combo.replace(Some(battery_combo.clone()));
         battery_group.add(&battery_combo);
         self.append(&battery_group);
 
+        self.setup_throttle_policy();
+        self.setup_display();
+        self.setup_graphics_mode();
+
         // Battery settings group
         let battery_settings = adw::PreferencesGroup::builder()
             .title("Battery Settings")
@@ -186,85 +373,620 @@ impl PowerPage {
 
         let charge_limit_row = adw::ActionRow::builder()
             .title("Charge Limit")
-            .subtitle("Limit maximum charge to extend battery lifespan")
+            .subtitle(charge_limit_summary(80))
             .build();
 
-        let charge_scale = gtk4::Scale::builder()
-            .orientation(gtk4::Orientation::Horizontal)
-            .adjustment(&gtk4::Adjustment::new(80.0, 20.0, 100.0, 5.0, 10.0, 0.0))
-            .width_request(200)
-            .valign(gtk4::Align::Center)
-            .draw_value(true)
-            .build();
+        let charge_scale = charge_limit_scale();
 
         // Connect charge scale to set charge limit
-        charge_scale.connect_value_changed(|scale| {
+        let charge_limit_row_clone = charge_limit_row.clone();
+        let known_charge_limit = imp.known_charge_limit.clone();
+        let applied_charge_limit = imp.applied_charge_limit.clone();
+        let guard = imp.updating.clone();
+        charge_scale.connect_value_changed(move |scale| {
             let value = scale.value() as u8;
-            if let Err(e) = backend::set_charge_limit(value) {
-                eprintln!("Failed to set charge limit: {e}");
+            // Preview what the value means while dragging
+            charge_limit_row_clone.set_subtitle(&charge_limit_summary(value));
+            if guard.is_active() {
+                return;
             }
+            known_charge_limit.set(value);
+            let applied_charge_limit = applied_charge_limit.clone();
+            let scale_clone = scale.clone();
+            bind::spawn_setter_then(
+                scale,
+                "Failed to set charge limit",
+                move || backend::set_charge_limit(value),
+                move |()| applied_charge_limit.record(&scale_clone, value),
+            );
         });
 
         imp.charge_scale.replace(Some(charge_scale.clone()));
         charge_limit_row.add_suffix(&charge_scale);
         battery_settings.add(&charge_limit_row);
 
+        let estimate_row = adw::ActionRow::builder()
+            .title("Time Remaining")
+            .subtitle("Calculating…")
+            .build();
+
+        imp.estimate_row.replace(Some(estimate_row.clone()));
+        battery_settings.add(&estimate_row);
+
         self.append(&battery_settings);
+
+        // Filled per battery once a load finds more than one with a limit
+        let charge_limits_group = adw::PreferencesGroup::builder()
+            .title("Charge Limits")
+            .description(CHARGE_LIMITS_DESCRIPTION)
+            .visible(false)
+            .build();
+
+        imp.charge_limits_group
+            .replace(Some(charge_limits_group.clone()));
+        self.append(&charge_limits_group);
+
+        // Filled per battery once the first load finishes
+        let battery_group = adw::PreferencesGroup::builder()
+            .title("Battery Status")
+            .visible(false)
+            .build();
+
+        imp.battery_group.replace(Some(battery_group.clone()));
+        self.append(&battery_group);
+
+        // Filled with whatever sensors the first load finds
+        let sensors_group = adw::PreferencesGroup::builder()
+            .title("Sensors")
+            .visible(false)
+            .build();
+
+        imp.sensors_group.replace(Some(sensors_group.clone()));
+        self.append(&sensors_group);
+    }
+
+    /// Throttle policy combos, hidden until asusctl reports support
+    fn setup_throttle_policy(&self) {
+        let imp = self.imp();
+
+        let group = adw::PreferencesGroup::builder()
+            .title("Throttle Policy")
+            .description("asusd's performance mode, kept independently of the power profile")
+            .visible(false)
+            .build();
+
+        let settings: [(&str, ThrottleGetter, ThrottleSetter); 3] = [
+            (
+                "Active",
+                backend::get_throttle_policy,
+                backend::set_throttle_policy,
+            ),
+            (
+                "On AC Power",
+                backend::get_throttle_policy_on_ac,
+                backend::set_throttle_policy_on_ac,
+            ),
+            (
+                "On Battery",
+                backend::get_throttle_policy_on_battery,
+                backend::set_throttle_policy_on_battery,
+            ),
+        ];
+
+        let mut combos = Vec::new();
+        for (title, getter, setter) in settings {
+            let combo = adw::ComboRow::builder()
+                .title(title)
+                .model(&profile_model())
+                .build();
+
+            let guard = imp.updating.clone();
+            combo.connect_selected_notify(move |combo| {
+                if guard.is_active() {
+                    return;
+                }
+                let Some(&policy) = PowerProfile::ALL.get(combo.selected() as usize) else {
+                    return;
+                };
+                bind::apply_setter(combo, "Failed to set throttle policy", move || {
+                    setter(policy)
+                });
+            });
+
+            group.add(&combo);
+            combos.push((combo, getter));
+        }
+
+        imp.throttle_combos.replace(combos);
+        imp.throttle_group.replace(Some(group.clone()));
+        self.append(&group);
+    }
+
+    /// Display settings, hidden until the model is known to have them
+    fn setup_display(&self) {
+        let imp = self.imp();
+
+        let group = adw::PreferencesGroup::builder()
+            .title("Display")
+            .visible(false)
+            .build();
+
+        let overdrive_row = adw::SwitchRow::builder()
+            .title("Panel Overdrive")
+            .subtitle("Faster pixel response, with some overshoot artifacts")
+            .build();
+
+        let guard = imp.updating.clone();
+        overdrive_row.connect_active_notify(move |switch| {
+            if guard.is_active() {
+                return;
+            }
+            bind::apply_switch(
+                switch,
+                &guard,
+                "Failed to set panel overdrive",
+                backend::set_panel_overdrive,
+            );
+        });
+
+        group.add(&overdrive_row);
+        imp.overdrive_switch.replace(Some(overdrive_row));
+        self.append(&group);
+    }
+
+    /// Graphics mode combo, hidden unless supergfxd is running
+    fn setup_graphics_mode(&self) {
+        let imp = self.imp();
+
+        let group = adw::PreferencesGroup::builder()
+            .title("Graphics")
+            .description("GPU mode switched by supergfxd")
+            .visible(false)
+            .build();
+
+        // Filled with the supported modes on load
+        let combo = adw::ComboRow::builder()
+            .title("Graphics Mode")
+            .model(&gtk4::StringList::new(&[]))
+            .build();
+
+        let guard = imp.updating.clone();
+        let page_weak = self.downgrade();
+        combo.connect_selected_notify(move |combo| {
+            if guard.is_active() {
+                return;
+            }
+            let Some(page) = page_weak.upgrade() else {
+                return;
+            };
+            let Some(&mode) = page.imp().gfx_modes.borrow().get(combo.selected() as usize) else {
+                return;
+            };
+            if page.imp().gfx_mode.get() != Some(mode) {
+                page.confirm_gfx_mode(mode);
+            }
+        });
+
+        group.add(&combo);
+        imp.gfx_combo.replace(Some(combo));
+        imp.gfx_group.replace(Some(group.clone()));
+        self.append(&group);
+    }
+
+    /// Ask before switching, since the switch usually ends the session
+    fn confirm_gfx_mode(&self, mode: GfxMode) {
+        let dialog = adw::AlertDialog::builder()
+            .heading(format!("Switch to {mode} Graphics?"))
+            .body("Applications using the dedicated GPU may be closed, and you will usually need to log out for the switch to finish.")
+            .close_response("cancel")
+            .default_response("cancel")
+            .build();
+        dialog.add_responses(&[("cancel", "Cancel"), ("switch", "Switch")]);
+        dialog.set_response_appearance("switch", adw::ResponseAppearance::Destructive);
+
+        let page_weak = self.downgrade();
+        dialog.connect_response(None, move |_, response| {
+            let Some(page) = page_weak.upgrade() else {
+                return;
+            };
+            let Some(combo) = page.imp().gfx_combo.borrow().clone() else {
+                return;
+            };
+
+            if response != "switch" {
+                page.select_gfx_mode();
+                return;
+            }
+
+            let combo_clone = combo.clone();
+            let page_weak = page.downgrade();
+            bind::apply_setter_then_else(
+                &combo,
+                "Failed to switch graphics mode",
+                move || backend::set_gfx_mode(mode),
+                move |action| bind::show_toast(&combo_clone, &action.to_string()),
+                // Back to the mode supergfxd is still in
+                move || {
+                    if let Some(page) = page_weak.upgrade() {
+                        page.select_gfx_mode();
+                    }
+                },
+            );
+        });
+
+        dialog.present(Some(self));
+    }
+
+    /// Select the mode supergfxd last reported in the combo
+    fn select_gfx_mode(&self) {
+        let imp = self.imp();
+        let _guard = imp.updating.hold();
+
+        if let Some(combo) = imp.gfx_combo.borrow().as_ref()
+            && let Some(mode) = imp.gfx_mode.get()
+            && let Some(index) = imp.gfx_modes.borrow().iter().position(|m| *m == mode)
+        {
+            combo.set_selected(index as u32);
+        }
     }
 
     /// Refresh/reload all data on this page
     fn refresh_data(&self) {
         let imp = self.imp();
 
+        // asusctl is only asked about throttle policy support once
+        let check_throttle = !imp.throttle_checked.get();
+        let read_throttle = check_throttle
+            || imp
+                .throttle_group
+                .borrow()
+                .as_ref()
+                .is_some_and(|group| group.is_visible());
+        let throttle_getters: Vec<ThrottleGetter> = imp
+            .throttle_combos
+            .borrow()
+            .iter()
+            .map(|(_, getter)| *getter)
+            .collect();
+
+        imp.loader.load(
+            self,
+            move || PowerState::read(check_throttle, read_throttle, &throttle_getters),
+            |page, state| page.apply_state(state),
+        );
+    }
+
+    fn apply_state(&self, state: PowerState) {
+        let imp = self.imp();
+        let _guard = imp.updating.hold();
+
         // Get current profile state via CLI (more reliable mapping)
-        match backend::get_profile_state() {
-            Ok(state) => {
+        match state.profiles.map(|profiles| profiles.active) {
+            Ok(Some(active)) => {
                 let radios = imp.profile_radios.borrow();
-                let index = match state.active {
+                let index = match active {
                     PowerProfile::Quiet => 0,
                     PowerProfile::Balanced => 1,
                     PowerProfile::Performance => 2,
                 };
 
                 if let Some(radio) = radios.get(index) {
+                    let changed = imp.known_profile.update(active);
                     radio.set_active(true);
+                    self.mark_profile(active);
+                    if changed {
+                        bind::flash_changed(radio);
+                    }
                 }
-
-                // Set AC combo
-                if let Some(combo) = imp.ac_combo.borrow().as_ref() {
-                    let ac_index = match state.on_ac {
-                        PowerProfile::Quiet => 0,
-                        PowerProfile::Balanced => 1,
-                        PowerProfile::Performance => 2,
-                    };
-                    combo.set_selected(ac_index);
-                }
-
-                // Set battery combo
-                if let Some(combo) = imp.battery_combo.borrow().as_ref() {
-                    let bat_index = match state.on_battery {
-                        PowerProfile::Quiet => 0,
-                        PowerProfile::Balanced => 1,
-                        PowerProfile::Performance => 2,
-                    };
-                    combo.set_selected(bat_index);
-                }
+            }
+            // Keep showing the last known profile rather than guessing one
+            Ok(None) => {
+                log::warn!("asusctl didn't report a known active profile");
             }
             Err(e) => {
-                eprintln!("Failed to get profile state: {e}");
+                log::warn!("Failed to get profile state: {e}");
+            }
+        }
+
+        // Each combo sits alone in its group, so hide the whole group
+        for (combo, context, profile) in [
+            (
+                &imp.ac_combo,
+                "Failed to get AC profile",
+                state.profile_on_ac,
+            ),
+            (
+                &imp.battery_combo,
+                "Failed to get battery profile",
+                state.profile_on_battery,
+            ),
+        ] {
+            if let Some(combo) = combo.borrow().as_ref()
+                && let Some(group) = combo.ancestor(adw::PreferencesGroup::static_type())
+                && let Some(profile) = bind::read_or_hide(&group, context, profile)
+                && let Some(index) = PowerProfile::ALL.iter().position(|p| *p == profile)
+            {
+                combo.set_selected(index as u32);
             }
         }
 
         // Load charge limit via D-Bus
-        if let Some(scale) = imp.charge_scale.borrow().as_ref() {
-            match backend::get_charge_limit_dbus() {
-                Ok(limit) => {
-                    scale.set_value(limit as f64);
+        if let Some(scale) = imp.charge_scale.borrow().as_ref()
+            && let Some(limit) =
+                bind::read_or_hide(scale, "Failed to get charge limit", state.charge_limit)
+        {
+            let changed = imp.known_charge_limit.update(limit);
+            scale.set_value(limit as f64);
+            imp.applied_charge_limit.check(scale, limit);
+            if changed {
+                bind::flash_changed(scale);
+            }
+        }
+
+        if let Some(supported) = state.throttle_supported {
+            imp.throttle_checked.set(true);
+            if let Some(group) = imp.throttle_group.borrow().as_ref() {
+                group.set_visible(supported);
+            }
+        }
+        for ((combo, _), policy) in imp.throttle_combos.borrow().iter().zip(state.throttle) {
+            if let Some(policy) = bind::read_or_hide(combo, "Failed to get throttle policy", policy)
+                && let Some(index) = PowerProfile::ALL.iter().position(|p| *p == policy)
+            {
+                combo.set_selected(index as u32);
+            }
+        }
+
+        // The Display group only holds this row
+        if let Some(switch) = imp.overdrive_switch.borrow().as_ref()
+            && let Some(group) = switch.ancestor(adw::PreferencesGroup::static_type())
+            && let Some(enabled) = bind::read_or_hide(
+                &group,
+                "Failed to get panel overdrive",
+                state.panel_overdrive,
+            )
+        {
+            switch.set_active(enabled);
+        }
+
+        self.show_gfx_modes(state.gfx_modes, state.gfx_mode);
+        self.show_batteries(state.batteries);
+        self.show_sensors(state.sensors);
+
+        // Hidden on machines without a battery; without UPower there is no estimate
+        if let Some(row) = imp.estimate_row.borrow().as_ref() {
+            match bind::read_or_hide(row, "Failed to get battery estimate", state.estimate) {
+                Some(estimate) => row.set_subtitle(&estimate.to_string()),
+                None if row.is_visible() => row.set_subtitle("Unavailable"),
+                None => {}
+            }
+        }
+    }
+
+    fn show_gfx_modes(&self, modes: backend::Result<Vec<GfxMode>>, mode: backend::Result<GfxMode>) {
+        let imp = self.imp();
+        let (Some(group), Some(combo)) = (
+            imp.gfx_group.borrow().clone(),
+            imp.gfx_combo.borrow().clone(),
+        ) else {
+            return;
+        };
+        let Some(modes) = bind::read_or_hide(&group, "Failed to get graphics modes", modes) else {
+            return;
+        };
+
+        group.set_visible(!modes.is_empty());
+        if *imp.gfx_modes.borrow() != modes {
+            let names: Vec<String> = modes.iter().map(|m| m.to_string()).collect();
+            let names: Vec<&str> = names.iter().map(String::as_str).collect();
+            combo.set_model(Some(&gtk4::StringList::new(&names)));
+            imp.gfx_modes.replace(modes);
+        }
+
+        match mode {
+            Ok(mode) => {
+                imp.gfx_mode.set(Some(mode));
+                self.select_gfx_mode();
+            }
+            Err(e) => log::warn!("Failed to get graphics mode: {e}"),
+        }
+    }
+
+    /// Fill the battery status rows, hiding the group without batteries
+    fn show_batteries(&self, batteries: backend::Result<Vec<backend::BatteryInfo>>) {
+        let imp = self.imp();
+        let Some(group) = imp.battery_group.borrow().clone() else {
+            return;
+        };
+        let batteries = match batteries {
+            Ok(batteries) => batteries,
+            Err(e) => {
+                log::warn!("Failed to get battery info: {e}");
+                // Without the list it's unknown which limits to offer
+                if let Some(limits_group) = imp.charge_limits_group.borrow().as_ref() {
+                    limits_group.set_description(Some(&format!(
+                        "Couldn't tell which batteries have a charge limit: {e}"
+                    )));
+                    limits_group.set_visible(true);
                 }
-                Err(e) => {
-                    eprintln!("Failed to get charge limit: {e}");
+                return;
+            }
+        };
+
+        group.set_visible(!batteries.is_empty());
+
+        // Rebuild only when a battery appears or goes away
+        let mut rows = imp.battery_rows.borrow_mut();
+        if rows.len() != batteries.len() * BATTERY_ROWS {
+            for row in rows.drain(..) {
+                group.remove(&row);
+            }
+            for _ in 0..batteries.len() * BATTERY_ROWS {
+                let row = adw::ActionRow::new();
+                group.add(&row);
+                rows.push(row);
+            }
+        }
+
+        self.show_charge_limits(&batteries);
+
+        let several = batteries.len() > 1;
+        for (battery, rows) in batteries.iter().zip(rows.chunks(BATTERY_ROWS)) {
+            let title = |label: &str| {
+                if several {
+                    format!("{label} ({})", battery.name)
+                } else {
+                    label.to_string()
                 }
+            };
+            let charge = match battery.capacity {
+                Some(capacity) => format!("{capacity}% · {}", battery.status),
+                None => battery.status.clone(),
+            };
+            let cycles = battery
+                .cycle_count
+                .map_or_else(|| "Not reported".to_string(), |count| count.to_string());
+            let health = battery.health.map_or_else(
+                || "Not reported".to_string(),
+                |health| format!("{health}% of design capacity"),
+            );
+
+            for (row, (label, value)) in rows.iter().zip([
+                ("Charge", charge),
+                ("Cycle Count", cycles),
+                ("Health", health),
+            ]) {
+                row.set_title(&title(label));
+                row.set_subtitle(&value);
+            }
+        }
+    }
+
+    /// Give each battery its own charge limit control when several have one
+    ///
+    /// asusd manages a single battery, so otherwise the D-Bus backed
+    /// control in Battery Settings is kept.
+    fn show_charge_limits(&self, batteries: &[backend::BatteryInfo]) {
+        let imp = self.imp();
+        let Some(group) = imp.charge_limits_group.borrow().clone() else {
+            return;
+        };
+
+        let limits: Vec<(&str, u8)> = batteries
+            .iter()
+            .filter_map(|battery| Some((battery.name.as_str(), battery.charge_limit?)))
+            .collect();
+        let several = limits.len() > 1;
+        group.set_description(Some(CHARGE_LIMITS_DESCRIPTION));
+        group.set_visible(several);
+        if !several {
+            return;
+        }
+
+        // Runs after the single control was loaded, so this hides it for good
+        if let Some(scale) = imp.charge_scale.borrow().as_ref()
+            && let Some(row) = scale.ancestor(adw::PreferencesRow::static_type())
+        {
+            row.set_visible(false);
+        }
+
+        // Rebuild only when a battery appears or goes away
+        let mut controls = imp.battery_charge_limits.borrow_mut();
+        if !controls
+            .iter()
+            .map(|control| control.name.as_str())
+            .eq(limits.iter().map(|(name, _)| *name))
+        {
+            for control in controls.drain(..) {
+                group.remove(&control.row);
+            }
+            for (name, _) in &limits {
+                let control = self.new_battery_charge_limit(name);
+                group.add(&control.row);
+                controls.push(control);
+            }
+        }
+
+        for (control, (_, limit)) in controls.iter().zip(limits) {
+            control.scale.set_value(limit as f64);
+            control.row.set_subtitle(&charge_limit_summary(limit));
+        }
+    }
+
+    fn new_battery_charge_limit(&self, name: &str) -> BatteryChargeLimit {
+        let row = adw::ActionRow::builder()
+            .title(format!("Charge Limit ({name})"))
+            .build();
+        let scale = charge_limit_scale();
+
+        let row_weak = row.downgrade();
+        let guard = self.imp().updating.clone();
+        let commit = Rc::new(Debouncer::default());
+        let battery = name.to_string();
+        scale.connect_value_changed(move |scale| {
+            let value = scale.value() as u8;
+            if let Some(row) = row_weak.upgrade() {
+                row.set_subtitle(&charge_limit_summary(value));
+            }
+            if guard.is_active() {
+                return;
             }
+
+            let scale = scale.clone();
+            let battery = battery.clone();
+            commit.call(BATTERY_CHARGE_LIMIT_DEBOUNCE, move || {
+                bind::spawn_privileged_setter(&scale, "Failed to set charge limit", move || {
+                    backend::set_battery_charge_limit(&battery, value)
+                });
+            });
+        });
+
+        row.add_suffix(&scale);
+        BatteryChargeLimit {
+            name: name.to_string(),
+            row,
+            scale,
+        }
+    }
+
+    /// Fill the sensor rows, leaving out sensors the machine lacks
+    fn show_sensors(&self, sensors: backend::Result<backend::SensorReadings>) {
+        let imp = self.imp();
+        let Some(group) = imp.sensors_group.borrow().clone() else {
+            return;
+        };
+        let Some(sensors) = bind::read_or_hide(&group, "Failed to read sensors", sensors) else {
+            return;
+        };
+
+        let mut readings = Vec::new();
+        for (label, temp) in [("CPU", sensors.cpu_temp), ("GPU", sensors.gpu_temp)] {
+            if let Some(temp) = temp {
+                readings.push((format!("{label} Temperature"), format!("{temp:.0} °C")));
+            }
+        }
+        for fan in &sensors.fans {
+            readings.push((fan.title(), format!("{} RPM", fan.rpm)));
+        }
+
+        group.set_visible(!readings.is_empty());
+
+        // Rebuild only when a sensor appears or goes away
+        let mut rows = imp.sensor_rows.borrow_mut();
+        if rows.len() != readings.len() {
+            for row in rows.drain(..) {
+                group.remove(&row);
+            }
+            for _ in 0..readings.len() {
+                let row = adw::ActionRow::new();
+                group.add(&row);
+                rows.push(row);
+            }
+        }
+
+        for (row, (title, value)) in rows.iter().zip(readings) {
+            row.set_title(&title);
+            row.set_subtitle(&value);
         }
     }
 }
@@ -279,4 +1001,8 @@ impl Refreshable for PowerPage {
     fn refresh(&self) {
         self.refresh_data();
     }
+
+    fn loader(&self) -> Rc<PageLoader> {
+        self.imp().loader.clone()
+    }
 }
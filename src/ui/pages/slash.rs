@@ -1,12 +1,16 @@
 use adw::prelude::*;
+use gtk4::gio;
 use gtk4::glib;
 use gtk4::prelude::*;
 use gtk4::subclass::prelude::*;
 use libadwaita as adw;
 use std::cell::RefCell;
+use std::rc::Rc;
+use std::time::Duration;
 
 use crate::backend::{self, SlashMode};
 use crate::ui::Refreshable;
+use crate::ui::bind::{self, Debouncer, KnownValue, PageLoader, UpdateGuard};
 
 mod imp {
     use super::*;
@@ -22,6 +26,13 @@ mod imp {
         pub show_on_sleep: RefCell<Option<adw::SwitchRow>>,
         pub show_on_battery: RefCell<Option<adw::SwitchRow>>,
         pub show_battery_warning: RefCell<Option<adw::SwitchRow>>,
+        /// Raised while `refresh_data` writes hardware state into the widgets
+        pub updating: Rc<UpdateGuard>,
+        pub known_brightness: Rc<KnownValue<u8>>,
+        pub known_mode: Rc<KnownValue<SlashMode>>,
+        /// Pending brightness write, restarted on every scale movement
+        pub brightness_commit: Rc<Debouncer>,
+        pub loader: Rc<PageLoader>,
     }
 
     #[glib::object_subclass]
@@ -43,30 +54,45 @@ mod imp {
     impl BoxImpl for SlashPage {}
 }
 
+/// Everything the page shows, read off the main thread
+struct SlashState {
+    enabled: backend::Result<bool>,
+    brightness: backend::Result<u8>,
+    mode: backend::Result<SlashMode>,
+    interval: backend::Result<u8>,
+    /// Boot, shutdown, sleep, battery and battery warning, in that order
+    show_on: [backend::Result<bool>; 5],
+}
+
+impl SlashState {
+    fn read() -> Self {
+        Self {
+            enabled: backend::get_slash_enabled(),
+            brightness: backend::get_slash_brightness(),
+            mode: backend::get_slash_mode(),
+            interval: backend::get_slash_interval(),
+            // Older asusd versions lack some of these
+            show_on: [
+                backend::get_slash_show_on_boot(),
+                backend::get_slash_show_on_shutdown(),
+                backend::get_slash_show_on_sleep(),
+                backend::get_slash_show_on_battery(),
+                backend::get_slash_show_battery_warning(),
+            ],
+        }
+    }
+}
+
 glib::wrapper! {
     pub struct SlashPage(ObjectSubclass<imp::SlashPage>)
         @extends gtk4::Box, gtk4::Widget,
         @implements gtk4::Accessible, gtk4::Buildable, gtk4::ConstraintTarget, gtk4::Orientable;
 }
 
-// Mode names in order (index matches SlashMode enum variant order)
-const SLASH_MODES: &[(&str, &str)] = &[
-    ("Bounce", "Bouncing light effect"),
-    ("Slash", "Slashing light animation"),
-    ("Loading", "Progress bar style animation"),
-    ("BitStream", "Digital data stream effect"),
-    ("Transmission", "Data transmission visualization"),
-    ("Flow", "Flowing light effect"),
-    ("Flux", "Pulsing light pattern"),
-    ("Phantom", "Ghostly fading effect"),
-    ("Spectrum", "Color spectrum animation"),
-    ("Hazard", "Warning/hazard style flashing"),
-    ("Interfacing", "Interface connection visualization"),
-    ("Ramp", "Ramping up/down brightness"),
-    ("GameOver", "Game over animation"),
-    ("Start", "Startup animation"),
-    ("Buzzer", "Alert/notification style animation"),
-];
+/// Quiet time after the last brightness change before it is written
+const BRIGHTNESS_DEBOUNCE: Duration = Duration::from_millis(200);
+
+type ShowOnSetter = fn(bool) -> backend::Result<()>;
 
 impl SlashPage {
     pub fn new() -> Self {
@@ -110,16 +136,38 @@ impl SlashPage {
             .build();
 
         // Connect the switch to enable/disable slash
-        enable_row.connect_active_notify(|switch| {
-            let result = if switch.is_active() {
-                backend::enable_slash()
-            } else {
-                backend::disable_slash()
-            };
-
-            if let Err(e) = result {
-                eprintln!("Failed to toggle slash: {e}");
+        let guard = imp.updating.clone();
+        let page_weak = self.downgrade();
+        let settings = gio::Settings::new("com.github.bl4ckspell7.asusctl-gui");
+        enable_row.connect_active_notify(move |switch| {
+            if guard.is_active() {
+                return;
             }
+
+            let active = switch.is_active();
+            let link_keyboard = settings.boolean("link-keyboard-slash");
+            // The switch shows "off" at brightness 0, so switching on alone
+            // would leave the bar dark; bring back a visible brightness too
+            let restore_brightness = active
+                && page_weak
+                    .upgrade()
+                    .is_some_and(|page| page.restore_brightness_if_dark());
+
+            bind::apply_setter(switch, "Failed to toggle slash", move || {
+                if active {
+                    backend::enable_slash()?;
+                    if restore_brightness {
+                        backend::set_slash_brightness(backend::SLASH_DEFAULT_BRIGHTNESS)?;
+                    }
+                } else {
+                    backend::disable_slash()?;
+                }
+
+                if link_keyboard {
+                    backend::sync_keyboard_to_slash(active)?;
+                }
+                Ok(())
+            });
         });
 
         imp.enable_switch.replace(Some(enable_row.clone()));
@@ -129,25 +177,43 @@ impl SlashPage {
         // Brightness group
         let brightness_group = adw::PreferencesGroup::builder().title("Brightness").build();
 
-        let brightness_row = adw::ActionRow::builder()
-            .title("Brightness Level")
-            .subtitle("0-255")
-            .build();
+        let brightness_row = adw::ActionRow::builder().title("Brightness Level").build();
 
+        // Arrow keys move by 5, Page Up/Down by 25; dragging stays at 1
         let brightness_scale = gtk4::Scale::builder()
             .orientation(gtk4::Orientation::Horizontal)
-            .adjustment(&gtk4::Adjustment::new(128.0, 0.0, 255.0, 1.0, 10.0, 0.0))
+            .adjustment(&gtk4::Adjustment::new(128.0, 0.0, 255.0, 5.0, 25.0, 0.0))
+            .round_digits(0)
+            .digits(0)
             .width_request(200)
             .valign(gtk4::Align::Center)
             .draw_value(true)
             .build();
+        // The scale holds the raw byte that is written; show it as a percentage
+        brightness_scale.set_format_value_func(|_, value| {
+            format!("{:.0}%", value / f64::from(u8::MAX) * 100.0)
+        });
 
         // Connect brightness scale to set slash brightness
-        brightness_scale.connect_value_changed(|scale| {
+        let guard = imp.updating.clone();
+        let page_weak = self.downgrade();
+        let known_brightness = imp.known_brightness.clone();
+        brightness_scale.connect_value_changed(move |scale| {
+            if guard.is_active() {
+                return;
+            }
+
+            let Some(page) = page_weak.upgrade() else {
+                return;
+            };
+
             let value = scale.value() as u8;
-            if let Err(e) = backend::set_slash_brightness(value) {
-                eprintln!("Failed to set slash brightness: {e}");
+            known_brightness.set(value);
+            // A dark bar is shown as disabled, same as in refresh_data
+            if value == 0 {
+                page.sync_enable_switch(false);
             }
+            page.commit_brightness_later(value);
         });
 
         imp.brightness_scale.replace(Some(brightness_scale.clone()));
@@ -159,38 +225,38 @@ impl SlashPage {
         // Mode group
         let mode_group = adw::PreferencesGroup::builder().title("Animation").build();
 
-        // Create mode names list for combo
-        let mode_names: Vec<&str> = SLASH_MODES.iter().map(|(name, _)| *name).collect();
+        // Create mode names list for combo, in SlashMode::ALL order
+        let mode_names: Vec<String> = SlashMode::ALL.iter().map(|m| m.to_string()).collect();
+        let mode_names: Vec<&str> = mode_names.iter().map(String::as_str).collect();
         let mode_combo = adw::ComboRow::builder()
             .title("Mode")
-            .subtitle("Animation style")
+            .subtitle(SlashMode::ALL[0].description())
             .model(&gtk4::StringList::new(&mode_names))
             .build();
 
-        // Connect mode combo to set slash mode
+        // Describe the selected mode, whether the user or a refresh picked it
         mode_combo.connect_selected_notify(|combo| {
-            let mode = match combo.selected() {
-                0 => SlashMode::Bounce,
-                1 => SlashMode::Slash,
-                2 => SlashMode::Loading,
-                3 => SlashMode::BitStream,
-                4 => SlashMode::Transmission,
-                5 => SlashMode::Flow,
-                6 => SlashMode::Flux,
-                7 => SlashMode::Phantom,
-                8 => SlashMode::Spectrum,
-                9 => SlashMode::Hazard,
-                10 => SlashMode::Interfacing,
-                11 => SlashMode::Ramp,
-                12 => SlashMode::GameOver,
-                13 => SlashMode::Start,
-                14 => SlashMode::Buzzer,
-                _ => return,
-            };
+            if let Some(mode) = SlashMode::from_index(combo.selected()) {
+                combo.set_subtitle(mode.description());
+            }
+        });
 
-            if let Err(e) = backend::set_slash_mode(mode) {
-                eprintln!("Failed to set slash mode: {e}");
+        // Connect mode combo to set slash mode
+        let guard = imp.updating.clone();
+        let known_mode = imp.known_mode.clone();
+        mode_combo.connect_selected_notify(move |combo| {
+            if guard.is_active() {
+                return;
             }
+
+            let Some(mode) = SlashMode::from_index(combo.selected()) else {
+                return;
+            };
+            known_mode.set(mode);
+
+            bind::apply_setter(combo, "Failed to set slash mode", move || {
+                backend::set_slash_mode(mode)
+            });
         });
 
         imp.mode_combo.replace(Some(mode_combo.clone()));
@@ -199,17 +265,25 @@ impl SlashPage {
         // Interval/speed combo
         let interval_combo = adw::ComboRow::builder()
             .title("Speed")
-            .subtitle("Animation interval (0 = fastest, 5 = slowest)")
-            .model(&gtk4::StringList::new(&["0", "1", "2", "3", "4", "5"]))
+            .subtitle("How quickly the animation plays")
+            .model(&gtk4::StringList::new(&backend::SLASH_SPEED_LABELS))
             .selected(0)
             .build();
 
         // Connect interval combo to set slash interval
-        interval_combo.connect_selected_notify(|combo| {
-            let interval = combo.selected() as u8;
-            if let Err(e) = backend::set_slash_interval(interval) {
-                eprintln!("Failed to set slash interval: {e}");
+        let guard = imp.updating.clone();
+        interval_combo.connect_selected_notify(move |combo| {
+            if guard.is_active() {
+                return;
             }
+
+            // The combo lists one speed per interval
+            let Ok(interval) = u8::try_from(combo.selected()) else {
+                return;
+            };
+            bind::apply_setter(combo, "Failed to set slash interval", move || {
+                backend::set_slash_interval(interval)
+            });
         });
 
         imp.interval_combo.replace(Some(interval_combo.clone()));
@@ -222,171 +296,298 @@ impl SlashPage {
             .description("When to display slash animations")
             .build();
 
-        // Show on boot
         let show_on_boot = adw::SwitchRow::builder()
             .title("Boot")
             .subtitle("Show animation when laptop boots")
             .build();
-        show_on_boot.connect_active_notify(|switch| {
-            if let Err(e) = backend::set_slash_show_on_boot(switch.is_active()) {
-                eprintln!("Failed to set show on boot: {e}");
-            }
-        });
-        imp.show_on_boot.replace(Some(show_on_boot.clone()));
-        events_group.add(&show_on_boot);
-
-        // Show on shutdown
         let show_on_shutdown = adw::SwitchRow::builder()
             .title("Shutdown")
             .subtitle("Show animation when laptop shuts down")
             .build();
-        show_on_shutdown.connect_active_notify(|switch| {
-            if let Err(e) = backend::set_slash_show_on_shutdown(switch.is_active()) {
-                eprintln!("Failed to set show on shutdown: {e}");
-            }
-        });
-        imp.show_on_shutdown.replace(Some(show_on_shutdown.clone()));
-        events_group.add(&show_on_shutdown);
-
-        // Show on sleep
         let show_on_sleep = adw::SwitchRow::builder()
             .title("Sleep")
             .subtitle("Show animation when laptop sleeps")
             .build();
-        show_on_sleep.connect_active_notify(|switch| {
-            if let Err(e) = backend::set_slash_show_on_sleep(switch.is_active()) {
-                eprintln!("Failed to set show on sleep: {e}");
-            }
-        });
-        imp.show_on_sleep.replace(Some(show_on_sleep.clone()));
-        events_group.add(&show_on_sleep);
-
-        // Show on battery
         let show_on_battery = adw::SwitchRow::builder()
             .title("Battery")
             .subtitle("Show animation when on battery power")
             .build();
-        show_on_battery.connect_active_notify(|switch| {
-            if let Err(e) = backend::set_slash_show_on_battery(switch.is_active()) {
-                eprintln!("Failed to set show on battery: {e}");
-            }
-        });
-        imp.show_on_battery.replace(Some(show_on_battery.clone()));
-        events_group.add(&show_on_battery);
-
-        // Show battery warning
         let show_battery_warning = adw::SwitchRow::builder()
             .title("Low Battery Warning")
             .subtitle("Show animation when battery is low")
             .build();
-        show_battery_warning.connect_active_notify(|switch| {
-            if let Err(e) = backend::set_slash_show_battery_warning(switch.is_active()) {
-                eprintln!("Failed to set show battery warning: {e}");
+
+        let events: [(&adw::SwitchRow, &'static str, &'static str, ShowOnSetter); 5] = [
+            (
+                &show_on_boot,
+                "Boot animation",
+                "Failed to set show on boot",
+                backend::set_slash_show_on_boot,
+            ),
+            (
+                &show_on_shutdown,
+                "Shutdown animation",
+                "Failed to set show on shutdown",
+                backend::set_slash_show_on_shutdown,
+            ),
+            (
+                &show_on_sleep,
+                "Sleep animation",
+                "Failed to set show on sleep",
+                backend::set_slash_show_on_sleep,
+            ),
+            (
+                &show_on_battery,
+                "Battery animation",
+                "Failed to set show on battery",
+                backend::set_slash_show_on_battery,
+            ),
+            (
+                &show_battery_warning,
+                "Low battery warning",
+                "Failed to set show battery warning",
+                backend::set_slash_show_battery_warning,
+            ),
+        ];
+        for (switch, name, error_context, setter) in events {
+            self.connect_show_on(switch, name, error_context, setter);
+            events_group.add(switch);
+        }
+
+        imp.show_on_boot.replace(Some(show_on_boot));
+        imp.show_on_shutdown.replace(Some(show_on_shutdown));
+        imp.show_on_sleep.replace(Some(show_on_sleep));
+        imp.show_on_battery.replace(Some(show_on_battery));
+        imp.show_battery_warning.replace(Some(show_battery_warning));
+
+        self.append(&events_group);
+
+        let reset_button = gtk4::Button::builder()
+            .label("Reset to Defaults")
+            .css_classes(["destructive-action"])
+            .halign(gtk4::Align::End)
+            .build();
+
+        let page_weak = self.downgrade();
+        reset_button.connect_clicked(move |button| {
+            if let Some(page) = page_weak.upgrade() {
+                page.confirm_reset(button);
             }
         });
-        imp.show_battery_warning
-            .replace(Some(show_battery_warning.clone()));
-        events_group.add(&show_battery_warning);
 
-        self.append(&events_group);
+        self.append(&reset_button);
     }
 
-    /// Refresh/reload all data on this page
-    fn refresh_data(&self) {
-        let imp = self.imp();
-
-        // Load enabled state from config file
-        if let Some(switch) = imp.enable_switch.borrow().as_ref() {
-            match backend::get_slash_enabled() {
-                Ok(enabled) => {
-                    switch.set_active(enabled);
-                }
-                Err(e) => {
-                    eprintln!("Failed to get slash enabled state: {e}");
-                }
+    /// Write a show-on switch when the user flips it
+    fn connect_show_on(
+        &self,
+        switch: &adw::SwitchRow,
+        name: &'static str,
+        error_context: &'static str,
+        setter: ShowOnSetter,
+    ) {
+        let guard = self.imp().updating.clone();
+        let page_weak = self.downgrade();
+        switch.connect_active_notify(move |switch| {
+            if guard.is_active() {
+                return;
             }
+
+            let active = switch.is_active();
+            let page_weak = page_weak.clone();
+            bind::apply_setter_then(
+                switch,
+                error_context,
+                move || setter(active),
+                move |()| {
+                    if let Some(page) = page_weak.upgrade() {
+                        page.confirm_show_on(name, active);
+                    }
+                },
+            );
+        });
+    }
+
+    /// Confirm a show-on change, which has no visible effect until the event
+    fn confirm_show_on(&self, name: &str, active: bool) {
+        let imp = self.imp();
+        let all_off = [
+            &imp.show_on_boot,
+            &imp.show_on_shutdown,
+            &imp.show_on_sleep,
+            &imp.show_on_battery,
+            &imp.show_battery_warning,
+        ]
+        .iter()
+        .all(|switch| switch.borrow().as_ref().is_none_or(|s| !s.is_active()));
+
+        if all_off {
+            bind::show_toast(self, "No events show an animation any more");
+        } else {
+            let state = if active { "enabled" } else { "disabled" };
+            bind::show_toast(self, &format!("{name} {state}"));
         }
+    }
 
-        // Load brightness from config file
-        if let Some(scale) = imp.brightness_scale.borrow().as_ref() {
-            match backend::get_slash_brightness() {
-                Ok(brightness) => {
-                    scale.set_value(brightness as f64);
-                }
-                Err(e) => {
-                    eprintln!("Failed to get slash brightness: {e}");
-                }
+    /// Ask before putting power, brightness and animation back to the defaults
+    fn confirm_reset(&self, button: &gtk4::Button) {
+        let dialog = adw::AlertDialog::builder()
+            .heading("Reset Slash Lighting?")
+            .body(format!(
+                "The LED bar will be switched on with the {} animation at the default brightness and speed. The show-on settings are kept.",
+                backend::SLASH_DEFAULT_MODE
+            ))
+            .close_response("cancel")
+            .default_response("cancel")
+            .build();
+        dialog.add_responses(&[("cancel", "Cancel"), ("reset", "Reset")]);
+        dialog.set_response_appearance("reset", adw::ResponseAppearance::Destructive);
+
+        let page_weak = self.downgrade();
+        let button = button.clone();
+        dialog.connect_response(None, move |_, response| {
+            if response != "reset" {
+                return;
             }
-        }
 
-        // Load mode from config file
-        if let Some(combo) = imp.mode_combo.borrow().as_ref() {
-            match backend::get_slash_mode() {
-                Ok(mode) => {
-                    let index = match mode {
-                        SlashMode::Bounce => 0,
-                        SlashMode::Slash => 1,
-                        SlashMode::Loading => 2,
-                        SlashMode::BitStream => 3,
-                        SlashMode::Transmission => 4,
-                        SlashMode::Flow => 5,
-                        SlashMode::Flux => 6,
-                        SlashMode::Phantom => 7,
-                        SlashMode::Spectrum => 8,
-                        SlashMode::Hazard => 9,
-                        SlashMode::Interfacing => 10,
-                        SlashMode::Ramp => 11,
-                        SlashMode::GameOver => 12,
-                        SlashMode::Start => 13,
-                        SlashMode::Buzzer => 14,
-                    };
-                    combo.set_selected(index);
-                }
-                Err(e) => {
-                    eprintln!("Failed to get slash mode: {e}");
+            let page_weak = page_weak.clone();
+            bind::apply_setter_then(
+                &button,
+                "Failed to reset slash settings",
+                backend::reset_slash_defaults,
+                move |()| {
+                    if let Some(page) = page_weak.upgrade() {
+                        // Our own change, so don't flag it as external
+                        let imp = page.imp();
+                        imp.known_brightness.set(backend::SLASH_DEFAULT_BRIGHTNESS);
+                        imp.known_mode.set(backend::SLASH_DEFAULT_MODE);
+                        page.refresh_data();
+                    }
+                },
+            );
+        });
+
+        dialog.present(Some(self));
+    }
+
+    /// Write `value` to the bar once the scale has stopped moving
+    ///
+    /// Every intermediate value of a drag would otherwise spawn its own
+    /// asusctl process and make the bar flicker.
+    fn commit_brightness_later(&self, value: u8) {
+        let page_weak = self.downgrade();
+        self.imp()
+            .brightness_commit
+            .call(BRIGHTNESS_DEBOUNCE, move || {
+                let Some(page) = page_weak.upgrade() else {
+                    return;
+                };
+
+                if let Some(scale) = page.imp().brightness_scale.borrow().as_ref() {
+                    bind::spawn_setter(scale, "Failed to set slash brightness", move || {
+                        backend::set_slash_brightness(value)
+                    });
                 }
-            }
+            });
+    }
+
+    /// Set the enable switch without triggering its handler
+    fn sync_enable_switch(&self, active: bool) {
+        let imp = self.imp();
+        let _guard = imp.updating.hold();
+        if let Some(switch) = imp.enable_switch.borrow().as_ref() {
+            switch.set_active(active);
         }
+    }
 
-        // Load interval from config file
-        if let Some(combo) = imp.interval_combo.borrow().as_ref() {
-            match backend::get_slash_interval() {
-                Ok(interval) => {
-                    combo.set_selected(interval as u32);
-                }
-                Err(e) => {
-                    eprintln!("Failed to get slash interval: {e}");
-                }
-            }
+    /// Move the brightness scale to the default if it sits at 0
+    ///
+    /// Returns true when the scale was moved and the backend brightness
+    /// needs to follow.
+    fn restore_brightness_if_dark(&self) -> bool {
+        let imp = self.imp();
+        let Some(scale) = imp.brightness_scale.borrow().clone() else {
+            return false;
+        };
+        if scale.value() > 0.0 {
+            return false;
         }
 
-        // Load show-on states from D-Bus
-        if let Some(switch) = imp.show_on_boot.borrow().as_ref() {
-            if let Ok(value) = backend::get_slash_show_on_boot() {
-                switch.set_active(value);
-            }
+        let _guard = imp.updating.hold();
+        scale.set_value(backend::SLASH_DEFAULT_BRIGHTNESS as f64);
+        true
+    }
+
+    /// Refresh/reload all data on this page
+    fn refresh_data(&self) {
+        self.imp()
+            .loader
+            .load(self, SlashState::read, |page, state| {
+                page.apply_state(state)
+            });
+    }
+
+    /// Show freshly read state
+    ///
+    /// Brightness 0 turns the LED bar dark even while `Enabled` is true, so
+    /// the enable switch is only shown as on when both agree.
+    fn apply_state(&self, state: SlashState) {
+        let imp = self.imp();
+        let _guard = imp.updating.hold();
+
+        let brightness = imp.brightness_scale.borrow().as_ref().and_then(|scale| {
+            bind::read_or_hide(scale, "Failed to get slash brightness", state.brightness)
+        });
+
+        // Load enabled state from config file
+        if let Some(switch) = imp.enable_switch.borrow().as_ref()
+            && let Some(enabled) =
+                bind::read_or_hide(switch, "Failed to get slash enabled state", state.enabled)
+        {
+            switch.set_active(enabled && brightness != Some(0));
         }
 
-        if let Some(switch) = imp.show_on_shutdown.borrow().as_ref() {
-            if let Ok(value) = backend::get_slash_show_on_shutdown() {
-                switch.set_active(value);
+        // Load brightness from config file
+        if let Some(scale) = imp.brightness_scale.borrow().as_ref()
+            && let Some(brightness) = brightness
+        {
+            scale.set_value(brightness as f64);
+            if imp.known_brightness.update(brightness) {
+                bind::flash_changed(scale);
             }
         }
 
-        if let Some(switch) = imp.show_on_sleep.borrow().as_ref() {
-            if let Ok(value) = backend::get_slash_show_on_sleep() {
-                switch.set_active(value);
+        // Load mode (D-Bus preferred, config fallback)
+        if let Some(combo) = imp.mode_combo.borrow().as_ref()
+            && let Some(mode) = bind::read_or_hide(combo, "Failed to get slash mode", state.mode)
+        {
+            combo.set_selected(mode.index());
+            if imp.known_mode.update(mode) {
+                bind::flash_changed(combo);
             }
         }
 
-        if let Some(switch) = imp.show_on_battery.borrow().as_ref() {
-            if let Ok(value) = backend::get_slash_show_on_battery() {
-                switch.set_active(value);
-            }
+        if let Some(combo) = imp.interval_combo.borrow().as_ref()
+            && let Some(interval) =
+                bind::read_or_hide(combo, "Failed to get slash interval", state.interval)
+            && interval <= backend::SLASH_MAX_INTERVAL
+        {
+            combo.set_selected(interval.into());
         }
 
-        if let Some(switch) = imp.show_battery_warning.borrow().as_ref() {
-            if let Ok(value) = backend::get_slash_show_battery_warning() {
+        // Load show-on states from D-Bus
+        let show_on_rows = [
+            &imp.show_on_boot,
+            &imp.show_on_shutdown,
+            &imp.show_on_sleep,
+            &imp.show_on_battery,
+            &imp.show_battery_warning,
+        ];
+        for (row, value) in show_on_rows.into_iter().zip(state.show_on) {
+            if let Some(switch) = row.borrow().as_ref()
+                && let Some(value) =
+                    bind::read_or_hide(switch, "Failed to get slash show-on state", value)
+            {
                 switch.set_active(value);
             }
         }
@@ -403,4 +604,8 @@ impl Refreshable for SlashPage {
     fn refresh(&self) {
         self.refresh_data();
     }
+
+    fn loader(&self) -> Rc<PageLoader> {
+        self.imp().loader.clone()
+    }
 }
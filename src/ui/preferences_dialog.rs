@@ -5,6 +5,8 @@ use gtk4::subclass::prelude::*;
 use libadwaita as adw;
 
 use super::Page;
+use super::window::MIN_REFRESH_INTERVAL;
+use crate::backend::{KeyboardBrightness, PowerProfile};
 
 mod imp {
     use super::*;
@@ -133,13 +135,13 @@ impl PreferencesDialog {
         // Create the Refresh group
         let refresh_group = adw::PreferencesGroup::builder().title("General").build();
 
-        // Create refresh interval spin row (0.1-10.0 seconds)
+        // Create refresh interval spin row (0.1-10.0 seconds, 0 = off)
         let refresh_interval_row = adw::SpinRow::builder()
             .title("Update Interval")
-            .subtitle("In seconds")
+            .subtitle("In seconds, 0 turns automatic updates off")
             .adjustment(&gtk4::Adjustment::new(
                 0.5,  // default value
-                0.1,  // min
+                0.0,  // min
                 10.0, // max
                 0.1,  // step increment
                 1.0,  // page increment
@@ -148,19 +150,198 @@ impl PreferencesDialog {
             .digits(2)
             .build();
 
+        // Show 0 as "Off" and accept "Off" as input
+        refresh_interval_row.connect_output(|spin_row| {
+            if spin_row.value() > 0.0 {
+                return false;
+            }
+            spin_row.set_text("Off");
+            true
+        });
+        refresh_interval_row.connect_input(|spin_row| {
+            spin_row
+                .text()
+                .trim()
+                .eq_ignore_ascii_case("off")
+                .then_some(Ok(0.0))
+        });
+
         // Load current refresh interval
         let current_interval = settings.double("refresh-interval");
         refresh_interval_row.set_value(current_interval);
 
-        // Connect refresh interval change
-        let settings_clone = settings;
+        // Connect refresh interval change; values between off and the minimum snap up
+        let settings_clone = settings.clone();
         refresh_interval_row.connect_value_notify(move |spin_row| {
-            let _ = settings_clone.set_double("refresh-interval", spin_row.value());
+            let value = spin_row.value();
+            if value > 0.0 && value < MIN_REFRESH_INTERVAL {
+                spin_row.set_value(MIN_REFRESH_INTERVAL);
+                return;
+            }
+            let _ = settings_clone.set_double("refresh-interval", value);
         });
 
+        let reduce_animations_row = adw::SwitchRow::builder()
+            .title("Reduce Animations")
+            .subtitle("Turn off page transitions, e.g. for remote sessions")
+            .build();
+        settings
+            .bind("reduce-animations", &reduce_animations_row, "active")
+            .build();
+
+        let brightness_slider_row = adw::SwitchRow::builder()
+            .title("Keyboard Brightness Slider")
+            .subtitle("Use a slider instead of one button per level")
+            .build();
+        settings
+            .bind("brightness-as-slider", &brightness_slider_row, "active")
+            .build();
+
+        let background_row = adw::SwitchRow::builder()
+            .title("Run in Background")
+            .subtitle("Keep a status icon with the power profiles after closing the window")
+            .build();
+        settings
+            .bind("run-in-background", &background_row, "active")
+            .build();
+
+        let notify_row = adw::SwitchRow::builder()
+            .title("Notify on External Changes")
+            .subtitle("Show a notification when a hotkey changes the profile or charge limit")
+            .build();
+        settings
+            .bind("notify-on-change", &notify_row, "active")
+            .build();
+
         refresh_group.add(&refresh_interval_row);
+        refresh_group.add(&reduce_animations_row);
+        refresh_group.add(&brightness_slider_row);
+        refresh_group.add(&background_row);
+        refresh_group.add(&notify_row);
         general_page.add(&refresh_group);
 
+        // Create the Apply on Startup group
+        let apply_group = adw::PreferencesGroup::builder()
+            .title("Apply on Startup")
+            .description("Re-apply settings for hardware that forgets them across reboots")
+            .build();
+
+        let apply_row = adw::SwitchRow::builder()
+            .title("Apply settings on startup")
+            .subtitle("Set the profile and keyboard brightness below when the app starts")
+            .build();
+
+        let profile_titles: Vec<String> = PowerProfile::ALL.iter().map(|p| p.to_string()).collect();
+        let profile_titles: Vec<&str> = profile_titles.iter().map(String::as_str).collect();
+        let startup_profile_row = adw::ComboRow::builder()
+            .title("Power Profile")
+            .model(&gtk4::StringList::new(&profile_titles))
+            .build();
+
+        let brightness_titles = KeyboardBrightness::ALL.map(|level| level.label());
+        let startup_brightness_row = adw::ComboRow::builder()
+            .title("Keyboard Brightness")
+            .model(&gtk4::StringList::new(&brightness_titles))
+            .build();
+
+        // Set initial state
+        let apply_on_startup = settings.boolean("apply-on-startup");
+        apply_row.set_active(apply_on_startup);
+        startup_profile_row.set_sensitive(apply_on_startup);
+        startup_brightness_row.set_sensitive(apply_on_startup);
+
+        let startup_profile = settings
+            .string("startup-profile")
+            .parse::<PowerProfile>()
+            .unwrap_or_default();
+        if let Some(index) = PowerProfile::ALL.iter().position(|p| *p == startup_profile) {
+            startup_profile_row.set_selected(index as u32);
+        }
+
+        let startup_brightness = settings
+            .string("startup-keyboard-brightness")
+            .parse::<KeyboardBrightness>()
+            .unwrap_or(KeyboardBrightness::Med);
+        if let Some(index) = KeyboardBrightness::ALL
+            .iter()
+            .position(|b| *b == startup_brightness)
+        {
+            startup_brightness_row.set_selected(index as u32);
+        }
+
+        // Connect apply-on-startup switch
+        let settings_clone = settings.clone();
+        let profile_row_clone = startup_profile_row.clone();
+        let brightness_row_clone = startup_brightness_row.clone();
+        apply_row.connect_active_notify(move |switch| {
+            let active = switch.is_active();
+            let _ = settings_clone.set_boolean("apply-on-startup", active);
+            profile_row_clone.set_sensitive(active);
+            brightness_row_clone.set_sensitive(active);
+        });
+
+        // Connect startup profile combo
+        let settings_clone = settings.clone();
+        startup_profile_row.connect_selected_notify(move |combo| {
+            if let Some(profile) = PowerProfile::ALL.get(combo.selected() as usize) {
+                let value = profile.to_string().to_lowercase();
+                let _ = settings_clone.set_string("startup-profile", &value);
+            }
+        });
+
+        // Connect startup keyboard brightness combo
+        let settings_clone = settings.clone();
+        startup_brightness_row.connect_selected_notify(move |combo| {
+            if let Some(level) = KeyboardBrightness::ALL.get(combo.selected() as usize) {
+                let _ =
+                    settings_clone.set_string("startup-keyboard-brightness", &level.to_string());
+            }
+        });
+
+        apply_group.add(&apply_row);
+        apply_group.add(&startup_profile_row);
+        apply_group.add(&startup_brightness_row);
+        general_page.add(&apply_group);
+
+        // Create the Integration group
+        let integration_group = adw::PreferencesGroup::builder()
+            .title("Integration")
+            .build();
+
+        let sync_profiles_row = adw::SwitchRow::builder()
+            .title("Sync GNOME power profile")
+            .subtitle("Keep the system power profile and the asusd profile in sync")
+            .build();
+        settings
+            .bind("sync-power-profiles", &sync_profiles_row, "active")
+            .build();
+
+        let link_lighting_row = adw::SwitchRow::builder()
+            .title("Link keyboard and slash lighting")
+            .subtitle("Switching one off or on also switches the other")
+            .build();
+        settings
+            .bind("link-keyboard-slash", &link_lighting_row, "active")
+            .build();
+
+        integration_group.add(&sync_profiles_row);
+        integration_group.add(&link_lighting_row);
+        general_page.add(&integration_group);
+
+        // Create the Advanced group
+        let advanced_group = adw::PreferencesGroup::builder().title("Advanced").build();
+
+        let developer_mode_row = adw::SwitchRow::builder()
+            .title("Developer Mode")
+            .subtitle("Show raw hardware values next to controls")
+            .build();
+        settings
+            .bind("developer-mode", &developer_mode_row, "active")
+            .build();
+
+        advanced_group.add(&developer_mode_row);
+        general_page.add(&advanced_group);
+
         self.add(&general_page);
     }
 }
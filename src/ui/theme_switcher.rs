@@ -40,6 +40,15 @@ impl Default for ThemeSwitcher {
     }
 }
 
+/// Map the `color-scheme` setting to a style, following the system by default
+pub fn color_scheme_from_setting(value: &str) -> adw::ColorScheme {
+    match value {
+        "light" => adw::ColorScheme::ForceLight,
+        "dark" => adw::ColorScheme::ForceDark,
+        _ => adw::ColorScheme::Default,
+    }
+}
+
 impl ThemeSwitcher {
     pub fn new() -> Self {
         glib::Object::builder().build()
@@ -84,21 +93,11 @@ impl ThemeSwitcher {
         dark_btn.add_css_class("theme-selector");
         dark_btn.add_css_class("dark");
 
-        // Load saved setting and apply
-        let saved_scheme = settings.string("color-scheme");
-        match saved_scheme.as_str() {
-            "light" => {
-                light_btn.set_active(true);
-                style_manager.set_color_scheme(adw::ColorScheme::ForceLight);
-            }
-            "dark" => {
-                dark_btn.set_active(true);
-                style_manager.set_color_scheme(adw::ColorScheme::ForceDark);
-            }
-            _ => {
-                system_btn.set_active(true);
-                style_manager.set_color_scheme(adw::ColorScheme::Default);
-            }
+        // The app applies the saved scheme on startup; only reflect it here
+        match color_scheme_from_setting(&settings.string("color-scheme")) {
+            adw::ColorScheme::ForceLight => light_btn.set_active(true),
+            adw::ColorScheme::ForceDark => dark_btn.set_active(true),
+            _ => system_btn.set_active(true),
         }
 
         // Connect signals with settings persistence
@@ -134,3 +133,29 @@ impl ThemeSwitcher {
         self.append(&dark_btn);
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_color_scheme_from_setting() {
+        assert_eq!(
+            color_scheme_from_setting("light"),
+            adw::ColorScheme::ForceLight
+        );
+        assert_eq!(
+            color_scheme_from_setting("dark"),
+            adw::ColorScheme::ForceDark
+        );
+        assert_eq!(
+            color_scheme_from_setting("system"),
+            adw::ColorScheme::Default
+        );
+        // Unknown values from older versions follow the system
+        assert_eq!(
+            color_scheme_from_setting("sepia"),
+            adw::ColorScheme::Default
+        );
+    }
+}
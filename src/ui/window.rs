@@ -4,16 +4,29 @@ use gtk4::glib;
 use gtk4::prelude::*;
 use gtk4::subclass::prelude::*;
 use libadwaita as adw;
+use std::rc::Rc;
 use std::time::Duration;
 
+use crate::backend::{self, AsusctlError, PowerProfile, ServiceState, SupportedFeatures};
+
 use super::{
-    AboutPage, AuraPage, Page, PowerPage, PreferencesDialog, Refreshable, SlashPage, ThemeSwitcher,
+    APP_VERSION, AboutPage, AnimePage, AuraPage, FanCurvesPage, Page, PowerPage, PreferencesDialog,
+    SlashPage, ThemeSwitcher, bind,
 };
 
+/// What the service banner's button does
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum BannerAction {
+    #[default]
+    Retry,
+    StartService,
+    OpenInstallDocs,
+}
+
 mod imp {
     use super::*;
     use adw::subclass::prelude::*;
-    use std::cell::RefCell;
+    use std::cell::{Cell, RefCell};
 
     #[derive(Debug, Default)]
     pub struct AsusctlGuiWindow {
@@ -21,13 +34,18 @@ mod imp {
         pub stack: RefCell<Option<gtk4::Stack>>,
         pub sidebar_list: RefCell<Option<gtk4::ListBox>>,
         pub settings: RefCell<Option<gio::Settings>>,
-        // Store direct references to pages for refresh
-        pub about_page: RefCell<Option<AboutPage>>,
-        pub aura_page: RefCell<Option<AuraPage>>,
-        pub power_page: RefCell<Option<PowerPage>>,
-        pub slash_page: RefCell<Option<SlashPage>>,
         // Track refresh timer source ID
         pub refresh_source_id: RefCell<Option<glib::SourceId>>,
+        pub service_banner: RefCell<Option<adw::Banner>>,
+        pub content_toolbar: RefCell<Option<adw::ToolbarView>>,
+        // Page content, replaced by a status page on unsupported hardware
+        pub content_scroll: RefCell<Option<gtk4::ScrolledWindow>>,
+        // Pending startup availability check (aborted when the window closes)
+        pub availability_check: RefCell<Option<glib::JoinHandle<()>>>,
+        // Periodic re-check while the service banner is shown
+        pub service_retry_id: RefCell<Option<glib::SourceId>>,
+        // What the banner button does for the current error
+        pub banner_action: Cell<BannerAction>,
     }
 
     #[glib::object_subclass]
@@ -45,7 +63,19 @@ mod imp {
     }
 
     impl WidgetImpl for AsusctlGuiWindow {}
-    impl WindowImpl for AsusctlGuiWindow {}
+
+    impl WindowImpl for AsusctlGuiWindow {
+        fn close_request(&self) -> glib::Propagation {
+            if let Some(handle) = self.availability_check.take() {
+                handle.abort();
+            }
+            if let Some(source_id) = self.service_retry_id.take() {
+                source_id.remove();
+            }
+            self.parent_close_request()
+        }
+    }
+
     impl ApplicationWindowImpl for AsusctlGuiWindow {}
     impl AdwApplicationWindowImpl for AsusctlGuiWindow {}
 }
@@ -58,6 +88,18 @@ glib::wrapper! {
                     gio::ActionGroup, gio::ActionMap;
 }
 
+// Startup availability check: asusd may still be starting right after login
+const AVAILABILITY_CHECK_ATTEMPTS: u32 = 4;
+const AVAILABILITY_CHECK_INITIAL_DELAY: Duration = Duration::from_millis(500);
+/// How often to look for asusd again while it is unavailable
+const SERVICE_RETRY_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Shortcuts for `win.set-profile`, in `PowerProfile::ALL` order
+pub const PROFILE_ACCELS: [&str; 3] = ["<Control>1", "<Control>2", "<Control>3"];
+
+/// Shortest refresh interval in seconds; only exactly 0 turns polling off
+pub(super) const MIN_REFRESH_INTERVAL: f64 = 0.1;
+
 impl AsusctlGuiWindow {
     pub fn new(app: &adw::Application) -> Self {
         glib::Object::builder()
@@ -69,10 +111,17 @@ impl AsusctlGuiWindow {
     }
 
     /// Start a periodic timer that refreshes the visible page
+    ///
+    /// An interval of 0 means polling is turned off and no timer is started;
+    /// anything else is at least `MIN_REFRESH_INTERVAL`.
     fn start_refresh_timer(&self, interval_secs: f64) {
+        if interval_secs <= 0.0 {
+            return;
+        }
+
         let imp = self.imp();
         let window_weak = self.downgrade();
-        let millis = (interval_secs * 1000.0) as u64;
+        let millis = (interval_secs.max(MIN_REFRESH_INTERVAL) * 1000.0) as u64;
 
         let source_id = glib::timeout_add_local(Duration::from_millis(millis), move || {
             let Some(window) = window_weak.upgrade() else {
@@ -99,44 +148,307 @@ impl AsusctlGuiWindow {
         self.start_refresh_timer(interval_secs);
     }
 
-    /// Refresh the currently visible page
-    fn refresh_visible_page(&self) {
-        let imp = self.imp();
+    /// Refresh the currently visible page, returning its loader
+    fn refresh_visible_page(&self) -> Option<Rc<bind::PageLoader>> {
+        let stack = self.imp().stack.borrow().as_ref().cloned()?;
+        let name = stack.visible_child_name()?;
+        let page = Page::try_from(name.as_str()).ok()?;
 
-        let Some(stack) = imp.stack.borrow().as_ref().cloned() else {
-            return;
-        };
+        page.refresh_in_stack(&stack)
+    }
+
+    /// Re-read everything for the visible page, including the cached system info
+    ///
+    /// `action` stays disabled until the page has finished loading, so
+    /// repeated presses don't pile up reads.
+    fn manual_refresh(&self, action: &gio::SimpleAction) {
+        action.set_enabled(false);
 
-        let Some(name) = stack.visible_child_name() else {
+        let window_weak = self.downgrade();
+        let action = action.clone();
+        glib::spawn_future_local(async move {
+            // Queued, so reads already in flight finish against the old cache
+            if backend::queue::submit(backend::invalidate_session_cache)
+                .await
+                .is_err()
+            {
+                log::error!("Invalidating the session cache panicked");
+            }
+            let loader = window_weak
+                .upgrade()
+                .and_then(|window| window.refresh_visible_page());
+            match loader {
+                Some(loader) => loader.when_idle(move || action.set_enabled(true)),
+                None => action.set_enabled(true),
+            }
+        });
+    }
+
+    /// Switch to `page` through the sidebar, as if the user picked it
+    pub fn show_page(&self, page: Page) {
+        if let Some(sidebar_list) = self.imp().sidebar_list.borrow().as_ref()
+            && let Some(row) = sidebar_list.row_at_index(page.index() as i32)
+        {
+            sidebar_list.select_row(Some(&row));
+        }
+    }
+
+    fn refresh_all_pages(&self) {
+        let Some(stack) = self.imp().stack.borrow().as_ref().cloned() else {
             return;
         };
 
-        let Ok(page) = Page::try_from(name.as_str()) else {
+        for page in Page::ALL {
+            page.refresh_in_stack(&stack);
+        }
+    }
+
+    /// Check service availability, retrying with backoff before showing the banner
+    ///
+    /// With `resync`, the visible page is reloaded once the service answers,
+    /// even on the first attempt.
+    fn check_service_availability(&self, resync: bool) {
+        if let Some(previous) = self.imp().availability_check.take() {
+            previous.abort();
+        }
+
+        let window_weak = self.downgrade();
+
+        let handle = glib::spawn_future_local(async move {
+            let mut delay = AVAILABILITY_CHECK_INITIAL_DELAY;
+
+            for attempt in 1..=AVAILABILITY_CHECK_ATTEMPTS {
+                let result = gio::spawn_blocking(backend::check_availability)
+                    .await
+                    .unwrap_or_else(|_| {
+                        Err(AsusctlError::CommandFailed(
+                            "availability check panicked".to_string(),
+                        ))
+                    });
+
+                let Some(window) = window_weak.upgrade() else {
+                    return;
+                };
+
+                match result {
+                    Ok(()) => {
+                        let was_unavailable = window.service_unavailable();
+                        // asusd may have been restarted or upgraded meanwhile
+                        if was_unavailable || resync {
+                            backend::invalidate_session_cache();
+                        }
+                        window.set_service_available(None);
+                        window.check_hardware_support();
+                        // Pages loaded while the service was down show stale errors
+                        if was_unavailable {
+                            window.refresh_all_pages();
+                        } else if attempt > 1 || resync {
+                            window.refresh_visible_page();
+                        }
+                        return;
+                    }
+                    Err(e) if attempt == AVAILABILITY_CHECK_ATTEMPTS => {
+                        window.set_service_available(Some(&e));
+                        return;
+                    }
+                    Err(_) => {}
+                }
+
+                drop(window);
+                glib::timeout_future(delay).await;
+                delay *= 2;
+            }
+        });
+
+        self.imp().availability_check.replace(Some(handle));
+    }
+
+    fn service_unavailable(&self) -> bool {
+        self.imp()
+            .service_banner
+            .borrow()
+            .as_ref()
+            .is_some_and(|banner| banner.is_revealed())
+    }
+
+    /// Show the service banner for `error`, or hide it when `None`
+    ///
+    /// While the banner is up, every page but About is insensitive and asusd
+    /// is looked for again periodically, so the banner goes away on its own
+    /// once the service is back.
+    fn set_service_available(&self, error: Option<&AsusctlError>) {
+        let imp = self.imp();
+        let Some(banner) = imp.service_banner.borrow().clone() else {
             return;
         };
 
-        match page {
-            Page::About => {
-                if let Some(p) = imp.about_page.borrow().as_ref() {
-                    p.refresh();
+        match error {
+            Some(e) => {
+                banner.set_title(&e.to_string());
+                banner.set_revealed(true);
+                if matches!(e, AsusctlError::NotInstalled) {
+                    imp.banner_action.set(BannerAction::OpenInstallDocs);
+                    banner.set_button_label(Some("Install Guide"));
+                } else {
+                    self.update_banner_action();
                 }
+                self.start_service_retry();
             }
-            Page::Aura => {
-                if let Some(p) = imp.aura_page.borrow().as_ref() {
-                    p.refresh();
+            None => {
+                banner.set_revealed(false);
+                if let Some(source_id) = imp.service_retry_id.take() {
+                    source_id.remove();
                 }
             }
-            Page::Power => {
-                if let Some(p) = imp.power_page.borrow().as_ref() {
-                    p.refresh();
+        }
+
+        if let Some(stack) = imp.stack.borrow().as_ref() {
+            for page in Page::ALL.into_iter().filter(|page| *page != Page::About) {
+                if let Some(child) = stack.child_by_name(page.as_str()) {
+                    child.set_sensitive(error.is_none());
                 }
             }
-            Page::Slash => {
-                if let Some(p) = imp.slash_page.borrow().as_ref() {
-                    p.refresh();
+        }
+    }
+
+    /// Offer to start asusd from the banner when systemd reports it stopped
+    ///
+    /// Without systemd, or without an asusd unit, the button only retries.
+    fn update_banner_action(&self) {
+        let window_weak = self.downgrade();
+
+        bind::load_then(backend::get_service_state, move |state| {
+            let Some(window) = window_weak.upgrade() else {
+                return;
+            };
+            let Some(banner) = window.imp().service_banner.borrow().clone() else {
+                return;
+            };
+
+            let action = if matches!(state, Ok(ServiceState::Stopped | ServiceState::Failed)) {
+                banner.set_button_label(Some("Start asusd"));
+                BannerAction::StartService
+            } else {
+                banner.set_button_label(Some("Retry"));
+                BannerAction::Retry
+            };
+            window.imp().banner_action.set(action);
+        });
+    }
+
+    fn start_service_retry(&self) {
+        let imp = self.imp();
+        if imp.service_retry_id.borrow().is_some() {
+            return;
+        }
+
+        let window_weak = self.downgrade();
+        let source_id = glib::timeout_add_local(SERVICE_RETRY_INTERVAL, move || {
+            let Some(window) = window_weak.upgrade() else {
+                return glib::ControlFlow::Break;
+            };
+
+            window.check_service_availability(false);
+            glib::ControlFlow::Continue
+        });
+
+        imp.service_retry_id.replace(Some(source_id));
+    }
+
+    /// Replace the pages with a notice when asusd reports no supported features
+    ///
+    /// Only a successful, empty feature list counts; if the query itself fails
+    /// the pages stay up and report their own errors.
+    fn check_hardware_support(&self) {
+        let window_weak = self.downgrade();
+
+        glib::spawn_future_local(async move {
+            let Ok(Ok(features)) = gio::spawn_blocking(backend::get_supported_features).await
+            else {
+                return;
+            };
+
+            let Some(window) = window_weak.upgrade() else {
+                return;
+            };
+
+            if !features.has_any() {
+                window.show_unsupported_hardware();
+                return;
+            }
+            window.show_supported_pages(&features);
+            if let Some(action) = window
+                .lookup_action("set-profile")
+                .and_downcast::<gio::SimpleAction>()
+            {
+                action.set_enabled(features.has_platform);
+            }
+        });
+    }
+
+    /// Show the sidebar entries of the pages `features` support, and only those
+    ///
+    /// Runs after every successful availability check, so pages come back
+    /// when a restarted asusd reports more than before.
+    fn show_supported_pages(&self, features: &SupportedFeatures) {
+        let imp = self.imp();
+
+        // Undo show_unsupported_hardware
+        if let Some(toolbar) = imp.content_toolbar.borrow().as_ref()
+            && let Some(scroll) = imp.content_scroll.borrow().as_ref()
+            && toolbar.content().as_ref() != Some(scroll.upcast_ref())
+        {
+            toolbar.set_content(Some(scroll));
+        }
+
+        let Some(sidebar_list) = imp.sidebar_list.borrow().clone() else {
+            return;
+        };
+        for page in Page::ALL {
+            if let Some(row) = sidebar_list.row_at_index(page.index() as i32) {
+                row.set_visible(page.is_supported(features));
+            }
+        }
+
+        // Don't leave the user on a hidden page
+        if sidebar_list
+            .selected_row()
+            .is_none_or(|row| !row.is_visible())
+            && let Some(about_row) = sidebar_list.row_at_index(Page::About.index() as i32)
+        {
+            sidebar_list.select_row(Some(&about_row));
+        }
+    }
+
+    fn show_unsupported_hardware(&self) {
+        let imp = self.imp();
+
+        // Leave only the About entry, so nothing points at a page that can't work
+        if let Some(sidebar_list) = imp.sidebar_list.borrow().as_ref() {
+            for page in Page::ALL {
+                if let Some(row) = sidebar_list.row_at_index(page.index() as i32) {
+                    row.set_visible(page == Page::About);
+                    if page == Page::About {
+                        sidebar_list.select_row(Some(&row));
+                    }
                 }
             }
         }
+
+        let status_page = adw::StatusPage::builder()
+            .icon_name("computer-fail-symbolic")
+            .title("No supported ASUS features detected")
+            .description(
+                "asusd is running but reports nothing this app can control. \
+                 This machine may not be an ASUS laptop, or the required kernel modules \
+                 may not be loaded.",
+            )
+            .vexpand(true)
+            .build();
+
+        if let Some(toolbar) = imp.content_toolbar.borrow().as_ref() {
+            toolbar.set_content(Some(&status_page));
+        }
     }
 
     fn setup_ui(&self) {
@@ -154,19 +466,21 @@ impl AsusctlGuiWindow {
         let about_page = AboutPage::new();
         let aura_page = AuraPage::new();
         let power_page = PowerPage::new();
+        let fan_curves_page = FanCurvesPage::new();
         let slash_page = SlashPage::new();
+        let anime_page = AnimePage::new();
 
         // Add pages to stack
         stack.add_titled(&about_page, Some(Page::About.as_str()), Page::About.title());
         stack.add_titled(&aura_page, Some(Page::Aura.as_str()), Page::Aura.title());
         stack.add_titled(&power_page, Some(Page::Power.as_str()), Page::Power.title());
+        stack.add_titled(
+            &fan_curves_page,
+            Some(Page::FanCurves.as_str()),
+            Page::FanCurves.title(),
+        );
         stack.add_titled(&slash_page, Some(Page::Slash.as_str()), Page::Slash.title());
-
-        // Store page references for later refresh
-        imp.about_page.replace(Some(about_page));
-        imp.aura_page.replace(Some(aura_page));
-        imp.power_page.replace(Some(power_page));
-        imp.slash_page.replace(Some(slash_page));
+        stack.add_titled(&anime_page, Some(Page::Anime.as_str()), Page::Anime.title());
 
         // Create sidebar with navigation items
         let sidebar_list = gtk4::ListBox::builder()
@@ -201,11 +515,11 @@ impl AsusctlGuiWindow {
         let stack_clone = stack.clone();
         let settings_clone = settings.clone();
         sidebar_list.connect_row_selected(move |_, row| {
-            if let Some(row) = row {
-                if let Some(name) = row.widget_name().as_str().strip_prefix("nav-") {
-                    stack_clone.set_visible_child_name(name);
-                    let _ = settings_clone.set_string("last-page", name);
-                }
+            if let Some(row) = row
+                && let Some(name) = row.widget_name().as_str().strip_prefix("nav-")
+            {
+                stack_clone.set_visible_child_name(name);
+                let _ = settings_clone.set_string("last-page", name);
             }
         });
 
@@ -230,6 +544,9 @@ impl AsusctlGuiWindow {
         let buttons_section = gio::Menu::new();
         buttons_section.append(Some("Preferences"), Some("win.preferences"));
         buttons_section.append(Some("Keyboard Shortcuts"), Some("win.show-shortcuts"));
+        buttons_section.append(Some("Export Settings…"), Some("win.export-settings"));
+        buttons_section.append(Some("Import Settings…"), Some("win.import-settings"));
+        buttons_section.append(Some("Restart asusd"), Some("win.restart-service"));
         buttons_section.append(Some("Quit"), Some("win.quit"));
         buttons_section.append(Some("About"), Some("win.about"));
         menu.append_section(None, &buttons_section);
@@ -242,10 +559,10 @@ impl AsusctlGuiWindow {
             .build();
 
         // Add ThemeSwitcher as custom child to the popover
-        if let Some(popover) = menu_button.popover() {
-            if let Ok(popover_menu) = popover.downcast::<gtk4::PopoverMenu>() {
-                popover_menu.add_child(&ThemeSwitcher::new(), "themeswitcher");
-            }
+        if let Some(popover) = menu_button.popover()
+            && let Ok(popover_menu) = popover.downcast::<gtk4::PopoverMenu>()
+        {
+            popover_menu.add_child(&ThemeSwitcher::new(), "themeswitcher");
         }
 
         // Create sidebar toolbar view with header
@@ -266,6 +583,12 @@ impl AsusctlGuiWindow {
 
         // Create content toolbar view with header
         let content_header = adw::HeaderBar::builder().show_title(false).build();
+        let refresh_button = gtk4::Button::builder()
+            .icon_name("view-refresh-symbolic")
+            .action_name("win.refresh")
+            .tooltip_text("Refresh")
+            .build();
+        content_header.pack_end(&refresh_button);
 
         // Wrap stack in a scrolled window to allow content scrolling
         let content_scroll = gtk4::ScrolledWindow::builder()
@@ -274,8 +597,29 @@ impl AsusctlGuiWindow {
             .child(&stack)
             .build();
 
+        // Banner shown when asusctl/asusd is unavailable
+        let service_banner = adw::Banner::builder()
+            .revealed(false)
+            .button_label("Retry")
+            .build();
+
+        let window_weak = self.downgrade();
+        service_banner.connect_button_clicked(move |_| {
+            let Some(window) = window_weak.upgrade() else {
+                return;
+            };
+            match window.imp().banner_action.get() {
+                BannerAction::Retry => window.check_service_availability(true),
+                BannerAction::StartService => {
+                    ActionGroupExt::activate_action(&window, "start-service", None);
+                }
+                BannerAction::OpenInstallDocs => bind::open_install_docs(&window),
+            }
+        });
+
         let content_toolbar = adw::ToolbarView::new();
         content_toolbar.add_top_bar(&content_header);
+        content_toolbar.add_top_bar(&service_banner);
         content_toolbar.set_content(Some(&content_scroll));
 
         // Create content navigation page
@@ -292,7 +636,11 @@ impl AsusctlGuiWindow {
             .max_sidebar_width(300.0)
             .build();
 
-        self.set_content(Some(&split_view));
+        // Toast overlay for reporting backend errors
+        let toast_overlay = adw::ToastOverlay::new();
+        toast_overlay.set_child(Some(&split_view));
+
+        self.set_content(Some(&toast_overlay));
 
         // Setup actions
         self.setup_actions();
@@ -302,6 +650,11 @@ impl AsusctlGuiWindow {
         imp.stack.replace(Some(stack));
         imp.sidebar_list.replace(Some(sidebar_list));
         imp.settings.replace(Some(settings.clone()));
+        imp.service_banner.replace(Some(service_banner));
+        imp.content_toolbar.replace(Some(content_toolbar));
+        imp.content_scroll.replace(Some(content_scroll));
+
+        self.check_service_availability(false);
 
         // Start refresh timer with interval from settings (in seconds)
         let interval_secs = settings.double("refresh-interval");
@@ -315,6 +668,34 @@ impl AsusctlGuiWindow {
                 window.restart_refresh_timer(new_interval);
             }
         });
+
+        self.apply_reduce_animations(settings.boolean("reduce-animations"));
+        let window_weak = self.downgrade();
+        settings.connect_changed(Some("reduce-animations"), move |settings, key| {
+            if let Some(window) = window_weak.upgrade() {
+                window.apply_reduce_animations(settings.boolean(key));
+            }
+        });
+    }
+
+    /// Turn page transitions and GTK's own animations off or back on
+    fn apply_reduce_animations(&self, reduce: bool) {
+        if let Some(stack) = self.imp().stack.borrow().as_ref() {
+            stack.set_transition_type(if reduce {
+                gtk4::StackTransitionType::None
+            } else {
+                gtk4::StackTransitionType::Crossfade
+            });
+        }
+
+        // Only affects this process; resetting goes back to the desktop's choice
+        if let Some(gtk_settings) = gtk4::Settings::default() {
+            if reduce {
+                gtk_settings.set_gtk_enable_animations(false);
+            } else {
+                gtk_settings.reset_property("gtk-enable-animations");
+            }
+        }
     }
 
     fn setup_actions(&self) {
@@ -342,15 +723,250 @@ impl AsusctlGuiWindow {
         });
         self.add_action(&shortcuts_action);
 
-        // Quit action
+        // Settings file actions
+        let export_action = gio::SimpleAction::new("export-settings", None);
+        let window = self.clone();
+        export_action.connect_activate(move |_, _| {
+            window.export_settings();
+        });
+        self.add_action(&export_action);
+
+        let import_action = gio::SimpleAction::new("import-settings", None);
+        let window = self.clone();
+        import_action.connect_activate(move |_, _| {
+            window.import_settings();
+        });
+        self.add_action(&import_action);
+
+        // Manual refresh action
+        let refresh_action = gio::SimpleAction::new("refresh", None);
+        let window = self.clone();
+        refresh_action.connect_activate(move |action, _| {
+            window.manual_refresh(action);
+        });
+        self.add_action(&refresh_action);
+
+        // Restart asusd action
+        let restart_action = gio::SimpleAction::new("restart-service", None);
+        let window = self.clone();
+        restart_action.connect_activate(move |_, _| {
+            window.confirm_restart_service();
+        });
+        self.add_action(&restart_action);
+
+        // Start asusd action (no confirmation, nothing is running to interrupt)
+        let start_action = gio::SimpleAction::new("start-service", None);
+        let window = self.clone();
+        start_action.connect_activate(move |_, _| {
+            window.run_service_command(
+                backend::start_service,
+                "asusd started",
+                "Failed to start asusd",
+            );
+        });
+        self.add_action(&start_action);
+
+        // Profile shortcuts, e.g. win.set-profile::quiet
+        let profile_action = gio::SimpleAction::new("set-profile", Some(glib::VariantTy::STRING));
+        let window = self.clone();
+        profile_action.connect_activate(move |_, parameter| {
+            let Some(profile) = parameter
+                .and_then(|p| p.str())
+                .and_then(|name| name.parse::<PowerProfile>().ok())
+            else {
+                return;
+            };
+            window.set_profile(profile);
+        });
+        self.add_action(&profile_action);
+
+        // Quit action; quits the whole app, which may be running in the background
         let quit_action = gio::SimpleAction::new("quit", None);
         let window = self.clone();
-        quit_action.connect_activate(move |_, _| {
-            window.close();
+        quit_action.connect_activate(move |_, _| match window.application() {
+            Some(app) => app.quit(),
+            None => window.close(),
         });
         self.add_action(&quit_action);
     }
 
+    fn confirm_restart_service(&self) {
+        let dialog = adw::AlertDialog::builder()
+            .heading("Restart asusd?")
+            .body("Controls are unavailable while the service restarts. This requires administrator rights.")
+            .close_response("cancel")
+            .default_response("cancel")
+            .build();
+        dialog.add_responses(&[("cancel", "Cancel"), ("restart", "Restart")]);
+        dialog.set_response_appearance("restart", adw::ResponseAppearance::Destructive);
+
+        let window = self.clone();
+        dialog.connect_response(Some("restart"), move |_, _| {
+            window.run_service_command(
+                backend::restart_service,
+                "asusd restarted",
+                "Failed to restart asusd",
+            );
+        });
+
+        dialog.present(Some(self));
+    }
+
+    /// Run a systemctl command for asusd, then resync once the service is back
+    ///
+    /// pkexec may keep a polkit prompt open for a while, so this runs on its
+    /// own thread rather than the backend queue, which would stall every
+    /// setter and page load behind it.
+    fn run_service_command(
+        &self,
+        command: fn() -> backend::Result<()>,
+        success_message: &'static str,
+        error_context: &'static str,
+    ) {
+        let window_weak = self.downgrade();
+
+        glib::spawn_future_local(async move {
+            let result = gio::spawn_blocking(command).await.unwrap_or_else(|_| {
+                Err(AsusctlError::CommandFailed(
+                    "service command panicked".to_string(),
+                ))
+            });
+
+            let Some(window) = window_weak.upgrade() else {
+                return;
+            };
+
+            match result {
+                Ok(()) => {
+                    window.show_toast(success_message);
+                    // asusd takes a moment to register on the bus again
+                    window.check_service_availability(true);
+                }
+                Err(e) => window.show_toast(&format!("{error_context}: {e}")),
+            }
+        });
+    }
+
+    /// File dialog limited to settings files
+    fn settings_file_dialog(title: &str) -> gtk4::FileDialog {
+        let filter = gtk4::FileFilter::new();
+        filter.set_name(Some("Settings files"));
+        filter.add_suffix("json");
+
+        gtk4::FileDialog::builder()
+            .title(title)
+            .modal(true)
+            .default_filter(&filter)
+            .build()
+    }
+
+    /// Save the current settings to a file the user picks
+    fn export_settings(&self) {
+        let dialog = Self::settings_file_dialog("Export Settings");
+        dialog.set_initial_name(Some("asusctl-gui-settings.json"));
+
+        let window = self.clone();
+        dialog.save(Some(self), gio::Cancellable::NONE, move |result| {
+            // An error here means the dialog was dismissed
+            let Some(path) = result.ok().and_then(|file| file.path()) else {
+                return;
+            };
+            let Some(split_view) = window.imp().split_view.borrow().clone() else {
+                return;
+            };
+
+            let window_weak = window.downgrade();
+            bind::apply_setter_then(
+                &split_view,
+                "Failed to export settings",
+                move || backend::export_settings(&path),
+                move |()| {
+                    if let Some(window) = window_weak.upgrade() {
+                        window.show_toast("Settings exported");
+                    }
+                },
+            );
+        });
+    }
+
+    /// Apply settings from a file the user picks, one at a time
+    fn import_settings(&self) {
+        let dialog = Self::settings_file_dialog("Import Settings");
+
+        let window = self.clone();
+        dialog.open(Some(self), gio::Cancellable::NONE, move |result| {
+            let Some(path) = result.ok().and_then(|file| file.path()) else {
+                return;
+            };
+            let Some(split_view) = window.imp().split_view.borrow().clone() else {
+                return;
+            };
+
+            let window_weak = window.downgrade();
+            bind::apply_setter_then(
+                &split_view,
+                "Failed to import settings",
+                move || backend::read_settings_file(&path).map(|backup| backup.apply()),
+                move |failures| {
+                    let Some(window) = window_weak.upgrade() else {
+                        return;
+                    };
+                    if failures.is_empty() {
+                        window.show_toast("Settings imported");
+                    }
+                    for (setting, e) in failures {
+                        window.show_error_toast(&format!("Failed to import {setting}"), &e);
+                    }
+                    window.refresh_visible_page();
+                },
+            );
+        });
+    }
+
+    /// Switch the power profile from anywhere in the window
+    fn set_profile(&self, profile: PowerProfile) {
+        let Some(split_view) = self.imp().split_view.borrow().clone() else {
+            return;
+        };
+
+        let window_weak = self.downgrade();
+        bind::apply_setter_then(
+            &split_view,
+            "Failed to set profile",
+            move || backend::set_profile(profile),
+            move |()| {
+                if let Some(window) = window_weak.upgrade() {
+                    window.show_toast(&format!("{profile} profile"));
+                    window.profile_applied(profile);
+                }
+            },
+        );
+    }
+
+    /// Show a profile the app set without going through the Power page
+    pub fn profile_applied(&self, profile: PowerProfile) {
+        if let Some(stack) = self.imp().stack.borrow().as_ref()
+            && let Some(power_page) = stack
+                .child_by_name(Page::Power.as_str())
+                .and_downcast::<PowerPage>()
+        {
+            power_page.remember_profile(profile);
+        }
+        self.refresh_visible_page();
+    }
+
+    fn show_toast(&self, message: &str) {
+        if let Some(split_view) = self.imp().split_view.borrow().as_ref() {
+            bind::show_toast(split_view, message);
+        }
+    }
+
+    fn show_error_toast(&self, context: &str, error: &AsusctlError) {
+        if let Some(split_view) = self.imp().split_view.borrow().as_ref() {
+            bind::show_error_toast(split_view, context, error);
+        }
+    }
+
     fn show_preferences_dialog(&self) {
         let prefs_dialog = PreferencesDialog::new();
         prefs_dialog.present(Some(self));
@@ -361,7 +977,7 @@ impl AsusctlGuiWindow {
             .application_name("asusctl-gui")
             .application_icon("preferences-other-symbolic")
             .developer_name("Bl4ckspell")
-            .version("0.1.0")
+            .version(APP_VERSION)
             .website("https://github.com/Bl4ckspell7/asusctl-gui")
             .license_type(gtk4::License::Gpl30)
             .build();
@@ -374,6 +990,8 @@ impl AsusctlGuiWindow {
 
         // Create section with items
         let section = adw::ShortcutsSection::new(Some("General"));
+        section.add(adw::ShortcutsItem::new("Preferences", "<Control>comma"));
+        section.add(adw::ShortcutsItem::new("Refresh", "F5 <Control>r"));
         section.add(adw::ShortcutsItem::new("Quit", "<Control>q"));
         section.add(adw::ShortcutsItem::new(
             "Keyboard Shortcuts",
@@ -381,6 +999,13 @@ impl AsusctlGuiWindow {
         ));
 
         shortcuts.add(section);
+
+        let profiles = adw::ShortcutsSection::new(Some("Power Profiles"));
+        for (profile, accel) in PowerProfile::ALL.iter().zip(PROFILE_ACCELS) {
+            profiles.add(adw::ShortcutsItem::new(&profile.to_string(), accel));
+        }
+        shortcuts.add(profiles);
+
         shortcuts.present(Some(self));
     }
 